@@ -118,7 +118,7 @@ fn build_undirected_neighbors(graph: &DiGraph) -> Vec<Vec<usize>> {
     let mut neighbors: Vec<HashSet<usize>> = vec![HashSet::new(); n];
 
     for u in 0..n {
-        for &v in graph.successors_slice(u) {
+        for v in graph.out_neighbors(u) {
             if u != v {
                 // Skip self-loops
                 neighbors[u].insert(v);
@@ -223,7 +223,7 @@ mod tests {
         let mut graph = DiGraph::new();
         let a = graph.add_node("a");
         let b = graph.add_node("b");
-        graph.add_edge(a, b);
+        graph.add_edge(a, b).unwrap();
 
         let ap = articulation_points(&graph);
         // Neither node is an articulation point (removing either leaves isolated node)
@@ -238,8 +238,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let ap = articulation_points(&graph);
         assert_eq!(ap.len(), 1);
@@ -254,9 +254,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let ap = articulation_points(&graph);
         assert!(ap.is_empty());
@@ -271,9 +271,9 @@ mod tests {
         let l1 = graph.add_node("l1");
         let l2 = graph.add_node("l2");
         let l3 = graph.add_node("l3");
-        graph.add_edge(hub, l1);
-        graph.add_edge(hub, l2);
-        graph.add_edge(hub, l3);
+        graph.add_edge(hub, l1).unwrap();
+        graph.add_edge(hub, l2).unwrap();
+        graph.add_edge(hub, l3).unwrap();
 
         let ap = articulation_points(&graph);
         // Hub is articulation point (root with >1 children)
@@ -297,17 +297,17 @@ mod tests {
         let f = graph.add_node("f");
 
         // Left triangle
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         // Bridge
-        graph.add_edge(c, e);
+        graph.add_edge(c, e).unwrap();
 
         // Right triangle
-        graph.add_edge(d, e);
-        graph.add_edge(e, f);
-        graph.add_edge(f, d);
+        graph.add_edge(d, e).unwrap();
+        graph.add_edge(e, f).unwrap();
+        graph.add_edge(f, d).unwrap();
 
         let ap = articulation_points(&graph);
         // c and e are both articulation points
@@ -323,9 +323,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let ap = articulation_points(&graph);
         assert_eq!(ap.len(), 2);
@@ -341,8 +341,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let br = bridges(&graph);
         assert_eq!(br.len(), 2);
@@ -355,9 +355,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let br = bridges(&graph);
         assert!(br.is_empty());
@@ -375,14 +375,14 @@ mod tests {
         let f = graph.add_node("f");
 
         // Triangle 1
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         // Triangle 2
-        graph.add_edge(d, e);
-        graph.add_edge(e, f);
-        graph.add_edge(f, d);
+        graph.add_edge(d, e).unwrap();
+        graph.add_edge(e, f).unwrap();
+        graph.add_edge(f, d).unwrap();
 
         let ap = articulation_points(&graph);
         assert!(ap.is_empty());
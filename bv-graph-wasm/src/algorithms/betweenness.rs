@@ -30,6 +30,38 @@ pub fn betweenness(graph: &DiGraph) -> Vec<f64> {
     bc
 }
 
+/// Exact betweenness, optionally normalized to [0, 1].
+///
+/// Normalizing divides by (n-1)(n-2), the number of ordered pairs of other
+/// nodes in a directed graph, with n counting live nodes only. Graphs with
+/// fewer than three live nodes have all-zero scores either way.
+pub fn betweenness_centrality(graph: &DiGraph, normalized: bool) -> Vec<f64> {
+    let mut bc = betweenness(graph);
+    let live = (0..graph.len()).filter(|&v| !graph.is_removed(v)).count();
+    if normalized && live > 2 {
+        let pairs = ((live - 1) * (live - 2)) as f64;
+        for score in &mut bc {
+            *score /= pairs;
+        }
+    }
+    bc
+}
+
+/// The `k` live nodes with the highest exact betweenness, as (node, score).
+///
+/// Sorted by descending score, ties by ascending index. Scores are not
+/// normalized; the ranking is the same either way.
+pub fn top_betweenness(graph: &DiGraph, k: usize) -> Vec<(usize, f64)> {
+    let bc = betweenness(graph);
+    let mut ranked: Vec<(usize, f64)> = (0..graph.len())
+        .filter(|&v| !graph.is_removed(v))
+        .map(|v| (v, bc[v]))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(k);
+    ranked
+}
+
 /// Compute approximate betweenness using k pivot samples.
 ///
 /// Instead of computing shortest paths from ALL nodes (O(V*E)), we sample k pivot
@@ -52,15 +84,21 @@ pub fn betweenness_approx(graph: &DiGraph, sample_size: usize, seed: Option<u64>
         return Vec::new();
     }
 
+    // Removed nodes can't be pivots; they lie on no paths
+    let live: Vec<usize> = (0..n).filter(|&v| !graph.is_removed(v)).collect();
+
     // For small graphs or when sample size >= node count, use exact algorithm
-    if sample_size >= n {
+    if sample_size >= live.len() {
         return betweenness(graph);
     }
 
     let mut bc = vec![0.0; n];
 
     // Sample k random pivot nodes
-    let pivots = sample_nodes(n, sample_size, seed);
+    let pivots: Vec<usize> = sample_nodes(live.len(), sample_size, seed)
+        .into_iter()
+        .map(|i| live[i])
+        .collect();
 
     // Compute partial betweenness from sampled pivots only
     for &pivot in &pivots {
@@ -69,7 +107,7 @@ pub fn betweenness_approx(graph: &DiGraph, sample_size: usize, seed: Option<u64>
 
     // Scale up: BC_approx = BC_partial * (n / k)
     // This extrapolates from the sample to the full graph
-    let scale = n as f64 / sample_size as f64;
+    let scale = live.len() as f64 / sample_size as f64;
     for score in &mut bc {
         *score *= scale;
     }
@@ -102,7 +140,7 @@ fn single_source_betweenness(graph: &DiGraph, source: usize, bc: &mut [f64]) {
     while let Some(v) = queue.pop_front() {
         stack.push(v);
 
-        for &w in graph.successors_slice(v) {
+        for w in graph.out_neighbors(v) {
             // Path discovery: first visit to w
             if dist[w] < 0 {
                 dist[w] = dist[v] + 1;
@@ -200,7 +238,7 @@ mod tests {
         let mut graph = DiGraph::new();
         let a = graph.add_node("a");
         let b = graph.add_node("b");
-        graph.add_edge(a, b);
+        graph.add_edge(a, b).unwrap();
 
         let bc = betweenness(&graph);
         assert_eq!(bc.len(), 2);
@@ -217,8 +255,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let bc = betweenness(&graph);
         // b is on the path a->c, so betweenness(b) = 1
@@ -229,6 +267,59 @@ mod tests {
         assert!((bc[b] - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_betweenness_centrality_fixture() {
+        // a -> {b, c} -> d -> e: b and c split the a -> d and a -> e paths,
+        // and d lies on every path into e
+        let mut graph = DiGraph::new();
+        for id in ["a", "b", "c", "d", "e"] {
+            graph.add_node(id);
+        }
+        for (u, v) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)] {
+            graph.add_edge(u, v).unwrap();
+        }
+
+        let raw = betweenness_centrality(&graph, false);
+        for (got, want) in raw.iter().zip([0.0, 1.0, 1.0, 3.0, 0.0]) {
+            assert!((got - want).abs() < 1e-9, "{} != {}", got, want);
+        }
+        let normalized = betweenness_centrality(&graph, true);
+        for (got, want) in normalized.iter().zip(&raw) {
+            assert!((got - want / 12.0).abs() < 1e-9);
+        }
+        assert_eq!(top_betweenness(&graph, 2), vec![(3, 3.0), (1, 1.0)]);
+    }
+
+    #[test]
+    fn test_top_betweenness_path_and_star() {
+        // Path p0 -> p1 -> p2 -> p3 -> p4: the middle node carries most paths
+        let mut path = DiGraph::new();
+        for i in 0..5 {
+            path.add_node(&format!("p{}", i));
+        }
+        for i in 0..4 {
+            path.add_edge(i, i + 1).unwrap();
+        }
+        assert_eq!(top_betweenness(&path, 1), vec![(2, 4.0)]);
+
+        // Star: two leaves feed the center, which feeds two more
+        let mut star = DiGraph::new();
+        let center = star.add_node("center");
+        for i in 0..4 {
+            let leaf = star.add_node(&format!("leaf{}", i));
+            if i < 2 {
+                star.add_edge(leaf, center).unwrap();
+            } else {
+                star.add_edge(center, leaf).unwrap();
+            }
+        }
+        let top = top_betweenness(&star, 10);
+        assert_eq!(top[0], (center, 4.0));
+        assert_eq!(top.len(), 5);
+        assert!(top[1..].iter().all(|&(_, score)| score == 0.0));
+        assert!(top_betweenness(&star, 0).is_empty());
+    }
+
     #[test]
     fn test_betweenness_diamond() {
         //     a
@@ -241,10 +332,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let bc = betweenness(&graph);
         // Two equal-length paths a->d, so b and c share the betweenness
@@ -266,10 +357,10 @@ mod tests {
         let s2 = graph.add_node("s2");
         let s3 = graph.add_node("s3");
         let out = graph.add_node("out");
-        graph.add_edge(s1, hub);
-        graph.add_edge(s2, hub);
-        graph.add_edge(s3, hub);
-        graph.add_edge(hub, out);
+        graph.add_edge(s1, hub).unwrap();
+        graph.add_edge(s2, hub).unwrap();
+        graph.add_edge(s3, hub).unwrap();
+        graph.add_edge(hub, out).unwrap();
 
         let bc = betweenness(&graph);
         // Hub is on 3 paths (s1->out, s2->out, s3->out)
@@ -286,9 +377,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let bc = betweenness(&graph);
         // In a cycle, each node is on some paths
@@ -304,8 +395,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let exact = betweenness(&graph);
         let approx = betweenness_approx(&graph, 10, Some(42)); // sample > node count
@@ -324,7 +415,7 @@ mod tests {
             graph.add_node(&format!("n{}", i));
         }
         for i in 0..19 {
-            graph.add_edge(i, i + 1);
+            graph.add_edge(i, i + 1).unwrap();
         }
 
         let bc1 = betweenness_approx(&graph, 5, Some(12345));
@@ -345,10 +436,10 @@ mod tests {
         let c = graph.add_node("c");
         let d = graph.add_node("d");
         let e = graph.add_node("e");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, d);
-        graph.add_edge(d, e);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, e).unwrap();
 
         let bc = betweenness(&graph);
 
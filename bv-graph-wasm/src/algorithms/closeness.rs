@@ -0,0 +1,93 @@
+//! Closeness centrality.
+//!
+//! Measures how few dependency hops separate a node from everything it
+//! reaches downstream. High closeness = a bead whose completion quickly
+//! ripples through the rest of the graph. Complements betweenness, which
+//! finds the bottlenecks in between.
+
+use crate::algorithms::paths::bfs_distances;
+use crate::algorithms::subgraph::Direction;
+use crate::graph::DiGraph;
+
+/// Compute closeness centrality over outgoing edges.
+///
+/// For a node reaching `r` other nodes at total hop distance `d`, the plain
+/// closeness `r / d` is scaled by `r / (n - 1)` (Wasserman–Faust), where
+/// `n` is the number of live nodes. That way a node reaching only one close
+/// neighbor doesn't outrank one reaching the whole graph, which matters
+/// because dependency graphs are rarely strongly connected. Nodes reaching
+/// nothing, and removed nodes, score 0.
+///
+/// Complexity: O(V*(V+E)), one BFS per node.
+pub fn closeness_centrality(graph: &DiGraph) -> Vec<f64> {
+    let n = graph.len();
+    let live = graph.active_len();
+    let mut scores = vec![0.0; n];
+    if live < 2 {
+        return scores;
+    }
+
+    for (v, score) in scores.iter_mut().enumerate() {
+        if graph.is_removed(v) {
+            continue;
+        }
+        let (reached, total) = bfs_distances(graph, &[v], Direction::Forward)
+            .into_iter()
+            .flatten()
+            .filter(|&d| d > 0)
+            .fold((0usize, 0u64), |(r, t), d| (r + 1, t + u64::from(d)));
+        if reached > 0 {
+            let r = reached as f64;
+            *score = (r / total as f64) * (r / (live - 1) as f64);
+        }
+    }
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_star_center_is_closest() {
+        // center -> each leaf, plus leaf1 -> leaf2
+        let mut graph = DiGraph::new();
+        let center = graph.add_node("center");
+        let leaves: Vec<usize> = (0..4)
+            .map(|i| graph.add_node(&format!("leaf{}", i)))
+            .collect();
+        for &leaf in &leaves {
+            graph.add_edge(center, leaf).unwrap();
+        }
+        graph.add_edge(leaves[1], leaves[2]).unwrap();
+
+        let scores = closeness_centrality(&graph);
+        assert_eq!(scores[center], 1.0);
+        assert_eq!(scores[leaves[1]], 0.25);
+        assert_eq!(scores[leaves[0]], 0.0);
+        let best = (0..scores.len()).max_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+        assert_eq!(best, Some(center));
+    }
+
+    #[test]
+    fn test_closeness_chain_and_removed() {
+        // a -> b -> c, with d removed
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(d, a).unwrap();
+        graph.remove_node(d);
+
+        // a reaches 2 of 2 others at total distance 3: (2/3) * (2/2)
+        let scores = closeness_centrality(&graph);
+        assert!((scores[a] - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(scores[b], 0.5);
+        assert_eq!(scores[c], 0.0);
+        assert_eq!(scores[d], 0.0);
+        assert!(closeness_centrality(&DiGraph::new()).is_empty());
+    }
+}
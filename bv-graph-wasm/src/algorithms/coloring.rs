@@ -0,0 +1,214 @@
+//! Graph coloring on the undirected view of the dependency graph.
+//!
+//! Edge direction is ignored: two beads are adjacent if either depends
+//! on the other.
+
+use crate::graph::DiGraph;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// Check whether the undirected view of the graph is bipartite.
+///
+/// Each connected component is 2-colored independently by BFS, starting
+/// from its lowest index with color `false`.
+///
+/// # Returns
+/// * `Some(colors)` - `colors[v]` is the side of node v, None if v is removed
+/// * `None` - an odd cycle (including a self-loop) exists
+pub fn is_bipartite(graph: &DiGraph) -> Option<Vec<Option<bool>>> {
+    let n = graph.len();
+    let mut color: Vec<Option<bool>> = vec![None; n];
+    let mut queue = VecDeque::new();
+
+    for start in 0..n {
+        if color[start].is_some() || graph.is_removed(start) {
+            continue;
+        }
+        color[start] = Some(false);
+        queue.push_back(start);
+
+        while let Some(v) = queue.pop_front() {
+            let side = color[v] == Some(true);
+            let neighbors = graph.out_neighbors(v).chain(graph.in_neighbors(v));
+            for w in neighbors {
+                match color[w] {
+                    None => {
+                        color[w] = Some(!side);
+                        queue.push_back(w);
+                    }
+                    Some(c) if c == side => return None, // Odd cycle
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    Some(color)
+}
+
+/// Color the undirected view greedily in Welsh–Powell (largest-degree-first) order.
+///
+/// Adjacent nodes always get different colors; self-loops are ignored.
+/// Ties in degree are broken by index, so the result is deterministic.
+/// Removed nodes get `usize::MAX`. The number of colors used is one more
+/// than the largest color of a live node (0 if there are none).
+pub fn greedy_coloring(graph: &DiGraph) -> Vec<usize> {
+    let n = graph.len();
+    let neighbors: Vec<Vec<usize>> = (0..n).map(|v| graph.undirected_neighbors(v)).collect();
+
+    let mut order: Vec<usize> = (0..n).filter(|&v| !graph.is_removed(v)).collect();
+    order.sort_by_key(|&v| (std::cmp::Reverse(neighbors[v].len()), v));
+
+    let mut colors = vec![usize::MAX; n];
+    let mut used: Vec<bool> = Vec::new();
+    for v in order {
+        used.clear();
+        used.resize(neighbors[v].len() + 1, false);
+        for &w in &neighbors[v] {
+            if colors[w] < used.len() {
+                used[colors[w]] = true;
+            }
+        }
+        colors[v] = used.iter().position(|&u| !u).unwrap();
+    }
+
+    colors
+}
+
+/// Greedy coloring with the number of colors used.
+#[derive(Serialize)]
+pub struct ColoringResult {
+    /// Color index per node
+    pub colors: Vec<usize>,
+    /// Number of distinct colors
+    pub color_count: usize,
+}
+
+/// Run `greedy_coloring` and report the color count alongside.
+pub fn greedy_coloring_with_info(graph: &DiGraph) -> ColoringResult {
+    let colors = greedy_coloring(graph);
+    ColoringResult {
+        color_count: colors
+            .iter()
+            .filter(|&&c| c != usize::MAX)
+            .max()
+            .map_or(0, |&c| c + 1),
+        colors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(len: usize) -> DiGraph {
+        let mut graph = DiGraph::new();
+        for i in 0..len {
+            graph.add_node(&format!("n{}", i));
+        }
+        for i in 0..len {
+            graph.add_edge(i, (i + 1) % len).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_bipartite_even_cycle() {
+        let colors = is_bipartite(&cycle(4)).unwrap();
+        assert_eq!(colors, [false, true, false, true].map(Some));
+    }
+
+    #[test]
+    fn test_bipartite_odd_cycle_and_self_loop() {
+        assert!(is_bipartite(&cycle(3)).is_none());
+
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        graph.add_edge(a, a).unwrap();
+        assert!(is_bipartite(&graph).is_none());
+    }
+
+    #[test]
+    fn test_bipartite_disconnected() {
+        // Two components: a -> b, and c -> d <- e; every edge crosses sides
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(e, d).unwrap();
+
+        let colors = is_bipartite(&graph).unwrap();
+        for (u, v) in graph.edges() {
+            assert_ne!(colors[u], colors[v]);
+        }
+        assert_eq!((colors[a], colors[c]), (Some(false), Some(false)));
+        assert_eq!(colors[c], colors[e]);
+    }
+
+    #[test]
+    fn test_coloring_skips_removed_nodes() {
+        // Triangle 0, 1, 2 with 2 removed leaves a single edge
+        let mut graph = cycle(3);
+        graph.remove_node(2);
+        let sides = is_bipartite(&graph).unwrap();
+        assert_eq!(sides, vec![Some(false), Some(true), None]);
+
+        let result = greedy_coloring_with_info(&graph);
+        assert_eq!(result.colors, vec![0, 1, usize::MAX]);
+        assert_eq!(result.color_count, 2);
+
+        graph.remove_node(0);
+        graph.remove_node(1);
+        assert_eq!(is_bipartite(&graph).unwrap(), vec![None; 3]);
+        assert_eq!(greedy_coloring_with_info(&graph).color_count, 0);
+    }
+
+    fn assert_proper(graph: &DiGraph, colors: &[usize]) {
+        for (u, v) in graph.edges() {
+            if u != v {
+                assert_ne!(colors[u], colors[v], "edge {} -> {}", u, v);
+            }
+        }
+    }
+
+    #[test]
+    fn test_greedy_coloring_triangle_and_path() {
+        let triangle = cycle(3);
+        let colors = greedy_coloring(&triangle);
+        assert_proper(&triangle, &colors);
+        assert_eq!(greedy_coloring_with_info(&triangle).color_count, 3);
+
+        let mut path = DiGraph::new();
+        for i in 0..5 {
+            path.add_node(&format!("p{}", i));
+        }
+        for i in 0..4 {
+            path.add_edge(i, i + 1).unwrap();
+        }
+        let colors = greedy_coloring(&path);
+        assert_proper(&path, &colors);
+        assert_eq!(greedy_coloring_with_info(&path).color_count, 2);
+
+        assert_eq!(greedy_coloring_with_info(&DiGraph::new()).color_count, 0);
+    }
+
+    #[test]
+    fn test_greedy_coloring_star_and_self_loop() {
+        // Hub has the largest degree, so it's colored first with color 0
+        let mut graph = DiGraph::new();
+        let hub = graph.add_node("hub");
+        graph.add_edge(hub, hub).unwrap();
+        for i in 0..4 {
+            let leaf = graph.add_node(&format!("leaf{}", i));
+            graph.add_edge(leaf, hub).unwrap();
+        }
+        let colors = greedy_coloring(&graph);
+        assert_proper(&graph, &colors);
+        assert_eq!(colors[hub], 0);
+        assert_eq!(greedy_coloring_with_info(&graph).color_count, 2);
+    }
+}
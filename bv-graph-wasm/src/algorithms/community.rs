@@ -0,0 +1,559 @@
+//! Community detection by label propagation and by Louvain.
+//!
+//! Groups beads into "themes" for the overview map. Edge direction is
+//! ignored: two beads are neighbors if either depends on the other.
+//! Label propagation is fast but can vary with the seed; Louvain optimizes
+//! modularity directly and gives steadier groups at a higher cost.
+
+use crate::graph::DiGraph;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A partition of the live nodes into communities.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommunityResult {
+    /// Community id per node, dense from 0 in order of each community's
+    /// lowest node index; usize::MAX for removed nodes
+    pub community_of: Vec<usize>,
+    /// Number of communities
+    pub count: usize,
+    /// Number of nodes in each community, indexed by id
+    pub sizes: Vec<usize>,
+    /// Rounds run (label propagation) or levels built (Louvain)
+    pub iterations: usize,
+    /// False if `max_iters` ran out before the labels settled; Louvain
+    /// always runs to completion
+    pub converged: bool,
+    /// Modularity of the partition (see `modularity`), at resolution 1.0
+    /// for label propagation
+    pub modularity: f64,
+    /// Louvain only: the partition after each level, coarsest last
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub levels: Vec<CommunityLevel>,
+}
+
+/// One level of the Louvain hierarchy.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommunityLevel {
+    /// Community id per node, numbered like `CommunityResult::community_of`
+    pub community_of: Vec<usize>,
+    /// Modularity of this level's partition
+    pub modularity: f64,
+}
+
+/// Modularity of a partition over the undirected weighted view.
+///
+/// Q = Σ_c [in_c / 2m − resolution · (tot_c / 2m)²], where in_c counts the
+/// weight of edges inside community c twice, tot_c is the total weighted
+/// degree of its nodes and m is the total edge weight. Unweighted edges
+/// weigh 1.0 and a self-loop adds its weight twice to its node's degree.
+/// Community ids can be any values, not just 0..n. Nodes whose entry is
+/// usize::MAX (or missing) are left out. 0.0 for a graph without edges.
+pub fn modularity(graph: &DiGraph, community_of: &[usize], resolution: f64) -> f64 {
+    // Renumber the caller's ids densely, in order of first appearance
+    let mut slot_of: HashMap<usize, usize> = HashMap::new();
+    let slots: Vec<usize> = (0..graph.len())
+        .map(|v| match community_of.get(v) {
+            Some(&c) if c != usize::MAX => {
+                let next = slot_of.len();
+                *slot_of.entry(c).or_insert(next)
+            }
+            _ => usize::MAX,
+        })
+        .collect();
+    let mut tot = vec![0.0; slot_of.len()];
+    let (mut inside, mut m2) = (0.0, 0.0);
+    for u in 0..graph.len() {
+        for (v, w) in graph.weighted_successors(u) {
+            let (cu, cv) = (slots[u], slots[v]);
+            if cu == usize::MAX || cv == usize::MAX {
+                continue;
+            }
+            tot[cu] += w;
+            tot[cv] += w;
+            m2 += 2.0 * w;
+            if cu == cv {
+                inside += 2.0 * w;
+            }
+        }
+    }
+    if m2 == 0.0 {
+        return 0.0;
+    }
+    inside / m2 - resolution * tot.iter().map(|t| (t / m2) * (t / m2)).sum::<f64>()
+}
+
+/// Assign each node a community id by asynchronous label propagation.
+///
+/// Every node starts in its own community. Each round visits the live nodes
+/// in a shuffled order and moves each one to the label held by most of its
+/// neighbors; a node whose current label is among the most frequent keeps
+/// it, and other ties are broken at random. Stops after a round with no
+/// changes, which sets `converged`, or after `max_iters` rounds. Each round
+/// is O(V + E).
+///
+/// The shuffle and tie-breaks come from an LCG seeded with `seed`, so the
+/// same seed always gives the same result. Like any label propagation, an
+/// unlucky first round can let one label flood across a weak bridge, so a
+/// few seeds may merge groups that others keep apart. Ids are renumbered
+/// densely in order of each community's lowest node index; removed nodes
+/// get `usize::MAX`.
+pub fn label_propagation(graph: &DiGraph, max_iters: usize, seed: u64) -> CommunityResult {
+    let n = graph.len();
+    let live: Vec<usize> = (0..n).filter(|&v| !graph.is_removed(v)).collect();
+    let neighbors: Vec<Vec<usize>> = (0..n).map(|v| graph.undirected_neighbors(v)).collect();
+
+    // LCG (same generator as betweenness sampling)
+    let mut rng_state = seed;
+    let mut next = |bound: usize| -> usize {
+        rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (rng_state >> 33) as usize % bound
+    };
+
+    let mut labels: Vec<usize> = (0..n).collect();
+    let mut order = live.clone();
+    // Scratch tally indexed by label; `seen` lists the entries to reset
+    let mut counts = vec![0usize; n];
+    let mut seen: Vec<usize> = Vec::new();
+    let mut best: Vec<usize> = Vec::new();
+
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < max_iters {
+        iterations += 1;
+        for i in (1..order.len()).rev() {
+            order.swap(i, next(i + 1));
+        }
+
+        let mut changed = false;
+        for &v in &order {
+            if neighbors[v].is_empty() {
+                continue;
+            }
+            for &w in &neighbors[v] {
+                if counts[labels[w]] == 0 {
+                    seen.push(labels[w]);
+                }
+                counts[labels[w]] += 1;
+            }
+            let top = seen.iter().map(|&l| counts[l]).max().unwrap_or(0);
+            best.clear();
+            best.extend(seen.iter().copied().filter(|&l| counts[l] == top));
+            for l in seen.drain(..) {
+                counts[l] = 0;
+            }
+
+            if !best.contains(&labels[v]) {
+                best.sort_unstable();
+                labels[v] = best[next(best.len())];
+                changed = true;
+            }
+        }
+        if !changed {
+            converged = true;
+            break;
+        }
+    }
+
+    let (community_of, sizes) = renumber(graph, &labels);
+    CommunityResult {
+        modularity: modularity(graph, &community_of, 1.0),
+        community_of,
+        count: sizes.len(),
+        sizes,
+        iterations,
+        converged,
+        levels: Vec::new(),
+    }
+}
+
+/// Detect communities with the Louvain method (Blondel et al., "Fast
+/// unfolding of communities in large networks", 2008).
+///
+/// Works on the undirected weighted view, where u -> v and v -> u add up to
+/// one connection; unweighted edges weigh 1.0 and weights should not be
+/// negative. Each level starts with every node in its own community and
+/// moves nodes, in a shuffled order, to the neighboring community that
+/// raises modularity most, until a pass moves nothing. The communities then
+/// become the nodes of a coarser graph for the next level. Stops once a
+/// level moves nothing, so modularity never decreases from one level to the
+/// next. Each level is O(V + E) per pass.
+///
+/// `resolution` scales the penalty for large communities: above 1.0 gives
+/// more, smaller communities, below 1.0 fewer, larger ones. The visit order
+/// comes from an LCG seeded with `seed`, so the same seed always gives the
+/// same result. Ids are numbered as in `label_propagation`.
+pub fn louvain(graph: &DiGraph, resolution: f64, seed: u64) -> CommunityResult {
+    let n = graph.len();
+    let live: Vec<usize> = (0..n).filter(|&v| !graph.is_removed(v)).collect();
+    let mut index = vec![usize::MAX; n];
+    for (i, &v) in live.iter().enumerate() {
+        index[v] = i;
+    }
+
+    // Current level graph: symmetric adjacency without loops, plus loop weight
+    let mut adj: Vec<Vec<(usize, f64)>> = vec![Vec::new(); live.len()];
+    let mut loops = vec![0.0; live.len()];
+    for &u in &live {
+        for (v, w) in graph.weighted_successors(u) {
+            let (a, b) = (index[u], index[v]);
+            if a == b {
+                loops[a] += 2.0 * w;
+            } else {
+                adj[a].push((b, w));
+                adj[b].push((a, w));
+            }
+        }
+    }
+    for list in &mut adj {
+        merge_parallel(list);
+    }
+
+    let mut rng_state = seed;
+    let mut next = |bound: usize| -> usize {
+        rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (rng_state >> 33) as usize % bound
+    };
+
+    // Level community of each live node, composed across levels
+    let mut node_comm: Vec<usize> = (0..live.len()).collect();
+    let mut levels = Vec::new();
+    let mut labels = vec![usize::MAX; n];
+    loop {
+        let size = adj.len();
+        let k: Vec<f64> = (0..size)
+            .map(|i| adj[i].iter().map(|&(_, w)| w).sum::<f64>() + loops[i])
+            .collect();
+        let m2: f64 = k.iter().sum();
+        if m2 == 0.0 {
+            break;
+        }
+
+        let mut order: Vec<usize> = (0..size).collect();
+        for i in (1..size).rev() {
+            order.swap(i, next(i + 1));
+        }
+        let mut comm: Vec<usize> = (0..size).collect();
+        let mut tot = k.clone();
+        // Scratch: weight from the current node to each community
+        let mut weight_to = vec![0.0; size];
+        let mut touched: Vec<usize> = Vec::new();
+        let mut moved_any = false;
+        loop {
+            let mut moved = false;
+            for &i in &order {
+                let own = comm[i];
+                for &(j, w) in &adj[i] {
+                    if weight_to[comm[j]] == 0.0 {
+                        touched.push(comm[j]);
+                    }
+                    weight_to[comm[j]] += w;
+                }
+                tot[own] -= k[i];
+                // Gain of joining c, up to a factor shared by all c
+                let gain = |c: usize| weight_to[c] - resolution * tot[c] * k[i] / m2;
+                let mut best = own;
+                let mut best_gain = gain(own);
+                for &c in &touched {
+                    let g = gain(c);
+                    if g > best_gain + 1e-12 {
+                        best = c;
+                        best_gain = g;
+                    }
+                }
+                tot[best] += k[i];
+                comm[i] = best;
+                moved |= best != own;
+                for c in touched.drain(..) {
+                    weight_to[c] = 0.0;
+                }
+            }
+            if !moved {
+                break;
+            }
+            moved_any = true;
+        }
+        if !moved_any {
+            break;
+        }
+
+        // Coarsen: each community becomes one node of the next level
+        let mut dense = vec![usize::MAX; size];
+        let mut count = 0;
+        for c in comm.iter_mut() {
+            if dense[*c] == usize::MAX {
+                dense[*c] = count;
+                count += 1;
+            }
+            *c = dense[*c];
+        }
+        let mut next_adj: Vec<Vec<(usize, f64)>> = vec![Vec::new(); count];
+        let mut next_loops = vec![0.0; count];
+        for i in 0..size {
+            next_loops[comm[i]] += loops[i];
+            for &(j, w) in &adj[i] {
+                if comm[i] == comm[j] {
+                    next_loops[comm[i]] += w;
+                } else {
+                    next_adj[comm[i]].push((comm[j], w));
+                }
+            }
+        }
+        for list in &mut next_adj {
+            merge_parallel(list);
+        }
+        adj = next_adj;
+        loops = next_loops;
+
+        for c in node_comm.iter_mut() {
+            *c = comm[*c];
+        }
+        for (i, &v) in live.iter().enumerate() {
+            labels[v] = node_comm[i];
+        }
+        let (community_of, _) = renumber(graph, &labels);
+        levels.push(CommunityLevel {
+            modularity: modularity(graph, &community_of, resolution),
+            community_of,
+        });
+    }
+
+    for (i, &v) in live.iter().enumerate() {
+        labels[v] = node_comm[i];
+    }
+    let (community_of, sizes) = renumber(graph, &labels);
+    CommunityResult {
+        modularity: modularity(graph, &community_of, resolution),
+        community_of,
+        count: sizes.len(),
+        sizes,
+        iterations: levels.len(),
+        converged: true,
+        levels,
+    }
+}
+
+/// Sort an adjacency list and add up the weights of repeated neighbors.
+fn merge_parallel(list: &mut Vec<(usize, f64)>) {
+    list.sort_unstable_by_key(|&(v, _)| v);
+    list.dedup_by(|next, kept| {
+        if next.0 == kept.0 {
+            kept.1 += next.1;
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Renumber labels densely by lowest member, so ids don't depend on which
+/// labels survived. Returns community ids (usize::MAX for removed nodes)
+/// and the size of each community.
+fn renumber(graph: &DiGraph, labels: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let n = graph.len();
+    let mut id_of = vec![usize::MAX; n];
+    let mut sizes = Vec::new();
+    let mut community_of = vec![usize::MAX; n];
+    for v in (0..n).filter(|&v| !graph.is_removed(v)) {
+        let label = labels[v];
+        if id_of[label] == usize::MAX {
+            id_of[label] = sizes.len();
+            sizes.push(0);
+        }
+        community_of[v] = id_of[label];
+        sizes[id_of[label]] += 1;
+    }
+    (community_of, sizes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two 5-cliques, 0..5 and 5..10, joined by the single edge 4 -> 5.
+    fn two_cliques() -> DiGraph {
+        let mut graph = DiGraph::new();
+        for i in 0..10 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for base in [0, 5] {
+            for i in base..base + 5 {
+                for j in i + 1..base + 5 {
+                    graph.add_edge(i, j).unwrap();
+                }
+            }
+        }
+        graph.add_edge(4, 5).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_two_cliques_split() {
+        let graph = two_cliques();
+        let result = label_propagation(&graph, 100, 42);
+        assert_eq!(result.community_of, [0, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+        assert_eq!(result.count, 2);
+        assert_eq!(result.sizes, [5, 5]);
+        assert!(result.converged);
+        assert!(result.iterations < 100);
+        // in = 20 and tot = 21 per clique, out of 2m = 42
+        assert!((result.modularity - (40.0 / 42.0 - 0.5)).abs() < 1e-12);
+        assert!(result.levels.is_empty());
+    }
+
+    #[test]
+    fn test_complete_graph_collapses() {
+        let mut graph = DiGraph::new();
+        for i in 0..6 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for i in 0..6 {
+            for j in 0..6 {
+                if i != j {
+                    graph.add_edge(i, j).unwrap();
+                }
+            }
+        }
+        for seed in 0..5 {
+            let result = label_propagation(&graph, 100, seed);
+            assert_eq!(result.community_of, [0; 6]);
+            assert_eq!(result.sizes, [6]);
+            assert!(result.converged);
+        }
+    }
+
+    #[test]
+    fn test_label_propagation_edge_cases() {
+        let graph = two_cliques();
+        assert_eq!(label_propagation(&graph, 50, 7), label_propagation(&graph, 50, 7));
+
+        // No rounds: every node stays alone
+        let result = label_propagation(&graph, 0, 1);
+        assert_eq!(result.community_of, (0..10).collect::<Vec<_>>());
+        assert_eq!(result.count, 10);
+        assert!(!result.converged);
+        assert_eq!(label_propagation(&graph, 1, 1).iterations, 1);
+
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_node("lonely");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.remove_node(c);
+        let result = label_propagation(&graph, 10, 3);
+        assert_eq!(result.community_of, [0, 0, usize::MAX, 1]);
+        assert_eq!(result.sizes, [2, 1]);
+        assert_eq!(label_propagation(&DiGraph::new(), 10, 3).count, 0);
+    }
+
+    /// Cliques of 4 nodes each; `links` adds one edge per pair of cliques
+    /// between their i-th nodes, for each i in the list.
+    fn cliques(count: usize, links: &[(usize, usize, &[usize])]) -> DiGraph {
+        let mut graph = DiGraph::new();
+        for i in 0..count * 4 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for c in 0..count {
+            for i in 0..4 {
+                for j in i + 1..4 {
+                    graph.add_edge(c * 4 + i, c * 4 + j).unwrap();
+                }
+            }
+        }
+        for &(a, b, nodes) in links {
+            for &i in nodes {
+                graph.add_edge(a * 4 + i, b * 4 + i).unwrap();
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_louvain_two_cliques() {
+        let graph = two_cliques();
+        for seed in 0..5 {
+            let result = louvain(&graph, 1.0, seed);
+            assert_eq!(result.community_of, [0, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+            assert_eq!(result.sizes, [5, 5]);
+            assert!((result.modularity - (40.0 / 42.0 - 0.5)).abs() < 1e-12);
+            let last = result.levels.last().unwrap();
+            assert_eq!(last.community_of, result.community_of);
+        }
+        assert_eq!(louvain(&graph, 1.0, 9), louvain(&graph, 1.0, 9));
+    }
+
+    #[test]
+    fn test_louvain_levels_never_lose_modularity() {
+        // A ring of 8 cliques, each tied to the next by one edge
+        let links: Vec<(usize, usize, &[usize])> =
+            (0..8).map(|c| (c, (c + 1) % 8, &[0usize][..])).collect();
+        let graph = cliques(8, &links);
+        for seed in 0..5 {
+            let result = louvain(&graph, 1.0, seed);
+            assert!(!result.levels.is_empty());
+            assert_eq!(result.iterations, result.levels.len());
+            for pair in result.levels.windows(2) {
+                assert!(pair[1].modularity >= pair[0].modularity - 1e-12);
+            }
+            let last = result.levels.last().unwrap();
+            assert_eq!(last.modularity, result.modularity);
+            assert!(result.modularity > 0.6, "{}", result.modularity);
+        }
+    }
+
+    #[test]
+    fn test_louvain_resolution_changes_granularity() {
+        // Cliques 0 and 1 are tightly linked, as are 2 and 3, with a
+        // single edge between the two pairs
+        let all: &[usize] = &[0, 1, 2, 3];
+        let graph = cliques(4, &[(0, 1, all), (2, 3, all), (1, 2, &[0])]);
+        for seed in 0..5 {
+            assert_eq!(louvain(&graph, 0.5, seed).sizes, [8, 8]);
+            assert_eq!(louvain(&graph, 2.0, seed).sizes, [4, 4, 4, 4]);
+        }
+    }
+
+    #[test]
+    fn test_louvain_weights_and_edge_cases() {
+        // Path 0 - 1 - 2 - 3 with a weak middle link
+        let mut graph = DiGraph::new();
+        for id in ["a", "b", "c", "d", "gone", "lonely"] {
+            graph.add_node(id);
+        }
+        graph.add_weighted_edge(0, 1, 10.0).unwrap();
+        graph.add_weighted_edge(2, 1, 0.1).unwrap();
+        graph.add_weighted_edge(2, 3, 5.0).unwrap();
+        graph.add_weighted_edge(3, 2, 5.0).unwrap();
+        graph.add_edge(4, 0).unwrap();
+        graph.remove_node(4);
+        let result = louvain(&graph, 1.0, 1);
+        assert_eq!(result.community_of, [0, 0, 1, 1, usize::MAX, 2]);
+        assert_eq!(result.sizes, [2, 2, 1]);
+
+        let empty = louvain(&DiGraph::new(), 1.0, 1);
+        assert_eq!(empty.count, 0);
+        assert_eq!(empty.modularity, 0.0);
+        assert!(empty.levels.is_empty());
+    }
+
+    #[test]
+    fn test_modularity_arbitrary_ids() {
+        let mut pair = DiGraph::new();
+        pair.add_node("a");
+        pair.add_node("b");
+        pair.add_edge(0, 1).unwrap();
+        // One community holding everything scores 1 - 1 = 0
+        assert_eq!(modularity(&pair, &[5, 5], 1.0), 0.0);
+        assert_eq!(modularity(&pair, &[5, usize::MAX - 1], 1.0), -0.5);
+
+        // Relabeling communities doesn't change the score
+        let graph = two_cliques();
+        let dense: Vec<usize> = (0..10).map(|v| v / 5).collect();
+        let sparse: Vec<usize> = dense.iter().map(|&c| 1000 - c * 7).collect();
+        assert_eq!(modularity(&graph, &sparse, 1.0), modularity(&graph, &dense, 1.0));
+        // Short or unassigned entries are left out
+        assert_eq!(modularity(&pair, &[], 1.0), 0.0);
+    }
+}
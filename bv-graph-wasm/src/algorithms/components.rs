@@ -0,0 +1,113 @@
+//! Weakly connected components.
+//!
+//! Edges are treated as undirected, so each component is one "island" of
+//! related beads that the viewer can lay out and filter on its own.
+
+use crate::graph::DiGraph;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// Result of weakly connected component analysis.
+#[derive(Debug, Clone, Serialize)]
+pub struct WCCResult {
+    /// Components in order of their lowest node index, each sorted ascending
+    pub components: Vec<Vec<usize>>,
+    /// component_of[v] is the index into `components` (usize::MAX for removed nodes)
+    pub component_of: Vec<usize>,
+    /// Number of components
+    pub count: usize,
+}
+
+/// Find weakly connected components with an iterative BFS.
+///
+/// Removed nodes belong to no component. Complexity: O(V + E)
+pub fn weakly_connected_components(graph: &DiGraph) -> WCCResult {
+    let n = graph.len();
+    let mut component_of = vec![usize::MAX; n];
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut queue = VecDeque::new();
+
+    for start in 0..n {
+        if component_of[start] != usize::MAX || graph.is_removed(start) {
+            continue;
+        }
+        let id = components.len();
+        component_of[start] = id;
+        queue.push_back(start);
+        let mut component = Vec::new();
+
+        while let Some(v) = queue.pop_front() {
+            component.push(v);
+            let neighbors = graph.out_neighbors(v).chain(graph.in_neighbors(v));
+            for w in neighbors {
+                if component_of[w] == usize::MAX {
+                    component_of[w] = id;
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        component.sort_unstable();
+        components.push(component);
+    }
+
+    WCCResult {
+        count: components.len(),
+        components,
+        component_of,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_cycles() -> DiGraph {
+        // a -> b -> c -> a and d -> e -> d
+        let mut graph = DiGraph::new();
+        for id in ["a", "b", "c", "d", "e"] {
+            graph.add_node(id);
+        }
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        graph.add_edge(2, 0).unwrap();
+        graph.add_edge(3, 4).unwrap();
+        graph.add_edge(4, 3).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_two_disjoint_cycles() {
+        let result = weakly_connected_components(&two_cycles());
+        assert_eq!(result.count, 2);
+        assert_eq!(result.components, vec![vec![0, 1, 2], vec![3, 4]]);
+        assert_eq!(result.component_of, vec![0, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_bridging_edge_merges() {
+        // Direction doesn't matter: an edge into the first island joins them
+        let mut graph = two_cycles();
+        graph.add_edge(4, 1).unwrap();
+
+        let result = weakly_connected_components(&graph);
+        assert_eq!(result.count, 1);
+        assert_eq!(result.components, vec![vec![0, 1, 2, 3, 4]]);
+    }
+
+    #[test]
+    fn test_isolated_and_removed_nodes() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.remove_node(b);
+
+        let result = weakly_connected_components(&graph);
+        assert_eq!(result.count, 2);
+        assert_eq!(result.components, vec![vec![a], vec![c]]);
+        assert_eq!(result.component_of, vec![0, usize::MAX, 1]);
+
+        assert_eq!(weakly_connected_components(&DiGraph::new()).count, 0);
+    }
+}
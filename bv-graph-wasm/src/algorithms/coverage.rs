@@ -70,14 +70,14 @@ pub fn coverage_set(graph: &DiGraph, limit: usize) -> CoverageResult {
             let mut count = 0;
 
             // Count uncovered outgoing edges (v -> w)
-            for &w in graph.successors_slice(v) {
+            for w in graph.out_neighbors(v) {
                 if !covered.contains(&(v, w)) {
                     count += 1;
                 }
             }
 
             // Count uncovered incoming edges (u -> v)
-            for &u in graph.predecessors_slice(v) {
+            for u in graph.in_neighbors(v) {
                 if !covered.contains(&(u, v)) {
                     count += 1;
                 }
@@ -92,11 +92,11 @@ pub fn coverage_set(graph: &DiGraph, limit: usize) -> CoverageResult {
         match best_node {
             Some(node) if best_count > 0 => {
                 // Mark edges as covered - outgoing
-                for &w in graph.successors_slice(node) {
+                for w in graph.out_neighbors(node) {
                     covered.insert((node, w));
                 }
                 // Mark edges as covered - incoming
-                for &u in graph.predecessors_slice(node) {
+                for u in graph.in_neighbors(node) {
                     covered.insert((u, node));
                 }
 
@@ -153,7 +153,7 @@ mod tests {
             g.add_node(&format!("n{}", i));
         }
         for (from, to) in edges {
-            g.add_edge(*from, *to);
+            g.add_edge(*from, *to).unwrap();
         }
         g
     }
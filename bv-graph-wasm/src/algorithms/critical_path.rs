@@ -22,10 +22,10 @@ pub fn critical_path_heights(graph: &DiGraph) -> Vec<f64> {
         return Vec::new();
     }
 
-    // Topological order (returns None if cyclic)
+    // Topological order (errors if cyclic)
     let order = match topological_sort(graph) {
-        Some(o) => o,
-        None => return vec![0.0; n], // Return zeros for cyclic graphs
+        Ok(o) => o,
+        Err(_) => return vec![0.0; n], // Return zeros for cyclic graphs
     };
 
     let mut heights = vec![0.0; n];
@@ -33,9 +33,8 @@ pub fn critical_path_heights(graph: &DiGraph) -> Vec<f64> {
     // Process in topological order
     for &v in &order {
         let max_pred_height = graph
-            .predecessors_slice(v)
-            .iter()
-            .map(|&u| heights[u])
+            .in_neighbors(v)
+            .map(|u| heights[u])
             .fold(0.0, f64::max);
 
         heights[v] = 1.0 + max_pred_height;
@@ -94,8 +93,8 @@ mod tests {
         let a = g.add_node("a");
         let b = g.add_node("b");
         let c = g.add_node("c");
-        g.add_edge(a, b);
-        g.add_edge(b, c);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
 
         let heights = critical_path_heights(&g);
         assert_eq!(heights[a], 1.0); // Root
@@ -115,10 +114,10 @@ mod tests {
         let b = g.add_node("b");
         let c = g.add_node("c");
         let d = g.add_node("d");
-        g.add_edge(a, b);
-        g.add_edge(a, c);
-        g.add_edge(b, d);
-        g.add_edge(c, d);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(a, c).unwrap();
+        g.add_edge(b, d).unwrap();
+        g.add_edge(c, d).unwrap();
 
         let heights = critical_path_heights(&g);
         assert_eq!(heights[a], 1.0);
@@ -137,9 +136,9 @@ mod tests {
         let c = g.add_node("c");
         let d = g.add_node("d");
         let e = g.add_node("e");
-        g.add_edge(a, b);
-        g.add_edge(b, c);
-        g.add_edge(d, e);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(d, e).unwrap();
 
         let heights = critical_path_heights(&g);
         assert_eq!(heights[a], 1.0);
@@ -158,9 +157,9 @@ mod tests {
         let a = g.add_node("a");
         let b = g.add_node("b");
         let c = g.add_node("c");
-        g.add_edge(a, b);
-        g.add_edge(b, c);
-        g.add_edge(c, a);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(c, a).unwrap();
 
         let heights = critical_path_heights(&g);
         // Should return zeros for cyclic graphs
@@ -174,8 +173,8 @@ mod tests {
         let a = g.add_node("a");
         let b = g.add_node("b");
         let c = g.add_node("c");
-        g.add_edge(a, b);
-        g.add_edge(b, c);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
 
         let critical = critical_path_nodes(&g);
         // Only c has maximum height (3)
@@ -192,8 +191,8 @@ mod tests {
         let b = g.add_node("b");
         let c = g.add_node("c");
         let d = g.add_node("d");
-        g.add_edge(a, b);
-        g.add_edge(c, d);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(c, d).unwrap();
 
         let critical = critical_path_nodes(&g);
         // Both b and d have maximum height (2)
@@ -211,10 +210,10 @@ mod tests {
         let c = g.add_node("c");
         let d = g.add_node("d");
         let e = g.add_node("e");
-        g.add_edge(a, b);
-        g.add_edge(a, c);
-        g.add_edge(a, d);
-        g.add_edge(a, e);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(a, c).unwrap();
+        g.add_edge(a, d).unwrap();
+        g.add_edge(a, e).unwrap();
 
         let heights = critical_path_heights(&g);
         assert_eq!(heights[a], 1.0);
@@ -4,106 +4,241 @@
 //! - Tarjan's SCC algorithm for fast cycle presence check
 //! - Johnson's algorithm for full cycle enumeration
 
+use crate::csr::{Adjacency, CsrGraph};
 use crate::graph::DiGraph;
-use serde::Serialize;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// Result of Strongly Connected Components analysis.
-#[derive(Serialize, Clone)]
+///
+/// Deserializing from JSON rebuilds `component_of`, but JSON doesn't carry
+/// Tarjan's finish order, so `reverse_topological_order` comes back empty.
+/// `to_bytes` keeps everything.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(from = "SCCFields")]
 pub struct SCCResult {
-    /// List of strongly connected components (each is a list of node indices)
+    /// List of strongly connected components (each is a list of node indices).
+    /// Each component is sorted ascending and components are ordered by their
+    /// smallest index, so the output is stable for snapshot tests.
     pub components: Vec<Vec<usize>>,
-    /// True if any SCC has more than one node (cycle exists)
+    /// True if any SCC is cyclic (cycle exists)
     pub has_cycles: bool,
-    /// Number of non-trivial SCCs (size > 1)
+    /// Number of cyclic SCCs: size > 1, or a single node with a self-loop
     pub cycle_count: usize,
+    /// Index into `components` for each node, built alongside them
+    #[serde(skip)]
+    component_index: Vec<usize>,
+    /// Component ids in the order Tarjan completed them
+    #[serde(skip)]
+    finish_order: Vec<usize>,
+}
+
+/// The serialized part of `SCCResult`.
+#[derive(Deserialize)]
+struct SCCFields {
+    components: Vec<Vec<usize>>,
+    cycle_count: usize,
+}
+
+impl From<SCCFields> for SCCResult {
+    fn from(fields: SCCFields) -> Self {
+        let node_count = fields.components.iter().flatten().max().map_or(0, |&v| v + 1);
+        SCCResult::from_parts(fields.components, fields.cycle_count, node_count, Vec::new())
+    }
+}
+
+impl SCCResult {
+    /// Assemble a result from normalized components, indexing every member.
+    /// Members must be below `node_count`.
+    pub(crate) fn from_parts(
+        components: Vec<Vec<usize>>,
+        cycle_count: usize,
+        node_count: usize,
+        finish_order: Vec<usize>,
+    ) -> Self {
+        let mut component_index = vec![usize::MAX; node_count];
+        for (id, component) in components.iter().enumerate() {
+            for &v in component {
+                component_index[v] = id;
+            }
+        }
+        SCCResult {
+            components,
+            has_cycles: cycle_count > 0,
+            cycle_count,
+            component_index,
+            finish_order,
+        }
+    }
+
+    /// Number of node slots covered, live or not: the graph's `len()` when
+    /// it was computed.
+    pub(crate) fn node_count(&self) -> usize {
+        self.component_index.len()
+    }
+
+    /// Encode in the compact binary format for caching (see `io::binary`).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        crate::io::binary::scc_to_bytes(self)
+    }
+
+    /// Decode a result produced by `to_bytes`.
+    ///
+    /// # Errors
+    /// See `io::binary::scc_from_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SCCResult, String> {
+        crate::io::binary::scc_from_bytes(bytes)
+    }
+
+    /// Index into `components` of the component holding `node`.
+    ///
+    /// O(1). Returns `usize::MAX` for removed or out-of-range nodes.
+    pub fn component_of(&self, node: usize) -> usize {
+        self.component_index.get(node).copied().unwrap_or(usize::MAX)
+    }
+
+    /// True if `a` and `b` are in the same strongly connected component,
+    /// i.e. each can reach the other. A node is in its own component.
+    pub fn same_component(&self, a: usize, b: usize) -> bool {
+        let comp = self.component_of(a);
+        comp != usize::MAX && comp == self.component_of(b)
+    }
+
+    /// Component ids in reverse topological order of the condensation.
+    ///
+    /// Tarjan completes a component only after every component it can
+    /// reach, so this is simply the order they were found in: a component
+    /// never has an edge to one listed after it. `scc_topological_order`
+    /// flips it so that dependencies come first.
+    pub fn reverse_topological_order(&self) -> &[usize] {
+        &self.finish_order
+    }
 }
 
 /// Tarjan's algorithm for finding strongly connected components.
 ///
-/// An SCC with more than one node indicates a cycle.
+/// An SCC with more than one node indicates a cycle, as does a
+/// single-node SCC whose node has a self-loop.
+/// Accepts a `DiGraph` or its frozen `CsrGraph`; the result is the same.
 /// Complexity: O(V + E)
-pub fn tarjan_scc(graph: &DiGraph) -> SCCResult {
+pub fn tarjan_scc<G: Adjacency>(graph: &G) -> SCCResult {
+    tarjan_scc_filtered(graph, |_, _| true)
+}
+
+/// Tarjan's algorithm over a virtual view of the graph.
+///
+/// Edges for which `keep(from, to)` returns false are treated as absent,
+/// so callers can mask edges without copying the graph.
+pub(crate) fn tarjan_scc_filtered<G, F>(graph: &G, keep: F) -> SCCResult
+where
+    G: Adjacency,
+    F: Fn(usize, usize) -> bool,
+{
     let n = graph.len();
     if n == 0 {
-        return SCCResult {
-            components: Vec::new(),
-            has_cycles: false,
-            cycle_count: 0,
-        };
+        return SCCResult::from_parts(Vec::new(), 0, 0, Vec::new());
     }
 
-    let mut index = 0usize;
-    let mut indices = vec![usize::MAX; n];
-    let mut lowlink = vec![usize::MAX; n];
-    let mut on_stack = vec![false; n];
-    let mut stack: Vec<usize> = Vec::new();
-    let mut components: Vec<Vec<usize>> = Vec::new();
-
-    fn strongconnect(
-        v: usize,
-        graph: &DiGraph,
-        index: &mut usize,
-        indices: &mut [usize],
-        lowlink: &mut [usize],
-        on_stack: &mut [bool],
-        stack: &mut Vec<usize>,
-        components: &mut Vec<Vec<usize>>,
-    ) {
-        indices[v] = *index;
-        lowlink[v] = *index;
-        *index += 1;
-        stack.push(v);
-        on_stack[v] = true;
-
-        for &w in graph.successors_slice(v) {
-            if indices[w] == usize::MAX {
-                // Not visited
-                strongconnect(w, graph, index, indices, lowlink, on_stack, stack, components);
-                lowlink[v] = lowlink[v].min(lowlink[w]);
-            } else if on_stack[w] {
-                // On stack = in current SCC
-                lowlink[v] = lowlink[v].min(indices[w]);
+    struct Tarjan<'a, G, F> {
+        graph: &'a G,
+        keep: F,
+        index: usize,
+        indices: Vec<usize>,
+        lowlink: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl<G: Adjacency, F: Fn(usize, usize) -> bool> Tarjan<'_, G, F> {
+        fn strongconnect(&mut self, v: usize) {
+            self.indices[v] = self.index;
+            self.lowlink[v] = self.index;
+            self.index += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+
+            let graph = self.graph;
+            for &w in graph.successors(v) {
+                let w = w as usize;
+                if !(self.keep)(v, w) {
+                    continue;
+                }
+                if self.indices[w] == usize::MAX {
+                    // Not visited
+                    self.strongconnect(w);
+                    self.lowlink[v] = self.lowlink[v].min(self.lowlink[w]);
+                } else if self.on_stack[w] {
+                    // On stack = in current SCC
+                    self.lowlink[v] = self.lowlink[v].min(self.indices[w]);
+                }
             }
-        }
 
-        // If v is a root node, pop the stack to get SCC
-        if lowlink[v] == indices[v] {
-            let mut component = Vec::new();
-            loop {
-                let w = stack.pop().unwrap();
-                on_stack[w] = false;
-                component.push(w);
-                if w == v {
-                    break;
+            // If v is a root node, pop the stack to get SCC
+            if self.lowlink[v] == self.indices[v] {
+                let mut component = Vec::new();
+                loop {
+                    let w = self.stack.pop().unwrap();
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
                 }
+                self.components.push(component);
             }
-            components.push(component);
         }
     }
 
+    let mut tarjan = Tarjan {
+        graph,
+        keep,
+        index: 0,
+        indices: vec![usize::MAX; n],
+        lowlink: vec![usize::MAX; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        components: Vec::new(),
+    };
+
     for v in 0..n {
-        if indices[v] == usize::MAX {
-            strongconnect(
-                v,
-                graph,
-                &mut index,
-                &mut indices,
-                &mut lowlink,
-                &mut on_stack,
-                &mut stack,
-                &mut components,
-            );
+        // Removed nodes are isolated; leave them out of the components
+        if tarjan.indices[v] == usize::MAX && !graph.is_removed(v) {
+            tarjan.strongconnect(v);
         }
     }
 
-    let cycle_count = components.iter().filter(|c| c.len() > 1).count();
-
-    SCCResult {
-        components,
-        has_cycles: cycle_count > 0,
-        cycle_count,
+    // Tarjan emits components in stack-pop order; normalize it, remembering
+    // where each one was emitted
+    let keep = tarjan.keep;
+    let mut components: Vec<(usize, Vec<usize>)> =
+        tarjan.components.into_iter().enumerate().collect();
+    for (_, component) in &mut components {
+        component.sort_unstable();
     }
+    components.sort_unstable_by_key(|(_, c)| c[0]);
+    let mut finish_order = vec![0; components.len()];
+    for (id, &(emitted, _)) in components.iter().enumerate() {
+        finish_order[emitted] = id;
+    }
+    let components: Vec<Vec<usize>> = components.into_iter().map(|(_, c)| c).collect();
+    let cycle_count = components
+        .iter()
+        .filter(|c| match c.as_slice() {
+            [v] => graph.has_edge(*v, *v) && keep(*v, *v),
+            _ => true,
+        })
+        .count();
+
+    SCCResult::from_parts(components, cycle_count, n, finish_order)
+}
+
+/// Tarjan's algorithm restricted to edges whose kind is in `kind_mask`.
+///
+/// Bit k of the mask selects `EdgeKind(k)`; `EdgeKind::ALL` keeps every edge.
+/// Use this to ignore e.g. "related" links, which don't constrain ordering.
+pub fn tarjan_scc_masked(graph: &DiGraph, kind_mask: u32) -> SCCResult {
+    tarjan_scc(&CsrGraph::masked(graph, kind_mask))
 }
 
 /// Check if graph has any cycles.
@@ -111,136 +246,260 @@ pub fn has_cycles(graph: &DiGraph) -> bool {
     tarjan_scc(graph).has_cycles
 }
 
-/// Enumerate elementary cycles using Johnson's algorithm.
-///
-/// Reference: Donald B. Johnson, "Finding All the Elementary Circuits of a Directed Graph"
-/// SIAM J. Computing, Vol. 4, No. 1, March 1975
-///
-/// # Arguments
-/// * `graph` - The directed graph
-/// * `max_cycles` - Maximum number of cycles to find (prevents exponential blowup)
-///
-/// # Returns
-/// Vector of cycles, each cycle is a vector of node indices in order
-pub fn enumerate_cycles(graph: &DiGraph, max_cycles: usize) -> Vec<Vec<usize>> {
-    let n = graph.len();
-    if n == 0 || max_cycles == 0 {
-        return Vec::new();
+/// Shared state for Johnson's circuit search.
+struct Johnson<'a, G> {
+    graph: &'a G,
+    blocked: Vec<bool>,
+    /// B-lists: nodes to unblock when the key node unblocks (deduplicated)
+    blocked_map: Vec<Vec<u32>>,
+    stack: Vec<usize>,
+    /// Nodes whose blocked state was touched since the last reset
+    dirty: Vec<usize>,
+    /// Set once the cycle sink asks to stop
+    stopped: bool,
+}
+
+impl<'a, G: Adjacency> Johnson<'a, G> {
+    fn new(graph: &'a G) -> Self {
+        let n = graph.len();
+        Johnson {
+            graph,
+            blocked: vec![false; n],
+            blocked_map: vec![Vec::new(); n],
+            stack: Vec::new(),
+            dirty: Vec::new(),
+            stopped: false,
+        }
     }
 
-    let mut cycles: Vec<Vec<usize>> = Vec::new();
-    let mut blocked = vec![false; n];
-    let mut blocked_map: Vec<HashSet<usize>> = vec![HashSet::new(); n];
-    let mut stack: Vec<usize> = Vec::new();
-
-    // Helper: unblock a node and recursively unblock dependents
-    fn unblock(u: usize, blocked: &mut [bool], blocked_map: &mut [HashSet<usize>]) {
-        blocked[u] = false;
-        let dependents: Vec<usize> = blocked_map[u].drain().collect();
+    /// Reset blocked state before searching from a new start vertex.
+    ///
+    /// Only entries touched by the previous search are cleared, so the
+    /// cost is proportional to the work done rather than to the graph size.
+    fn reset(&mut self) {
+        for v in self.dirty.drain(..) {
+            self.blocked[v] = false;
+            self.blocked_map[v].clear();
+        }
+    }
+
+    /// Unblock a node and recursively unblock dependents.
+    fn unblock(&mut self, u: usize) {
+        self.blocked[u] = false;
+        let dependents = std::mem::take(&mut self.blocked_map[u]);
         for w in dependents {
-            if blocked[w] {
-                unblock(w, blocked, blocked_map);
+            if self.blocked[w as usize] {
+                self.unblock(w as usize);
             }
         }
     }
 
-    // Circuit search from start vertex
-    fn circuit(
-        v: usize,
-        start: usize,
-        graph: &DiGraph,
-        blocked: &mut [bool],
-        blocked_map: &mut [HashSet<usize>],
-        stack: &mut Vec<usize>,
-        cycles: &mut Vec<Vec<usize>>,
-        max_cycles: usize,
-        min_node: usize,
-    ) -> bool {
-        if cycles.len() >= max_cycles {
+    /// Circuit search from `v` back to `start`, following only edges in scope.
+    ///
+    /// Each cycle found is passed to `emit`; returning false stops the search.
+    fn circuit<S, F>(&mut self, v: usize, start: usize, in_scope: &S, emit: &mut F) -> bool
+    where
+        S: Fn(usize, usize) -> bool,
+        F: FnMut(&[usize]) -> bool,
+    {
+        if self.stopped {
             return false;
         }
 
+        let graph = self.graph;
         let mut found = false;
-        stack.push(v);
-        blocked[v] = true;
+        self.stack.push(v);
+        self.blocked[v] = true;
+        self.dirty.push(v);
 
-        for &w in graph.successors_slice(v) {
-            // Only consider nodes >= min_node (Johnson's optimization)
-            if w < min_node {
+        for &w in graph.successors(v) {
+            let w = w as usize;
+            if !in_scope(v, w) {
                 continue;
             }
 
             if w == start {
                 // Found a cycle
-                cycles.push(stack.clone());
                 found = true;
-                if cycles.len() >= max_cycles {
-                    stack.pop();
-                    return found;
-                }
-            } else if !blocked[w] {
-                if circuit(
-                    w,
-                    start,
-                    graph,
-                    blocked,
-                    blocked_map,
-                    stack,
-                    cycles,
-                    max_cycles,
-                    min_node,
-                ) {
-                    found = true;
+                if !emit(&self.stack) {
+                    self.stopped = true;
                 }
+            } else if !self.blocked[w] && self.circuit(w, start, in_scope, emit) {
+                found = true;
+            }
+
+            if self.stopped {
+                self.stack.pop();
+                return found;
             }
         }
 
         if found {
-            unblock(v, blocked, blocked_map);
+            self.unblock(v);
         } else {
-            for &w in graph.successors_slice(v) {
-                if w >= min_node {
-                    blocked_map[w].insert(v);
+            for &w in graph.successors(v) {
+                let w = w as usize;
+                if in_scope(v, w) {
+                    // Node indices fit in u32: DiGraph refuses to grow past that
+                    let v32 = v as u32;
+                    if !self.blocked_map[w].contains(&v32) {
+                        self.blocked_map[w].push(v32);
+                    }
+                    self.dirty.push(w);
                 }
             }
         }
 
-        stack.pop();
+        self.stack.pop();
         found
     }
+}
+
+/// Enumerate elementary cycles using Johnson's algorithm.
+///
+/// Reference: Donald B. Johnson, "Finding All the Elementary Circuits of a Directed Graph"
+/// SIAM J. Computing, Vol. 4, No. 1, March 1975
+///
+/// # Arguments
+/// * `graph` - The directed graph
+/// * `max_cycles` - Maximum number of cycles to find (prevents exponential blowup)
+///
+/// # Returns
+/// Vector of cycles, each cycle is a vector of node indices in order
+pub fn enumerate_cycles<G: Adjacency>(graph: &G, max_cycles: usize) -> Vec<Vec<usize>> {
+    enumerate_cycles_filtered(graph, max_cycles, |_, _| true)
+}
+
+/// Johnson's algorithm over a virtual view of the graph.
+///
+/// Edges for which `keep(from, to)` returns false are treated as absent.
+pub(crate) fn enumerate_cycles_filtered<G, K>(
+    graph: &G,
+    max_cycles: usize,
+    keep: K,
+) -> Vec<Vec<usize>>
+where
+    G: Adjacency,
+    K: Fn(usize, usize) -> bool,
+{
+    let mut cycles: Vec<Vec<usize>> = Vec::new();
+    if max_cycles == 0 {
+        return cycles;
+    }
+    // Johnson's search already starts each cycle at its smallest node and
+    // finds it once; canonicalizing and deduplicating guards that invariant.
+    let mut seen: HashSet<Vec<usize>> = HashSet::new();
+    for_each_cycle_filtered(graph, keep, |cycle| {
+        let cycle = canonical_rotation(cycle);
+        if seen.insert(cycle.clone()) {
+            cycles.push(cycle);
+        }
+        cycles.len() < max_cycles
+    });
+    cycles
+}
+
+/// Rotate a cycle so that its smallest node comes first.
+fn canonical_rotation(cycle: &[usize]) -> Vec<usize> {
+    let min_pos = cycle
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &v)| v)
+        .map_or(0, |(i, _)| i);
+    let mut rotated = cycle.to_vec();
+    rotated.rotate_left(min_pos);
+    rotated
+}
+
+/// Stream elementary cycles to a callback instead of collecting them.
+///
+/// Cycles are found in the same order as `enumerate_cycles` and passed to
+/// `f` as a borrowed slice, so nothing is allocated per cycle. Returning
+/// false from `f` stops the search immediately.
+pub fn for_each_cycle<G: Adjacency, F: FnMut(&[usize]) -> bool>(graph: &G, f: F) {
+    for_each_cycle_filtered(graph, |_, _| true, f)
+}
+
+/// Streaming Johnson's algorithm; `keep` as in `enumerate_cycles_filtered`.
+pub(crate) fn for_each_cycle_filtered<G, K, F>(graph: &G, keep: K, mut f: F)
+where
+    G: Adjacency,
+    K: Fn(usize, usize) -> bool,
+    F: FnMut(&[usize]) -> bool,
+{
+    let n = graph.len();
+    if n == 0 {
+        return;
+    }
+
+    // Cycles never leave an SCC, so trivial SCCs can't start one
+    let scc = tarjan_scc_filtered(graph, &keep);
+    let mut johnson = Johnson::new(graph);
 
     // Run Johnson's algorithm starting from each node
     for start in 0..n {
-        if cycles.len() >= max_cycles {
+        if johnson.stopped {
             break;
         }
 
-        // Reset blocked state
-        for b in &mut blocked {
-            *b = false;
-        }
-        for s in &mut blocked_map {
-            s.clear();
+        // Removed nodes belong to no component
+        let comp = scc.component_of(start);
+        if comp == usize::MAX
+            || scc.components[comp].len() == 1
+                && !(graph.has_edge(start, start) && keep(start, start))
+        {
+            continue;
         }
 
-        circuit(
-            start,
-            start,
-            graph,
-            &mut blocked,
-            &mut blocked_map,
-            &mut stack,
-            &mut cycles,
-            max_cycles,
-            start,
-        );
+        johnson.reset();
+
+        // Only consider nodes >= start (Johnson's optimization) in the same SCC
+        let in_scope = |v: usize, w: usize| w >= start && scc.component_of(w) == comp && keep(v, w);
+        johnson.circuit(start, start, &in_scope, &mut f);
+    }
+}
+
+/// Enumerate elementary cycles using only edges whose kind is in `kind_mask`.
+///
+/// See `tarjan_scc_masked` for the mask format.
+pub fn enumerate_cycles_masked(
+    graph: &DiGraph,
+    max_cycles: usize,
+    kind_mask: u32,
+) -> Vec<Vec<usize>> {
+    enumerate_cycles(&CsrGraph::masked(graph, kind_mask), max_cycles)
+}
+
+/// Enumerate the elementary cycles that pass through `node`.
+///
+/// Runs a single Johnson circuit search rooted at `node`, restricted to the
+/// node's strongly connected component: nodes outside it can never lead back,
+/// so those branches are pruned up front. Each cycle starts with `node`.
+pub fn cycles_through_node(graph: &DiGraph, node: usize, max_cycles: usize) -> Vec<Vec<usize>> {
+    let n = graph.len();
+    if node >= n || max_cycles == 0 {
+        return Vec::new();
     }
 
+    let scc = tarjan_scc(graph);
+    // Removed nodes belong to no component
+    let comp = scc.component_of(node);
+    if comp == usize::MAX {
+        return Vec::new();
+    }
+
+    let mut cycles: Vec<Vec<usize>> = Vec::new();
+    let mut johnson = Johnson::new(graph);
+    johnson.circuit(node, node, &|_, w| scc.component_of(w) == comp, &mut |cycle| {
+        cycles.push(cycle.to_vec());
+        cycles.len() < max_cycles
+    });
+
     cycles
 }
 
 /// Result of cycle enumeration with metadata.
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct CycleEnumerationResult {
     /// List of cycles found
     pub cycles: Vec<Vec<usize>>,
@@ -248,6 +507,25 @@ pub struct CycleEnumerationResult {
     pub truncated: bool,
     /// Number of cycles found
     pub count: usize,
+    /// Components (indices into `tarjan_scc(graph).components`) left out by
+    /// `enumerate_cycles_bounded` for being too large; omitted from JSON when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_sccs: Vec<usize>,
+}
+
+impl CycleEnumerationResult {
+    /// Encode in the compact binary format for caching (see `io::binary`).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        crate::io::binary::cycles_to_bytes(self)
+    }
+
+    /// Decode a result produced by `to_bytes`.
+    ///
+    /// # Errors
+    /// See `io::binary::cycles_from_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<CycleEnumerationResult, String> {
+        crate::io::binary::cycles_from_bytes(bytes)
+    }
 }
 
 /// Enumerate cycles with metadata about truncation.
@@ -258,6 +536,198 @@ pub fn enumerate_cycles_with_info(graph: &DiGraph, max_cycles: usize) -> CycleEn
         cycles,
         truncated: count >= max_cycles,
         count,
+        skipped_sccs: Vec::new(),
+    }
+}
+
+/// Enumerate cycles, skipping any SCC with more than `max_nodes_per_scc` nodes.
+///
+/// A dense SCC can hold exponentially many cycles, and Johnson's blocked
+/// lists grow with it, so a single pathological tangle could stall the
+/// viewer. Cycles never leave their SCC, so skipping the large ones still
+/// enumerates the tractable regions completely (up to `max_cycles`). The
+/// skipped components are listed in `skipped_sccs`; `truncated` only
+/// reports the `max_cycles` limit.
+pub fn enumerate_cycles_bounded(
+    graph: &DiGraph,
+    max_cycles: usize,
+    max_nodes_per_scc: usize,
+) -> CycleEnumerationResult {
+    let scc = tarjan_scc(graph);
+    let too_big = |c: usize| scc.components[c].len() > max_nodes_per_scc;
+    let skipped_sccs: Vec<usize> = (0..scc.components.len()).filter(|&c| too_big(c)).collect();
+
+    // Dropping the edges out of a skipped SCC removes all of its cycles and
+    // none elsewhere, since edges leaving an SCC are on no cycle
+    let cycles = enumerate_cycles_filtered(graph, max_cycles, |u, _| !too_big(scc.component_of(u)));
+    let count = cycles.len();
+    CycleEnumerationResult {
+        cycles,
+        truncated: count >= max_cycles,
+        count,
+        skipped_sccs,
+    }
+}
+
+/// Result of cycle enumeration with node labels instead of indices.
+#[derive(Serialize)]
+pub struct LabeledCycleResult {
+    /// List of cycles found, each a list of node labels in cycle order
+    pub cycles: Vec<Vec<String>>,
+    /// Whether max_cycles limit was reached
+    pub truncated: bool,
+    /// Number of cycles found
+    pub count: usize,
+}
+
+/// Enumerate cycles, mapping each node index to its label.
+///
+/// Same cycles and truncation metadata as `enumerate_cycles_with_info`.
+pub fn enumerate_cycles_labeled(graph: &DiGraph, max_cycles: usize) -> LabeledCycleResult {
+    let info = enumerate_cycles_with_info(graph, max_cycles);
+    let cycles = info
+        .cycles
+        .iter()
+        .map(|cycle| {
+            cycle
+                .iter()
+                .map(|&v| graph.node_id(v).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    LabeledCycleResult {
+        cycles,
+        truncated: info.truncated,
+        count: info.count,
+    }
+}
+
+/// Edges of a cycle given as a node list, including the closing edge back to the start.
+fn cycle_edges(cycle: &[usize]) -> Vec<(usize, usize)> {
+    (0..cycle.len())
+        .map(|i| (cycle[i], cycle[(i + 1) % cycle.len()]))
+        .collect()
+}
+
+/// Enumerate cycles as ordered edge lists.
+///
+/// Same cycles as `enumerate_cycles`, but each cycle is given as its edges
+/// in traversal order, ending with the closing edge back to the first node.
+/// A self-loop yields a single (v, v) edge.
+pub fn enumerate_cycle_edges(graph: &DiGraph, max_cycles: usize) -> Vec<Vec<(usize, usize)>> {
+    enumerate_cycles(graph, max_cycles)
+        .iter()
+        .map(|cycle| cycle_edges(cycle))
+        .collect()
+}
+
+// ============================================================================
+// Cycle Count Estimation
+// ============================================================================
+
+/// Estimated number of elementary cycles, for graphs too tangled to enumerate.
+#[derive(Debug, Clone, Serialize)]
+pub struct CycleEstimate {
+    /// Cycles actually found by (partial) Johnson enumeration
+    pub lower_bound: usize,
+    /// Estimated total number of elementary cycles
+    pub estimate: f64,
+    /// True if enumeration completed, so `lower_bound` is the exact count
+    pub exact: bool,
+}
+
+/// Estimate the number of elementary cycles without full enumeration.
+///
+/// First runs Johnson's algorithm capped at `samples` cycles. If that finishes
+/// under the cap the count is exact. Otherwise the count becomes a lower bound
+/// and the total is estimated with `samples` randomized DFS probes (Knuth's
+/// estimator over Johnson's search tree: each probe picks a random start node
+/// and walks random simple paths through higher-indexed nodes of the same SCC,
+/// weighting each closing edge back to the start by the product of the branching
+/// factors seen so far).
+///
+/// The `seed` makes the estimate reproducible. With `samples == 0` nothing
+/// is enumerated or probed: a graph without cycles still gets an exact 0,
+/// and any other graph gets a lower bound and estimate of 0, not exact.
+pub fn estimate_cycle_count(graph: &DiGraph, samples: usize, seed: u64) -> CycleEstimate {
+    let n = graph.len();
+    let scc = tarjan_scc(graph);
+
+    // Only nodes in non-trivial SCCs (or with self-loops) can start a cycle;
+    // removed nodes are in no component
+    let starts: Vec<usize> = (0..n)
+        .filter(|&v| {
+            let comp = scc.component_of(v);
+            comp != usize::MAX && (scc.components[comp].len() > 1 || graph.has_edge(v, v))
+        })
+        .collect();
+
+    if starts.is_empty() || samples == 0 {
+        return CycleEstimate {
+            lower_bound: 0,
+            estimate: 0.0,
+            exact: starts.is_empty(),
+        };
+    }
+
+    let found = enumerate_cycles(graph, samples).len();
+    if found < samples {
+        return CycleEstimate {
+            lower_bound: found,
+            estimate: found as f64,
+            exact: true,
+        };
+    }
+
+    // LCG (same generator as betweenness sampling)
+    let mut rng_state = seed;
+    let mut next = |bound: usize| -> usize {
+        rng_state = rng_state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1);
+        (rng_state >> 33) as usize % bound
+    };
+
+    let mut visited = vec![false; n];
+    let mut path: Vec<usize> = Vec::new();
+    let mut choices: Vec<usize> = Vec::new();
+    let mut total = 0.0;
+
+    for _ in 0..samples {
+        let start = starts[next(starts.len())];
+        let mut weight = 1.0;
+        let mut v = start;
+        visited[start] = true;
+        path.push(start);
+
+        loop {
+            if graph.has_edge(v, start) {
+                total += weight;
+            }
+            choices.clear();
+            choices.extend(graph.out_neighbors(v).filter(|&w| {
+                w > start && !visited[w] && scc.component_of(w) == scc.component_of(start)
+            }));
+            if choices.is_empty() {
+                break;
+            }
+            weight *= choices.len() as f64;
+            v = choices[next(choices.len())];
+            visited[v] = true;
+            path.push(v);
+        }
+
+        for u in path.drain(..) {
+            visited[u] = false;
+        }
+    }
+
+    let sampled = starts.len() as f64 * total / samples as f64;
+
+    CycleEstimate {
+        lower_bound: found,
+        estimate: sampled.max(found as f64),
+        exact: false,
     }
 }
 
@@ -325,18 +795,15 @@ pub fn cycle_break_suggestions(
     let cycles = &cycle_info.cycles;
 
     // Build a map of edge -> cycles it appears in
-    let mut edge_cycle_count: std::collections::HashMap<(usize, usize), usize> =
-        std::collections::HashMap::new();
+    let mut edge_cycle_count: HashMap<(usize, usize), usize> = HashMap::new();
 
     for cycle in cycles {
         if cycle.len() < 2 {
             continue;
         }
         // Count edges in this cycle
-        for i in 0..cycle.len() {
-            let from = cycle[i];
-            let to = cycle[(i + 1) % cycle.len()];
-            *edge_cycle_count.entry((from, to)).or_insert(0) += 1;
+        for edge in cycle_edges(cycle) {
+            *edge_cycle_count.entry(edge).or_insert(0) += 1;
         }
     }
 
@@ -352,7 +819,7 @@ pub fn cycle_break_suggestions(
     let mut suggestions: Vec<CycleBreakItem> = Vec::new();
 
     for &from in &cycle_nodes {
-        for &to in graph.successors_slice(from) {
+        for to in graph.out_neighbors(from) {
             if cycle_nodes.contains(&to) {
                 let cycles_broken = edge_cycle_count.get(&(from, to)).copied().unwrap_or(0);
                 let collateral = graph.successors_slice(from).len() + graph.predecessors_slice(to).len();
@@ -386,6 +853,255 @@ pub fn cycle_break_suggestions(
     }
 }
 
+/// Per-edge "fix impact": how many enumerated cycles contain the edge.
+#[derive(Debug, Clone, Serialize)]
+pub struct EdgeCycleScore {
+    /// Source node of the edge
+    pub from: usize,
+    /// Target node of the edge
+    pub to: usize,
+    /// Number of enumerated cycles containing this edge
+    pub cycle_count: usize,
+    /// cycle_count / total enumerated cycles
+    pub fraction: f64,
+    /// True if enumeration hit max_cycles, so scores may be undercounted
+    pub partial: bool,
+}
+
+/// Rank edges by how many cycles they participate in.
+///
+/// Scores every edge inside a non-trivial SCC (plus self-loops, which
+/// trivially score 1 cycle each). Sorted by cycle_count descending, then
+/// by (from, to) ascending.
+pub fn edge_cycle_participation(graph: &DiGraph, max_cycles: usize) -> Vec<EdgeCycleScore> {
+    let scc = tarjan_scc(graph);
+    let info = enumerate_cycles_with_info(graph, max_cycles);
+
+    let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+    for cycle in &info.cycles {
+        for edge in cycle_edges(cycle) {
+            *counts.entry(edge).or_insert(0) += 1;
+        }
+    }
+
+    let mut comp_of = vec![usize::MAX; graph.len()];
+    for (id, component) in scc.components.iter().enumerate() {
+        if component.len() > 1 {
+            for &v in component {
+                comp_of[v] = id;
+            }
+        }
+    }
+
+    let total = info.count.max(1) as f64;
+    let mut scores: Vec<EdgeCycleScore> = graph
+        .edges()
+        .filter(|&(from, to)| {
+            from == to || (comp_of[from] != usize::MAX && comp_of[from] == comp_of[to])
+        })
+        .map(|(from, to)| {
+            let cycle_count = counts.get(&(from, to)).copied().unwrap_or(0);
+            EdgeCycleScore {
+                from,
+                to,
+                cycle_count,
+                fraction: cycle_count as f64 / total,
+                partial: info.truncated,
+            }
+        })
+        .collect();
+
+    scores.sort_by(|a, b| {
+        b.cycle_count
+            .cmp(&a.cycle_count)
+            .then((a.from, a.to).cmp(&(b.from, b.to)))
+    });
+    scores
+}
+
+/// Suggest a set of edges whose removal makes the graph acyclic.
+///
+/// Uses the Eades–Lin–Smyth greedy ordering: repeatedly peel sinks to the
+/// right end and sources to the left end of a vertex sequence, otherwise
+/// move the node with the largest (out-degree − in-degree) to the left.
+/// Edges pointing backwards in the final sequence, plus self-loops, form
+/// the feedback arc set. Only nodes in non-trivial SCCs take part, since
+/// edges between components never lie on a cycle. A bucket queue on
+/// out-degree − in-degree keeps the whole pass O(V + E).
+///
+/// This is a heuristic: the result always breaks every cycle, but it is not
+/// guaranteed to be minimal (minimum feedback arc set is NP-hard).
+///
+/// # Returns
+/// Edges (from, to) to remove, sorted ascending.
+pub fn feedback_arc_set(graph: &DiGraph) -> Vec<(usize, usize)> {
+    let n = graph.len();
+    let scc = tarjan_scc(graph);
+
+    let mut arcs: Vec<(usize, usize)> = (0..n)
+        .filter(|&v| graph.has_edge(v, v))
+        .map(|v| (v, v))
+        .collect();
+
+    // Components are disjoint, so these are allocated once and each
+    // component only touches its own members' slots
+    let mut out_deg = vec![0isize; n];
+    let mut in_deg = vec![0isize; n];
+    let mut removed = vec![false; n];
+    let mut pos = vec![0usize; n];
+
+    for (id, component) in scc.components.iter().enumerate() {
+        if component.len() < 2 {
+            continue;
+        }
+        let in_comp = |w: usize| scc.component_of(w) == id;
+
+        // Degrees within the component, ignoring self-loops
+        for &v in component {
+            for w in graph.out_neighbors(v) {
+                if w != v && in_comp(w) {
+                    out_deg[v] += 1;
+                    in_deg[w] += 1;
+                }
+            }
+        }
+
+        // Bucket queue on out - in, shifted to be non-negative. Entries go
+        // stale when a degree changes; the node is pushed again instead of
+        // moved, and stale entries are skipped when popped.
+        let offset = component.len() as isize;
+        let bucket = |v: usize, out_deg: &[isize], in_deg: &[isize]| {
+            (out_deg[v] - in_deg[v] + offset) as usize
+        };
+        let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); 2 * component.len() + 1];
+        let mut top = 0;
+        for &v in component {
+            let b = bucket(v, &out_deg, &in_deg);
+            buckets[b].push(v);
+            top = top.max(b);
+        }
+        let mut sinks: Vec<usize> = Vec::new();
+        let mut sources: Vec<usize> = Vec::new();
+
+        let mut left: Vec<usize> = Vec::with_capacity(component.len());
+        let mut right: Vec<usize> = Vec::new();
+        for _ in 0..component.len() {
+            // Peel sinks to the right and sources to the left; otherwise
+            // take the largest out - in to the left
+            let v = if let Some(v) = pop_live(&mut sinks, &removed) {
+                right.push(v);
+                v
+            } else if let Some(v) = pop_live(&mut sources, &removed) {
+                left.push(v);
+                v
+            } else {
+                let v = loop {
+                    match buckets[top].pop() {
+                        Some(v) if !removed[v] && bucket(v, &out_deg, &in_deg) == top => break v,
+                        Some(_) => {}
+                        None => top -= 1,
+                    }
+                };
+                left.push(v);
+                v
+            };
+
+            removed[v] = true;
+            for w in graph.out_neighbors(v) {
+                if w != v && in_comp(w) && !removed[w] {
+                    in_deg[w] -= 1;
+                    if in_deg[w] == 0 {
+                        sources.push(w);
+                    }
+                    let b = bucket(w, &out_deg, &in_deg);
+                    buckets[b].push(w);
+                    top = top.max(b);
+                }
+            }
+            for u in graph.in_neighbors(v) {
+                if u != v && in_comp(u) && !removed[u] {
+                    out_deg[u] -= 1;
+                    if out_deg[u] == 0 {
+                        sinks.push(u);
+                    }
+                    buckets[bucket(u, &out_deg, &in_deg)].push(u);
+                }
+            }
+        }
+
+        // Sequence = left ++ reverse(right)
+        for (i, &v) in left.iter().chain(right.iter().rev()).enumerate() {
+            pos[v] = i;
+        }
+
+        for &v in component {
+            for w in graph.out_neighbors(v) {
+                if w != v && in_comp(w) && pos[w] < pos[v] {
+                    arcs.push((v, w));
+                }
+            }
+        }
+    }
+
+    arcs.sort_unstable();
+    arcs
+}
+
+/// Pop entries until one that hasn't been removed yet.
+fn pop_live(stack: &mut Vec<usize>, removed: &[bool]) -> Option<usize> {
+    while let Some(v) = stack.pop() {
+        if !removed[v] {
+            return Some(v);
+        }
+    }
+    None
+}
+
+/// Suggest a small set of nodes whose removal makes the graph acyclic.
+///
+/// Greedy heuristic: nodes with self-loops are taken first, then each round
+/// recomputes the SCCs of what's left and takes the node with the highest
+/// degree inside each non-trivial component (lowest index on ties), until
+/// no cycle remains. Always breaks every cycle but isn't guaranteed minimal
+/// (minimum feedback vertex set is NP-hard).
+///
+/// # Returns
+/// Node indices to remove, sorted ascending.
+pub fn feedback_vertex_set(graph: &DiGraph) -> Vec<usize> {
+    let n = graph.len();
+    let mut gone: Vec<bool> = (0..n).map(|v| graph.has_edge(v, v)).collect();
+
+    loop {
+        let scc = tarjan_scc_filtered(graph, |u, v| !gone[u] && !gone[v]);
+
+        let mut picked = Vec::new();
+        for (id, component) in scc.components.iter().enumerate() {
+            if component.len() < 2 {
+                continue;
+            }
+            let degree = |v: usize| {
+                let succs = graph.out_neighbors(v);
+                succs.chain(graph.in_neighbors(v)).filter(|&w| scc.component_of(w) == id).count()
+            };
+            let best = component
+                .iter()
+                .copied()
+                .max_by_key(|&v| (degree(v), std::cmp::Reverse(v)))
+                .unwrap();
+            picked.push(best);
+        }
+
+        if picked.is_empty() {
+            break;
+        }
+        for v in picked {
+            gone[v] = true;
+        }
+    }
+
+    (0..n).filter(|&v| gone[v]).collect()
+}
+
 /// Quick check for edges that could break cycles.
 ///
 /// A simplified version that only looks at SCC membership without
@@ -407,7 +1123,7 @@ pub fn quick_cycle_break_edges(graph: &DiGraph, limit: usize) -> Vec<CycleBreakI
     let mut suggestions: Vec<CycleBreakItem> = Vec::new();
 
     for &from in &cycle_nodes {
-        for &to in graph.successors_slice(from) {
+        for to in graph.out_neighbors(from) {
             if cycle_nodes.contains(&to) {
                 // Heuristic: edges with low total degree are better to remove
                 let collateral = graph.successors_slice(from).len() + graph.predecessors_slice(to).len();
@@ -456,11 +1172,20 @@ mod tests {
     fn test_scc_self_loop() {
         let mut graph = DiGraph::new();
         let a = graph.add_node("a");
-        graph.add_edge(a, a);
+        graph.add_edge(a, a).unwrap();
         let result = tarjan_scc(&graph);
-        // Self-loop creates SCC of size 1 with edge to itself
-        // Tarjan considers this a cycle
-        assert!(result.has_cycles || result.components[0].len() == 1);
+        // Self-loop creates SCC of size 1 with edge to itself, which is a cycle
+        assert_eq!(result.components, vec![vec![a]]);
+        assert!(result.has_cycles);
+        assert_eq!(result.cycle_count, 1);
+
+        // Single nodes without self-edges are not cycles
+        let b = graph.add_node("b");
+        graph.add_edge(a, b).unwrap();
+        let result = tarjan_scc(&graph);
+        assert_eq!(result.components, vec![vec![a], vec![b]]);
+        assert_eq!(result.cycle_count, 1);
+        assert!(has_cycles(&graph));
     }
 
     #[test]
@@ -470,17 +1195,15 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let result = tarjan_scc(&graph);
         assert!(result.has_cycles);
         assert_eq!(result.cycle_count, 1);
-        // One SCC with all 3 nodes
-        let big_scc = result.components.iter().find(|c| c.len() > 1);
-        assert!(big_scc.is_some());
-        assert_eq!(big_scc.unwrap().len(), 3);
+        // One SCC with all 3 nodes, in ascending order
+        assert_eq!(result.components, vec![vec![a, b, c]]);
     }
 
     #[test]
@@ -490,13 +1213,13 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let result = tarjan_scc(&graph);
         assert!(!result.has_cycles);
-        // Each node is its own SCC
-        assert_eq!(result.components.len(), 3);
+        // Each node is its own SCC, ordered by index
+        assert_eq!(result.components, vec![vec![a], vec![b], vec![c]]);
     }
 
     #[test]
@@ -508,14 +1231,60 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
-        graph.add_edge(c, d);
-        graph.add_edge(d, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, c).unwrap();
 
         let result = tarjan_scc(&graph);
         assert!(result.has_cycles);
         assert_eq!(result.cycle_count, 2);
+        assert_eq!(result.components, vec![vec![a, b], vec![c, d]]);
+    }
+
+    #[test]
+    fn test_scc_order_independent_of_insertion() {
+        // Same 3-cycle plus tail, with edges added in a different order
+        // and the DFS entering the cycle from its highest index
+        let mut graph = DiGraph::new();
+        for id in ["a", "b", "c", "d"] {
+            graph.add_node(id);
+        }
+        graph.add_edge(3, 2).unwrap();
+        graph.add_edge(2, 0).unwrap();
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+
+        let result = tarjan_scc(&graph);
+        assert_eq!(result.components, vec![vec![0, 1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn test_scc_membership_queries() {
+        // a -> b -> c -> a, c -> d; e removed
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.remove_node(e);
+
+        let result = tarjan_scc(&graph);
+        assert!(result.same_component(a, c));
+        assert!(result.same_component(d, d));
+        assert!(!result.same_component(c, d));
+        assert!(!result.same_component(e, e));
+        assert_eq!(result.component_of(d), 1);
+        assert_eq!(result.component_of(e), usize::MAX);
+        assert_eq!(result.component_of(99), usize::MAX);
+        for (id, component) in result.components.iter().enumerate() {
+            assert!(component.iter().all(|&v| result.component_of(v) == id));
+        }
     }
 
     #[test]
@@ -532,8 +1301,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let cycles = enumerate_cycles(&graph, 100);
         assert!(cycles.is_empty());
@@ -546,9 +1315,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let cycles = enumerate_cycles(&graph, 100);
         assert_eq!(cycles.len(), 1);
@@ -561,8 +1330,8 @@ mod tests {
         let mut graph = DiGraph::new();
         let a = graph.add_node("a");
         let b = graph.add_node("b");
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
 
         let cycles = enumerate_cycles(&graph, 100);
         assert_eq!(cycles.len(), 1);
@@ -578,20 +1347,69 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
-        graph.add_edge(b, c);
-        graph.add_edge(c, b);
-        graph.add_edge(c, d);
-        graph.add_edge(d, c);
-        graph.add_edge(d, a);
-        graph.add_edge(a, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, b).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, c).unwrap();
+        graph.add_edge(d, a).unwrap();
+        graph.add_edge(a, d).unwrap();
 
         // Limit to 2 cycles
         let cycles = enumerate_cycles(&graph, 2);
         assert_eq!(cycles.len(), 2);
     }
 
+    #[test]
+    fn test_for_each_cycle_stops_early() {
+        // Same multi-cycle graph as above
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        for (u, v) in [(a, b), (b, a), (b, c), (c, b), (c, d), (d, c), (d, a), (a, d)] {
+            graph.add_edge(u, v).unwrap();
+        }
+
+        let mut first_two = Vec::new();
+        let mut calls = 0;
+        for_each_cycle(&graph, |cycle| {
+            calls += 1;
+            first_two.push(cycle.to_vec());
+            first_two.len() < 2
+        });
+        assert_eq!(calls, 2);
+        assert_eq!(first_two, enumerate_cycles(&graph, 2));
+
+        // Running to completion sees every cycle
+        let mut total = 0;
+        for_each_cycle(&graph, |_| {
+            total += 1;
+            true
+        });
+        assert_eq!(total, enumerate_cycles(&graph, 1000).len());
+    }
+
+    #[test]
+    fn test_enumerate_many_isolated_nodes() {
+        // 50k isolated nodes plus one 3-cycle: per-start resets must not be O(n)
+        let mut graph = DiGraph::new();
+        for i in 0..50_000 {
+            graph.add_node(&format!("n{}", i));
+        }
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let cycles = enumerate_cycles(&graph, 100);
+        assert_eq!(cycles, vec![vec![a, b, c]]);
+    }
+
     #[test]
     fn test_enumerate_diamond_with_back_edge() {
         //     a
@@ -604,25 +1422,58 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
-        graph.add_edge(d, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, a).unwrap();
 
         let cycles = enumerate_cycles(&graph, 100);
         // Two cycles: a->b->d->a and a->c->d->a
         assert_eq!(cycles.len(), 2);
     }
 
+    #[test]
+    fn test_enumerate_complete_triangle_no_rotations() {
+        // Every ordered pair of a, b, c is an edge: three 2-cycles plus the
+        // triangle in each direction, each reported once from its smallest node
+        let mut graph = DiGraph::new();
+        for id in ["a", "b", "c"] {
+            graph.add_node(id);
+        }
+        for u in 0..3 {
+            for v in 0..3 {
+                if u != v {
+                    graph.add_edge(u, v).unwrap();
+                }
+            }
+        }
+
+        let mut cycles = enumerate_cycles(&graph, 100);
+        assert_eq!(cycles.len(), 5);
+        assert!(cycles.iter().all(|c| c[0] == *c.iter().min().unwrap()));
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            vec![vec![0, 1], vec![0, 1, 2], vec![0, 2], vec![0, 2, 1], vec![1, 2]]
+        );
+    }
+
+    #[test]
+    fn test_canonical_rotation() {
+        assert_eq!(canonical_rotation(&[3, 1, 2]), vec![1, 2, 3]);
+        assert_eq!(canonical_rotation(&[5]), vec![5]);
+        assert!(canonical_rotation(&[]).is_empty());
+    }
+
     #[test]
     fn test_enumerate_with_info() {
         // a -> b -> a
         let mut graph = DiGraph::new();
         let a = graph.add_node("a");
         let b = graph.add_node("b");
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
 
         let result = enumerate_cycles_with_info(&graph, 100);
         assert_eq!(result.count, 1);
@@ -636,19 +1487,103 @@ mod tests {
         assert!(result_one.truncated);
     }
 
+    #[test]
+    fn test_cycles_through_node() {
+        // a -> b -> a and a -> c -> a share a; d -> e -> d is separate
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(d, e).unwrap();
+        graph.add_edge(e, d).unwrap();
+
+        let mut cycles = cycles_through_node(&graph, a, 100);
+        cycles.sort();
+        assert_eq!(cycles, vec![vec![a, b], vec![a, c]]);
+
+        // Node with a higher index than another cycle member still finds it
+        assert_eq!(cycles_through_node(&graph, c, 100), vec![vec![c, a]]);
+        assert_eq!(cycles_through_node(&graph, d, 100), vec![vec![d, e]]);
+        assert_eq!(cycles_through_node(&graph, a, 1).len(), 1);
+        assert!(cycles_through_node(&graph, 99, 100).is_empty());
+
+        graph.remove_node(b);
+        assert_eq!(cycles_through_node(&graph, a, 100), vec![vec![a, c]]);
+        assert!(cycles_through_node(&graph, b, 100).is_empty());
+    }
+
+    #[test]
+    fn test_enumerate_cycles_labeled() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("bd-1");
+        let b = graph.add_node("bd-2");
+        let c = graph.add_node("bd-3");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let result = enumerate_cycles_labeled(&graph, 100);
+        assert_eq!(result.count, 1);
+        assert!(!result.truncated);
+        assert_eq!(result.cycles[0], vec!["bd-1", "bd-2", "bd-3"]);
+    }
+
+    #[test]
+    fn test_enumerate_cycles_labeled_unicode() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("bd-ü1");
+        let b = graph.add_node("任务-2");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+
+        let result = enumerate_cycles_labeled(&graph, 100);
+        let json = serde_json::to_string(&result).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["cycles"][0][0], "bd-ü1");
+        assert_eq!(parsed["cycles"][0][1], "任务-2");
+    }
+
+    #[test]
+    fn test_enumerate_cycle_edges() {
+        // a -> b -> c -> a
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let cycles = enumerate_cycle_edges(&graph, 100);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0], vec![(a, b), (b, c), (c, a)]);
+
+        // Self-loop is a single closing edge
+        let mut looped = DiGraph::new();
+        let x = looped.add_node("x");
+        looped.add_edge(x, x).unwrap();
+        assert_eq!(enumerate_cycle_edges(&looped, 100), vec![vec![(x, x)]]);
+    }
+
     #[test]
     fn test_has_cycles() {
         let mut dag = DiGraph::new();
         let a = dag.add_node("a");
         let b = dag.add_node("b");
-        dag.add_edge(a, b);
+        dag.add_edge(a, b).unwrap();
         assert!(!has_cycles(&dag));
 
         let mut cyclic = DiGraph::new();
         let x = cyclic.add_node("x");
         let y = cyclic.add_node("y");
-        cyclic.add_edge(x, y);
-        cyclic.add_edge(y, x);
+        cyclic.add_edge(x, y).unwrap();
+        cyclic.add_edge(y, x).unwrap();
         assert!(has_cycles(&cyclic));
     }
 
@@ -660,12 +1595,12 @@ mod tests {
             graph.add_node(&format!("n{}", i));
         }
         // Create some cycles
-        graph.add_edge(0, 1);
-        graph.add_edge(1, 2);
-        graph.add_edge(2, 0); // Cycle: 0->1->2->0
-        graph.add_edge(2, 3);
-        graph.add_edge(3, 4);
-        graph.add_edge(4, 2); // Cycle: 2->3->4->2
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        graph.add_edge(2, 0).unwrap(); // Cycle: 0->1->2->0
+        graph.add_edge(2, 3).unwrap();
+        graph.add_edge(3, 4).unwrap();
+        graph.add_edge(4, 2).unwrap(); // Cycle: 2->3->4->2
 
         let scc = tarjan_scc(&graph);
         assert!(scc.has_cycles);
@@ -685,8 +1620,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let result = cycle_break_suggestions(&graph, 10, 100);
         assert!(result.suggestions.is_empty());
@@ -700,8 +1635,8 @@ mod tests {
         let mut graph = DiGraph::new();
         let a = graph.add_node("a");
         let b = graph.add_node("b");
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
 
         let result = cycle_break_suggestions(&graph, 10, 100);
         assert_eq!(result.total_cycles, 1);
@@ -720,9 +1655,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let result = cycle_break_suggestions(&graph, 10, 100);
         assert_eq!(result.total_cycles, 1);
@@ -746,11 +1681,11 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
-        graph.add_edge(d, a); // Shared back edge
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, a).unwrap(); // Shared back edge
 
         let result = cycle_break_suggestions(&graph, 10, 100);
         assert_eq!(result.total_cycles, 2);
@@ -768,8 +1703,8 @@ mod tests {
         let mut graph = DiGraph::new();
         graph.add_node("issue-1");
         graph.add_node("issue-2");
-        graph.add_edge(0, 1);
-        graph.add_edge(1, 0);
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 0).unwrap();
 
         let result = cycle_break_suggestions(&graph, 10, 100);
         assert!(!result.suggestions.is_empty());
@@ -788,7 +1723,7 @@ mod tests {
         }
         // Create a 10-node cycle
         for i in 0..10 {
-            graph.add_edge(i, (i + 1) % 10);
+            graph.add_edge(i, (i + 1) % 10).unwrap();
         }
 
         let result = cycle_break_suggestions(&graph, 3, 100);
@@ -801,8 +1736,8 @@ mod tests {
         let mut graph = DiGraph::new();
         let a = graph.add_node("a");
         let b = graph.add_node("b");
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
 
         let suggestions = quick_cycle_break_edges(&graph, 10);
         assert_eq!(suggestions.len(), 2);
@@ -818,7 +1753,7 @@ mod tests {
         let mut graph = DiGraph::new();
         let a = graph.add_node("a");
         let b = graph.add_node("b");
-        graph.add_edge(a, b);
+        graph.add_edge(a, b).unwrap();
 
         let suggestions = quick_cycle_break_edges(&graph, 10);
         assert!(suggestions.is_empty());
@@ -834,10 +1769,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
-        graph.add_edge(c, d);
-        graph.add_edge(d, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, c).unwrap();
 
         let result = cycle_break_suggestions(&graph, 10, 100);
         assert_eq!(result.total_cycles, 2);
@@ -853,11 +1788,11 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
-        graph.add_edge(a, c);
-        graph.add_edge(c, d);
-        graph.add_edge(d, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, a).unwrap();
 
         let result = cycle_break_suggestions(&graph, 10, 100);
         // Should have suggestions sorted by cycles_broken desc, then collateral asc
@@ -875,4 +1810,360 @@ mod tests {
             }
         }
     }
+
+    // ========================================================================
+    // Cycle Count Estimation Tests
+    // ========================================================================
+
+    #[test]
+    fn test_estimate_cycle_count_exact_small() {
+        let dag = {
+            let mut g = DiGraph::new();
+            let a = g.add_node("a");
+            let b = g.add_node("b");
+            g.add_edge(a, b).unwrap();
+            g
+        };
+        let est = estimate_cycle_count(&dag, 10, 1);
+        assert!(est.exact);
+        assert_eq!(est.lower_bound, 0);
+
+        // Diamond with back edge: exactly 2 cycles
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, a).unwrap();
+
+        let est = estimate_cycle_count(&graph, 100, 42);
+        assert!(est.exact);
+        assert_eq!(est.lower_bound, 2);
+        assert_eq!(est.estimate, 2.0);
+    }
+
+    #[test]
+    fn test_estimate_cycle_count_complete_digraph() {
+        // Complete digraph on 12 nodes has ~10^8 elementary cycles
+        let mut graph = DiGraph::new();
+        for i in 0..12 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for i in 0..12 {
+            for j in 0..12 {
+                if i != j {
+                    graph.add_edge(i, j).unwrap();
+                }
+            }
+        }
+
+        let samples = 200;
+        let partial = enumerate_cycles(&graph, samples).len();
+        let est = estimate_cycle_count(&graph, samples, 7);
+        assert!(!est.exact);
+        assert_eq!(est.lower_bound, partial);
+        assert!(est.estimate >= est.lower_bound as f64);
+
+        // Same seed reproduces the estimate
+        let again = estimate_cycle_count(&graph, samples, 7);
+        assert_eq!(est.estimate, again.estimate);
+
+        // No samples: nothing to go on, but never NaN
+        let none = estimate_cycle_count(&graph, 0, 7);
+        assert!(!none.exact);
+        assert_eq!((none.lower_bound, none.estimate), (0, 0.0));
+        let mut acyclic = DiGraph::new();
+        acyclic.add_node("a");
+        assert!(estimate_cycle_count(&acyclic, 0, 7).exact);
+    }
+
+    #[test]
+    fn test_enumerate_cycles_bounded_skips_large_scc() {
+        // Complete digraph on nodes 0..12 (about 10^8 cycles), then a
+        // separate 2-cycle 12 <-> 13 reached from it
+        let mut graph = DiGraph::new();
+        for i in 0..14 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for i in 0..12 {
+            for j in 0..12 {
+                if i != j {
+                    graph.add_edge(i, j).unwrap();
+                }
+            }
+        }
+        graph.add_edge(11, 12).unwrap();
+        graph.add_edge(12, 13).unwrap();
+        graph.add_edge(13, 12).unwrap();
+
+        let result = enumerate_cycles_bounded(&graph, 1000, 5);
+        assert_eq!(result.cycles, vec![vec![12, 13]]);
+        assert!(!result.truncated);
+        let scc = tarjan_scc(&graph);
+        assert_eq!(result.skipped_sccs, vec![scc.component_of(0)]);
+
+        let json = serde_json::to_string(&enumerate_cycles_bounded(&graph, 5, 20)).unwrap();
+        assert!(!json.contains("skipped_sccs"));
+    }
+
+    #[test]
+    fn test_edge_cycle_participation() {
+        //     a
+        //    / \
+        //   b   c
+        //    \ /
+        //     d -> a (back edge shared by both cycles)
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, a).unwrap();
+        graph.add_edge(d, e).unwrap(); // Leaves the SCC, not scored
+        graph.add_edge(e, e).unwrap(); // Self-loop scores 1
+
+        let scores = edge_cycle_participation(&graph, 100);
+        assert_eq!(scores.len(), 6);
+        assert_eq!((scores[0].from, scores[0].to), (d, a));
+        assert_eq!(scores[0].cycle_count, 2);
+        assert!(!scores[0].partial);
+
+        let score_of = |from, to| scores.iter().find(|s| s.from == from && s.to == to).unwrap();
+        assert_eq!(score_of(a, b).cycle_count, 1);
+        assert_eq!(score_of(a, c).cycle_count, 1);
+        assert_eq!(score_of(e, e).cycle_count, 1);
+        assert!((score_of(d, a).fraction - 2.0 / 3.0).abs() < 1e-9);
+        assert!(!scores.iter().any(|s| s.from == d && s.to == e));
+
+        // Truncated enumeration flags the scores as partial
+        assert!(edge_cycle_participation(&graph, 1).iter().all(|s| s.partial));
+    }
+
+    // ========================================================================
+    // Feedback Arc Set Tests
+    // ========================================================================
+
+    /// Copy of `graph` without the given edges.
+    fn without_edges(graph: &DiGraph, removed: &[(usize, usize)]) -> DiGraph {
+        let mut out = DiGraph::new();
+        for i in 0..graph.len() {
+            out.add_node(&graph.node_id(i).unwrap());
+        }
+        for (from, to) in graph.edges() {
+            if !removed.contains(&(from, to)) {
+                out.add_edge(from, to).unwrap();
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_feedback_vertex_set_complex() {
+        // Same graph as test_complex_graph: 0->1->2->0 and 2->3->4->2 share node 2
+        let mut graph = DiGraph::new();
+        for i in 0..5 {
+            graph.add_node(&format!("n{}", i));
+        }
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        graph.add_edge(2, 0).unwrap();
+        graph.add_edge(2, 3).unwrap();
+        graph.add_edge(3, 4).unwrap();
+        graph.add_edge(4, 2).unwrap();
+
+        let nodes = feedback_vertex_set(&graph);
+        assert_eq!(nodes, vec![2]);
+        for &v in &nodes {
+            graph.remove_node(v);
+        }
+        assert!(!has_cycles(&graph));
+    }
+
+    #[test]
+    fn test_feedback_vertex_set_multiple_rounds() {
+        // Interconnected 4-node graph plus a self-loop and a separate 2-cycle
+        let mut graph = DiGraph::new();
+        for i in 0..7 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for (u, v) in [(0, 1), (1, 0), (1, 2), (2, 1), (2, 3), (3, 2), (3, 0), (0, 3)] {
+            graph.add_edge(u, v).unwrap();
+        }
+        graph.add_edge(4, 4).unwrap();
+        graph.add_edge(5, 6).unwrap();
+        graph.add_edge(6, 5).unwrap();
+
+        let nodes = feedback_vertex_set(&graph);
+        assert!(nodes.contains(&4));
+        assert!(nodes.len() < graph.len());
+        for &v in &nodes {
+            graph.remove_node(v);
+        }
+        assert!(!has_cycles(&graph));
+
+        let mut dag = DiGraph::new();
+        dag.add_node("a");
+        assert!(feedback_vertex_set(&dag).is_empty());
+    }
+
+    #[test]
+    fn test_feedback_arc_set_dag() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.add_edge(a, b).unwrap();
+        assert!(feedback_arc_set(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_feedback_arc_set_interconnected() {
+        // Same interconnected 4-node graph as test_enumerate_max_limit
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, b).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, c).unwrap();
+        graph.add_edge(d, a).unwrap();
+        graph.add_edge(a, d).unwrap();
+
+        let arcs = feedback_arc_set(&graph);
+        assert!(!arcs.is_empty());
+        assert!(arcs.len() < graph.edge_count());
+        assert!(!has_cycles(&without_edges(&graph, &arcs)));
+    }
+
+    #[test]
+    fn test_cycles_skip_removed_nodes() {
+        // 0 <-> 1 and 2 <-> 3, then 0 is removed
+        let mut graph = DiGraph::new();
+        for i in 0..4 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for (u, v) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+            graph.add_edge(u, v).unwrap();
+        }
+        graph.remove_node(0);
+        assert_eq!(enumerate_cycles(&graph, 10), vec![vec![2, 3]]);
+        assert_eq!(estimate_cycle_count(&graph, 10, 1).lower_bound, 1);
+
+        // With every node removed there is nothing left to search
+        for v in 1..4 {
+            graph.remove_node(v);
+        }
+        assert!(enumerate_cycles(&graph, 10).is_empty());
+        assert!(enumerate_cycles_with_info(&graph, 10).cycles.is_empty());
+        assert!(enumerate_cycles_bounded(&graph, 10, 5).cycles.is_empty());
+        let est = estimate_cycle_count(&graph, 10, 1);
+        assert!(est.exact);
+        assert_eq!(est.estimate, 0.0);
+    }
+
+    #[test]
+    fn test_feedback_arc_set_random_graphs_become_acyclic() {
+        for seed in 0..5u64 {
+            let mut state = seed;
+            let mut next = |bound: usize| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                (state >> 33) as usize % bound
+            };
+            let mut graph = DiGraph::new();
+            for i in 0..40 {
+                graph.add_node(&format!("n{}", i));
+            }
+            for _ in 0..120 {
+                graph.add_edge(next(40), next(40)).unwrap();
+            }
+            let arcs = feedback_arc_set(&graph);
+            assert!(!has_cycles(&without_edges(&graph, &arcs)), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_feedback_arc_set_self_loop() {
+        // a -> a, a -> b -> c -> a
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, a).unwrap();
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let arcs = feedback_arc_set(&graph);
+        assert!(arcs.contains(&(a, a)));
+        assert_eq!(arcs.len(), 2);
+        let reduced = without_edges(&graph, &arcs);
+        assert!(enumerate_cycles(&reduced, 10).is_empty());
+    }
+
+    #[test]
+    fn test_kind_mask_excludes_related_cycle() {
+        use crate::graph::EdgeKind;
+        // a -> b -> c blocks, c -> a related: the only cycle needs the related edge
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge_kind(c, a, EdgeKind::RELATED.0).unwrap();
+
+        assert!(tarjan_scc(&graph).has_cycles);
+        assert_eq!(enumerate_cycles(&graph, 10).len(), 1);
+
+        let blocks = EdgeKind::BLOCKS.mask();
+        let scc = tarjan_scc_masked(&graph, blocks);
+        assert!(!scc.has_cycles);
+        assert_eq!(scc.components.len(), 3);
+        assert!(enumerate_cycles_masked(&graph, 10, blocks).is_empty());
+
+        assert!(tarjan_scc_masked(&graph, EdgeKind::ALL).has_cycles);
+        assert_eq!(enumerate_cycles_masked(&graph, 10, EdgeKind::ALL).len(), 1);
+    }
+
+    #[test]
+    fn test_kind_mask_self_loop() {
+        use crate::graph::EdgeKind;
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        graph.add_edge_kind(a, a, EdgeKind::RELATED.0).unwrap();
+
+        assert!(!tarjan_scc_masked(&graph, EdgeKind::BLOCKS.mask()).has_cycles);
+        assert!(enumerate_cycles_masked(&graph, 10, EdgeKind::BLOCKS.mask()).is_empty());
+        assert_eq!(enumerate_cycles_masked(&graph, 10, EdgeKind::RELATED.mask()), vec![vec![a]]);
+    }
+
+    #[test]
+    fn test_doubled_edges_yield_one_cycle() {
+        // Ingesting a <-> b twice (once per dependency type) must not
+        // duplicate the cycle: the graph never stores parallel edges.
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        for _ in 0..2 {
+            graph.add_edge(a, b).unwrap();
+            graph.add_edge(b, a).unwrap();
+        }
+        graph.add_edge_kind(a, b, crate::graph::EdgeKind::RELATED.0).unwrap();
+
+        assert!(graph.has_edge(a, b) && graph.has_edge(b, a));
+        assert_eq!(enumerate_cycles(&graph, 10), vec![vec![a, b]]);
+        assert_eq!(tarjan_scc(&graph).cycle_count, 1);
+    }
 }
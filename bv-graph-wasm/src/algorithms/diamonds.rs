@@ -0,0 +1,132 @@
+//! Diamond detection: work that fans out and converges again.
+//!
+//! A diamond is an apex A and a sink D joined by two routes that share no
+//! bead, like A→{B,C}→D. Each route is owned and scheduled separately, yet
+//! D needs both, so reviewers flag these as coordination risks.
+
+use crate::algorithms::dominators::dominators;
+use crate::algorithms::topo::topological_sort;
+use crate::graph::DiGraph;
+
+/// `(apex, sink)` pairs joined by at least two node-disjoint paths of
+/// length two or more, sorted ascending.
+///
+/// Paths are never enumerated. By Menger's theorem the routes from `apex`
+/// to `sink` split into two disjoint ones exactly when no single bead lies
+/// on all of them, which the dominator tree from `apex` answers: the
+/// sink's predecessors (other than `apex`) must have `apex` as their
+/// nearest common dominator. A direct edge `apex -> sink` is ignored, so a
+/// diamond with a shortcut is still reported. Only the converge points are
+/// reported, not the beads further downstream, and the check costs one
+/// dominator tree per apex with two or more successors, O(V·(V + E)) in the
+/// worst case.
+///
+/// Diamonds are a DAG notion, so a graph with a cycle yields no pairs.
+pub fn diamonds(graph: &DiGraph) -> Vec<(usize, usize)> {
+    let Ok(order) = topological_sort(graph) else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+    let mut depth = vec![0usize; graph.len()];
+    for apex in 0..graph.len() {
+        if graph.is_removed(apex) || graph.out_degree(apex) < 2 {
+            continue;
+        }
+        let idom = dominators(graph, apex).idom;
+        // A dominator precedes what it dominates in topological order
+        depth[apex] = 0;
+        for &v in &order {
+            if let Some(d) = idom[v].filter(|&d| d != v) {
+                depth[v] = depth[d] + 1;
+            }
+        }
+
+        for sink in 0..graph.len() {
+            if sink == apex || idom[sink].is_none() || graph.in_degree(sink) < 2 {
+                continue;
+            }
+            let mut common: Option<usize> = None;
+            for p in graph.in_neighbors(sink) {
+                if p == apex || idom[p].is_none() {
+                    continue;
+                }
+                common = Some(match common {
+                    None => p,
+                    Some(c) => nearest_common_dominator(&idom, &depth, c, p),
+                });
+            }
+            if common == Some(apex) {
+                found.push((apex, sink));
+            }
+        }
+    }
+    found
+}
+
+fn nearest_common_dominator(
+    idom: &[Option<usize>],
+    depth: &[usize],
+    mut a: usize,
+    mut b: usize,
+) -> usize {
+    while a != b {
+        if depth[a] >= depth[b] {
+            a = idom[a].unwrap_or(a);
+        } else {
+            b = idom[b].unwrap_or(b);
+        }
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph_with(n: usize, edges: &[(usize, usize)]) -> DiGraph {
+        let mut graph = DiGraph::new();
+        for i in 0..n {
+            graph.add_node(&format!("n{}", i));
+        }
+        for &(u, v) in edges {
+            graph.add_edge(u, v).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_diamonds_simple_and_shortcut() {
+        let graph = graph_with(4, &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+        assert_eq!(diamonds(&graph), vec![(0, 3)]);
+
+        let graph = graph_with(4, &[(0, 1), (0, 2), (1, 3), (2, 3), (0, 3)]);
+        assert_eq!(diamonds(&graph), vec![(0, 3)]);
+
+        // One route plus the direct edge is not a diamond
+        let graph = graph_with(3, &[(0, 1), (1, 2), (0, 2)]);
+        assert!(diamonds(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_diamonds_chain_and_cycle() {
+        let graph = graph_with(4, &[(0, 1), (1, 2), (2, 3)]);
+        assert!(diamonds(&graph).is_empty());
+
+        let graph = graph_with(4, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 0)]);
+        assert!(diamonds(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_diamonds_need_disjoint_routes() {
+        // 0 -> 1 -> {2, 3} -> 4 -> 5: both routes from 0 pass through 1,
+        // so only 1 is an apex, and 5 lies below the converge point
+        let graph = graph_with(6, &[(0, 1), (1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+        assert_eq!(diamonds(&graph), vec![(1, 4)]);
+
+        // Both predecessors of 5 can be reached through 1, but the routes
+        // 0 -> 1 -> 3 -> 5 and 0 -> 2 -> 4 -> 5 are still disjoint
+        let graph = graph_with(6, &[(0, 1), (0, 2), (1, 3), (1, 4), (2, 4), (3, 5), (4, 5)]);
+        assert_eq!(diamonds(&graph), vec![(0, 4), (0, 5), (1, 5)]);
+    }
+}
@@ -0,0 +1,124 @@
+//! Hop-distance statistics on the undirected view of the graph.
+//!
+//! Direction is ignored so that a plain chain of dependencies has its
+//! middle as the center, as a user looking at the drawing would expect.
+
+use crate::algorithms::components::weakly_connected_components;
+use crate::graph::DiGraph;
+use std::collections::VecDeque;
+
+/// Eccentricity of every node: the largest BFS distance to any node it
+/// can reach, ignoring edge direction.
+///
+/// Unreachable nodes are left out rather than counted as infinitely far,
+/// so an isolated node has eccentricity 0. Removed nodes get None.
+/// Runs one BFS per node, O(V·(V + E)).
+pub fn eccentricities(graph: &DiGraph) -> Vec<Option<usize>> {
+    let n = graph.len();
+    let mut dist = vec![usize::MAX; n];
+    let mut queue = VecDeque::new();
+
+    (0..n)
+        .map(|source| {
+            if graph.is_removed(source) {
+                return None;
+            }
+            dist.fill(usize::MAX);
+            dist[source] = 0;
+            queue.push_back(source);
+            let mut farthest = 0;
+
+            while let Some(v) = queue.pop_front() {
+                farthest = dist[v];
+                let neighbors = graph.out_neighbors(v).chain(graph.in_neighbors(v));
+                for w in neighbors {
+                    if dist[w] == usize::MAX {
+                        dist[w] = dist[v] + 1;
+                        queue.push_back(w);
+                    }
+                }
+            }
+            Some(farthest)
+        })
+        .collect()
+}
+
+/// Largest eccentricity within the largest weakly connected component.
+/// None for a graph without live nodes.
+pub fn diameter(graph: &DiGraph) -> Option<usize> {
+    let ecc = eccentricities(graph);
+    largest_component(graph).iter().filter_map(|&v| ecc[v]).max()
+}
+
+/// Smallest eccentricity within the largest weakly connected component.
+/// None for a graph without live nodes.
+pub fn radius(graph: &DiGraph) -> Option<usize> {
+    let ecc = eccentricities(graph);
+    largest_component(graph).iter().filter_map(|&v| ecc[v]).min()
+}
+
+/// Nodes of the largest weakly connected component (lowest index wins ties).
+fn largest_component(graph: &DiGraph) -> Vec<usize> {
+    let wcc = weakly_connected_components(graph);
+    let mut best: Vec<usize> = Vec::new();
+    for component in wcc.components {
+        if component.len() > best.len() {
+            best = component;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(len: usize) -> DiGraph {
+        let mut graph = DiGraph::new();
+        for i in 0..=len {
+            graph.add_node(&format!("n{}", i));
+        }
+        for i in 0..len {
+            graph.add_edge(i, i + 1).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_path_of_length_four() {
+        let graph = path(4);
+        let ecc = eccentricities(&graph);
+        assert_eq!(ecc, vec![Some(4), Some(3), Some(2), Some(3), Some(4)]);
+        assert_eq!(diameter(&graph), Some(4));
+        assert_eq!(radius(&graph), Some(2));
+    }
+
+    #[test]
+    fn test_unreachable_pairs_excluded() {
+        // Path of length 4 plus a separate edge and an isolated node
+        let mut graph = path(4);
+        let x = graph.add_node("x");
+        let y = graph.add_node("y");
+        let z = graph.add_node("z");
+        graph.add_edge(x, y).unwrap();
+
+        let ecc = eccentricities(&graph);
+        assert_eq!(ecc[x], Some(1));
+        assert_eq!(ecc[z], Some(0));
+        assert_eq!(diameter(&graph), Some(4));
+        assert_eq!(radius(&graph), Some(2));
+    }
+
+    #[test]
+    fn test_empty_and_removed() {
+        let graph = DiGraph::new();
+        assert!(eccentricities(&graph).is_empty());
+        assert_eq!(diameter(&graph), None);
+        assert_eq!(radius(&graph), None);
+
+        let mut graph = path(2);
+        graph.remove_node(2);
+        assert_eq!(eccentricities(&graph), vec![Some(1), Some(1), None]);
+        assert_eq!(diameter(&graph), Some(1));
+    }
+}
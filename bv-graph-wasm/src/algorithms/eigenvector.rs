@@ -48,7 +48,7 @@ pub fn eigenvector(graph: &DiGraph, config: &EigenvectorConfig) -> Vec<f64> {
         // Multiply: work = A^T * vec (sum of predecessor scores)
         // A node's score = sum of scores of nodes that point to it
         for v in 0..n {
-            for &u in graph.predecessors_slice(v) {
+            for u in graph.in_neighbors(v) {
                 work[v] += vec[u];
             }
         }
@@ -117,8 +117,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let scores = eigenvector(&graph, &EigenvectorConfig::default());
         // In a DAG, scores converge to uniform distribution
@@ -138,9 +138,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let scores = eigenvector(&graph, &EigenvectorConfig::default());
         let diff = (scores[a] - scores[b]).abs() + (scores[b] - scores[c]).abs();
@@ -161,9 +161,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(a, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(a, d).unwrap();
 
         let scores = eigenvector(&graph, &EigenvectorConfig::default());
         // b, c, d should have equal scores
@@ -181,10 +181,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(b, a);
-        graph.add_edge(c, a);
-        graph.add_edge(d, a);
-        graph.add_edge(a, b); // Creates cycle a->b->a
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(d, a).unwrap();
+        graph.add_edge(a, b).unwrap(); // Creates cycle a->b->a
 
         let scores = eigenvector(&graph, &EigenvectorConfig::default());
         // a receives from b, c, d - should have higher score than isolated c, d
@@ -209,10 +209,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let scores = eigenvector(&graph, &EigenvectorConfig::default());
         // b and c should be equal (symmetric structure)
@@ -231,9 +231,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let scores = eigenvector(&graph, &EigenvectorConfig::default());
         let length: f64 = scores.iter().map(|x| x * x).sum::<f64>().sqrt();
@@ -252,8 +252,8 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let scores = eigenvector(&graph, &EigenvectorConfig::default());
         assert_eq!(scores.len(), 4);
@@ -272,11 +272,11 @@ mod tests {
         }
         // Create chain with some cross edges
         for i in 0..19 {
-            graph.add_edge(i, i + 1);
+            graph.add_edge(i, i + 1).unwrap();
         }
-        graph.add_edge(0, 10);
-        graph.add_edge(5, 15);
-        graph.add_edge(10, 0);
+        graph.add_edge(0, 10).unwrap();
+        graph.add_edge(5, 15).unwrap();
+        graph.add_edge(10, 0).unwrap();
 
         let scores = eigenvector(&graph, &EigenvectorConfig::default());
         assert_eq!(scores.len(), 20);
@@ -34,8 +34,10 @@ pub struct HITSResult {
     pub hubs: Vec<f64>,
     /// Authority scores (nodes pointed to by hubs)
     pub authorities: Vec<f64>,
-    /// Number of iterations until convergence
+    /// Number of iterations performed
     pub iterations: u32,
+    /// Whether the scores settled within the tolerance before `max_iterations`
+    pub converged: bool,
 }
 
 /// Compute HITS hub and authority scores.
@@ -46,6 +48,9 @@ pub struct HITSResult {
 /// 3. Normalize both vectors
 /// 4. Repeat until convergence
 ///
+/// Nodes without edges, and graphs without any, end up with 0.0 rather
+/// than NaN, since an all-zero vector is left unnormalized.
+///
 /// # Arguments
 /// * `graph` - The directed graph
 /// * `config` - HITS configuration parameters
@@ -59,6 +64,7 @@ pub fn hits(graph: &DiGraph, config: &HITSConfig) -> HITSResult {
             hubs: Vec::new(),
             authorities: Vec::new(),
             iterations: 0,
+            converged: true,
         };
     }
 
@@ -67,6 +73,7 @@ pub fn hits(graph: &DiGraph, config: &HITSConfig) -> HITSResult {
     let mut auth = vec![1.0 / (n as f64); n];
 
     let mut iterations = 0;
+    let mut converged = false;
 
     for iter in 0..config.max_iterations {
         iterations = iter + 1;
@@ -76,14 +83,14 @@ pub fn hits(graph: &DiGraph, config: &HITSConfig) -> HITSResult {
 
         // Authority update: auth(v) = sum of hub(u) for all u → v
         for v in 0..n {
-            for &u in graph.predecessors_slice(v) {
+            for u in graph.in_neighbors(v) {
                 new_auth[v] += hubs[u];
             }
         }
 
         // Hub update: hub(u) = sum of auth(v) for all u → v
         for u in 0..n {
-            for &v in graph.successors_slice(u) {
+            for v in graph.out_neighbors(u) {
                 new_hubs[u] += new_auth[v];
             }
         }
@@ -108,6 +115,7 @@ pub fn hits(graph: &DiGraph, config: &HITSConfig) -> HITSResult {
         hubs = new_hubs;
 
         if auth_diff + hub_diff < config.tolerance {
+            converged = true;
             break;
         }
     }
@@ -116,6 +124,7 @@ pub fn hits(graph: &DiGraph, config: &HITSConfig) -> HITSResult {
         hubs,
         authorities: auth,
         iterations,
+        converged,
     }
 }
 
@@ -164,8 +173,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let result = hits_default(&graph);
 
@@ -187,9 +196,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(hub, a);
-        graph.add_edge(hub, b);
-        graph.add_edge(hub, c);
+        graph.add_edge(hub, a).unwrap();
+        graph.add_edge(hub, b).unwrap();
+        graph.add_edge(hub, c).unwrap();
 
         let result = hits_default(&graph);
 
@@ -213,9 +222,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let auth = graph.add_node("auth");
-        graph.add_edge(a, auth);
-        graph.add_edge(b, auth);
-        graph.add_edge(c, auth);
+        graph.add_edge(a, auth).unwrap();
+        graph.add_edge(b, auth).unwrap();
+        graph.add_edge(c, auth).unwrap();
 
         let result = hits_default(&graph);
 
@@ -240,10 +249,10 @@ mod tests {
         let h2 = graph.add_node("h2");
         let a1 = graph.add_node("a1");
         let a2 = graph.add_node("a2");
-        graph.add_edge(h1, a1);
-        graph.add_edge(h1, a2);
-        graph.add_edge(h2, a1);
-        graph.add_edge(h2, a2);
+        graph.add_edge(h1, a1).unwrap();
+        graph.add_edge(h1, a2).unwrap();
+        graph.add_edge(h2, a1).unwrap();
+        graph.add_edge(h2, a2).unwrap();
 
         let result = hits_default(&graph);
 
@@ -255,6 +264,58 @@ mod tests {
         assert!(result.authorities[a2] > result.hubs[a2]);
     }
 
+    #[test]
+    fn test_hits_symmetric_graph() {
+        // Every edge has its reverse, so the graph equals its transpose and
+        // each node is exactly as good a hub as it is an authority. The triangle
+        // keeps it non-bipartite; on a bipartite graph the two can settle apart
+        let mut graph = DiGraph::new();
+        for i in 0..5 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (1, 3), (3, 4)] {
+            graph.add_edge(u, v).unwrap();
+            graph.add_edge(v, u).unwrap();
+        }
+
+        let result = hits_default(&graph);
+        assert!(result.converged);
+        for v in 0..5 {
+            assert!((result.hubs[v] - result.authorities[v]).abs() < 1e-6);
+        }
+        assert!(result.hubs[1] > result.hubs[0]);
+    }
+
+    #[test]
+    fn test_hits_edgeless_and_unconverged() {
+        let mut graph = DiGraph::new();
+        graph.add_node("a");
+        graph.add_node("b");
+        let result = hits_default(&graph);
+        assert_eq!(result.hubs, vec![0.0, 0.0]);
+        assert_eq!(result.authorities, vec![0.0, 0.0]);
+
+        // Two separate stars, one bigger, stopped after a single round
+        let mut graph = DiGraph::new();
+        for i in 0..8 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for v in 1..5 {
+            graph.add_edge(0, v).unwrap();
+        }
+        for v in 6..8 {
+            graph.add_edge(5, v).unwrap();
+        }
+        let config = HITSConfig {
+            max_iterations: 1,
+            ..HITSConfig::default()
+        };
+        let result = hits(&graph, &config);
+        assert_eq!(result.iterations, 1);
+        assert!(!result.converged);
+        assert!(result.hubs.iter().chain(&result.authorities).all(|s| s.is_finite()));
+    }
+
     #[test]
     fn test_hits_cycle() {
         // a -> b -> c -> a
@@ -262,9 +323,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let result = hits_default(&graph);
 
@@ -286,9 +347,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let result = hits_default(&graph);
 
@@ -321,7 +382,7 @@ mod tests {
         // Add various edges
         for i in 0..5 {
             for j in 5..10 {
-                graph.add_edge(i, j);
+                graph.add_edge(i, j).unwrap();
             }
         }
 
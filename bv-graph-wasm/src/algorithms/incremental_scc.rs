@@ -0,0 +1,308 @@
+//! Strongly connected components kept current while edges are added.
+//!
+//! Editing dependencies in the viewer adds one edge at a time, and rerunning
+//! Tarjan after every edit costs O(V + E). `IncrementalScc` keeps a
+//! topological order of the condensation instead, maintained as in Pearce
+//! and Kelly's dynamic topological sort ("A Dynamic Topological Sort
+//! Algorithm for Directed Acyclic Graphs", 2006). An edge that agrees with
+//! the order is O(1). An edge against it searches only the components
+//! ordered between its endpoints, and merges those on the new cycle if it
+//! closes one.
+
+use crate::algorithms::cycles::tarjan_scc;
+use crate::graph::{DiGraph, GraphError};
+use std::collections::HashSet;
+
+/// A `DiGraph` together with its strongly connected components, updated on
+/// every `add_node` and `add_edge`.
+///
+/// Component ids are stable between edits but not dense: when components
+/// merge, one id absorbs the others and those ids fall out of use.
+#[derive(Debug)]
+pub struct IncrementalScc {
+    graph: DiGraph,
+    /// Component id of each node, usize::MAX for removed nodes
+    comp: Vec<usize>,
+    /// Nodes of each component id, ascending; empty once merged away
+    members: Vec<Vec<usize>>,
+    /// Position of each component in a topological order of the condensation
+    ord: Vec<usize>,
+    /// Whether each component contains a cycle (two or more nodes, or a self-loop)
+    cyclic: Vec<bool>,
+    cycle_count: usize,
+    /// Order position for the next new component, past every existing one
+    next_ord: usize,
+}
+
+impl IncrementalScc {
+    /// Take ownership of `graph` and decompose it once with `tarjan_scc`.
+    pub fn new(graph: DiGraph) -> Self {
+        let scc = tarjan_scc(&graph);
+        let k = scc.components.len();
+        let comp: Vec<usize> = (0..graph.len()).map(|v| scc.component_of(v)).collect();
+        let cyclic: Vec<bool> = scc
+            .components
+            .iter()
+            .map(|c| c.len() > 1 || graph.has_edge(c[0], c[0]))
+            .collect();
+
+        let mut ord = vec![0; k];
+        for (position, &c) in scc.reverse_topological_order().iter().rev().enumerate() {
+            ord[c] = position;
+        }
+
+        IncrementalScc {
+            graph,
+            comp,
+            members: scc.components,
+            ord,
+            cycle_count: cyclic.iter().filter(|&&c| c).count(),
+            cyclic,
+            next_ord: k,
+        }
+    }
+
+    /// The wrapped graph.
+    pub fn graph(&self) -> &DiGraph {
+        &self.graph
+    }
+
+    /// Give the graph back.
+    pub fn into_graph(self) -> DiGraph {
+        self.graph
+    }
+
+    /// Component id of `node`, or `usize::MAX` if it is removed or out of range.
+    pub fn component_of(&self, node: usize) -> usize {
+        self.comp.get(node).copied().unwrap_or(usize::MAX)
+    }
+
+    /// Whether `a` and `b` are live nodes in the same component.
+    pub fn same_component(&self, a: usize, b: usize) -> bool {
+        let c = self.component_of(a);
+        c != usize::MAX && c == self.component_of(b)
+    }
+
+    /// Whether the graph currently has a cycle.
+    pub fn has_cycles(&self) -> bool {
+        self.cycle_count > 0
+    }
+
+    /// Number of cyclic components, as in `SCCResult::cycle_count`.
+    pub fn cycle_count(&self) -> usize {
+        self.cycle_count
+    }
+
+    /// Current components in `SCCResult` layout: each sorted ascending, and
+    /// ordered by smallest index.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut components: Vec<Vec<usize>> = self
+            .members
+            .iter()
+            .filter(|m| !m.is_empty())
+            .cloned()
+            .collect();
+        components.sort_unstable_by_key(|m| m[0]);
+        components
+    }
+
+    /// Add a node as `DiGraph::add_node` does; a new node is its own component.
+    pub fn add_node(&mut self, id: &str) -> usize {
+        let v = self.graph.add_node(id);
+        if v == self.comp.len() {
+            self.comp.push(self.members.len());
+            self.members.push(vec![v]);
+            self.ord.push(self.next_ord);
+            self.next_ord += 1;
+            self.cyclic.push(false);
+        }
+        v
+    }
+
+    /// Add an edge as `DiGraph::add_edge` does and update the components.
+    ///
+    /// # Errors
+    /// Fails like `DiGraph::add_edge`, leaving everything unchanged.
+    pub fn add_edge(&mut self, from: usize, to: usize) -> Result<(), GraphError> {
+        self.graph.add_edge(from, to)?;
+        let (a, b) = (self.comp[from], self.comp[to]);
+        if a == b {
+            // Only a self-loop can make a component cyclic from within
+            if !self.cyclic[a] {
+                self.cyclic[a] = true;
+                self.cycle_count += 1;
+            }
+            return Ok(());
+        }
+        if self.ord[a] < self.ord[b] {
+            return Ok(());
+        }
+
+        // The edge points backwards in the order. Only components ordered
+        // between b and a can lie on a new cycle or need to move.
+        let (lower, upper) = (self.ord[b], self.ord[a]);
+        let forward = self.search(b, true, |o| o <= upper);
+        let backward = self.search(a, false, |o| o >= lower);
+        let forward_set: HashSet<usize> = forward.iter().copied().collect();
+        let on_cycle: HashSet<usize> = backward
+            .iter()
+            .copied()
+            .filter(|c| forward_set.contains(c))
+            .collect();
+
+        let mut slots: Vec<usize> = forward
+            .iter()
+            .chain(&backward)
+            .map(|&c| self.ord[c])
+            .collect();
+        slots.sort_unstable();
+        slots.dedup();
+        let by_ord = |list: &[usize]| {
+            let mut list: Vec<usize> = list
+                .iter()
+                .copied()
+                .filter(|c| !on_cycle.contains(c))
+                .collect();
+            list.sort_unstable_by_key(|&c| self.ord[c]);
+            list
+        };
+        let (before, after) = (by_ord(&backward), by_ord(&forward));
+
+        // What reaches `a` goes first, then the merged cycle, then what `b`
+        // reaches. Each group keeps its relative order, and `after` takes
+        // the highest slots so nothing moves below an outside predecessor.
+        let merged = self.merge(&on_cycle);
+        for (&c, &slot) in before.iter().chain(&merged).zip(&slots) {
+            self.ord[c] = slot;
+        }
+        for (&c, &slot) in after.iter().zip(&slots[slots.len() - after.len()..]) {
+            self.ord[c] = slot;
+        }
+        Ok(())
+    }
+
+    /// Components reachable from `start` (following edges forwards or
+    /// backwards) through components whose order passes `in_range`.
+    fn search(&self, start: usize, forward: bool, in_range: impl Fn(usize) -> bool) -> Vec<usize> {
+        let mut seen = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(c) = stack.pop() {
+            for &u in &self.members[c] {
+                let next = if forward {
+                    self.graph.successors_slice(u)
+                } else {
+                    self.graph.predecessors_slice(u)
+                };
+                for &w in next {
+                    let d = self.comp[w as usize];
+                    if in_range(self.ord[d]) && seen.insert(d) {
+                        stack.push(d);
+                    }
+                }
+            }
+        }
+        seen.into_iter().collect()
+    }
+
+    /// Fold `group` into its largest component, returning that id (None if
+    /// `group` is empty).
+    fn merge(&mut self, group: &HashSet<usize>) -> Option<usize> {
+        let &target = group.iter().max_by_key(|&&c| (self.members[c].len(), c))?;
+        for &c in group {
+            if self.cyclic[c] {
+                self.cycle_count -= 1;
+            }
+            if c == target {
+                continue;
+            }
+            let moved = std::mem::take(&mut self.members[c]);
+            for &v in &moved {
+                self.comp[v] = target;
+            }
+            self.members[target].extend(moved);
+        }
+        self.members[target].sort_unstable();
+        self.cyclic[target] = true;
+        self.cycle_count += 1;
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check the components against Tarjan and the order against every edge.
+    fn assert_consistent(inc: &IncrementalScc) {
+        let scc = tarjan_scc(inc.graph());
+        assert_eq!(inc.components(), scc.components);
+        assert_eq!(inc.has_cycles(), scc.has_cycles);
+        assert_eq!(inc.cycle_count(), scc.cycle_count);
+        for (u, v) in inc.graph().edges() {
+            let (a, b) = (inc.component_of(u), inc.component_of(v));
+            assert!(a == b || inc.ord[a] < inc.ord[b], "edge {} -> {}", u, v);
+        }
+    }
+
+    #[test]
+    fn test_incremental_matches_tarjan_on_random_insertions() {
+        for seed in 0..5u64 {
+            let mut state = seed;
+            let mut next = |bound: usize| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                (state >> 33) as usize % bound
+            };
+
+            let mut graph = DiGraph::new();
+            for i in 0..30 {
+                graph.add_node(&format!("n{}", i));
+            }
+            for _ in 0..10 {
+                graph.add_edge(next(30), next(30)).unwrap();
+            }
+            graph.remove_node(7);
+
+            let mut inc = IncrementalScc::new(graph);
+            assert_consistent(&inc);
+            for _ in 0..60 {
+                let (u, v) = (next(30), next(30));
+                if u == 7 || v == 7 {
+                    assert!(inc.add_edge(u, v).is_err());
+                    continue;
+                }
+                inc.add_edge(u, v).unwrap();
+                assert_consistent(&inc);
+            }
+            assert_eq!(inc.component_of(7), usize::MAX);
+        }
+    }
+
+    #[test]
+    fn test_incremental_merges_and_new_nodes() {
+        let mut inc = IncrementalScc::new(DiGraph::new());
+        let a = inc.add_node("a");
+        let b = inc.add_node("b");
+        let c = inc.add_node("c");
+        assert_eq!(inc.add_node("a"), a);
+        inc.add_edge(a, b).unwrap();
+        inc.add_edge(b, c).unwrap();
+        assert!(!inc.has_cycles());
+
+        // c -> a closes a -> b -> c -> a
+        inc.add_edge(c, a).unwrap();
+        assert!(inc.same_component(a, c) && inc.same_component(b, c));
+        assert_eq!(inc.cycle_count(), 1);
+
+        let d = inc.add_node("d");
+        inc.add_edge(d, d).unwrap();
+        assert_eq!(inc.cycle_count(), 2);
+        inc.add_edge(d, a).unwrap();
+        inc.add_edge(c, d).unwrap();
+        assert_eq!(inc.components(), vec![vec![a, b, c, d]]);
+        assert_eq!(inc.cycle_count(), 1);
+        assert_consistent(&inc);
+
+        assert!(inc.add_edge(a, 99).is_err());
+        assert_eq!(inc.into_graph().edge_count(), 6);
+    }
+}
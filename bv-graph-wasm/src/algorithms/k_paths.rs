@@ -53,10 +53,10 @@ pub fn k_critical_paths(graph: &DiGraph, k: usize) -> KPathsResult {
         };
     }
 
-    // Get topological order - returns None if graph has cycles
+    // Get topological order - errors if graph has cycles
     let order = match topological_sort(graph) {
-        Some(o) => o,
-        None => {
+        Ok(o) => o,
+        Err(_) => {
             return KPathsResult {
                 paths: Vec::new(),
                 total_nodes: n,
@@ -72,7 +72,7 @@ pub fn k_critical_paths(graph: &DiGraph, k: usize) -> KPathsResult {
 
     // Process in topological order
     for &v in &order {
-        for &u in graph.predecessors_slice(v) {
+        for u in graph.in_neighbors(v) {
             if dist[u] + 1 > dist[v] {
                 dist[v] = dist[u] + 1;
                 pred[v] = Some(u);
@@ -146,7 +146,7 @@ mod tests {
             g.add_node(&format!("n{}", i));
         }
         for (from, to) in edges {
-            g.add_edge(*from, *to);
+            g.add_edge(*from, *to).unwrap();
         }
         g
     }
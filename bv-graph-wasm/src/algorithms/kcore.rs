@@ -5,85 +5,83 @@
 //! High core numbers indicate densely connected regions.
 
 use crate::graph::DiGraph;
-use std::collections::HashSet;
 
 /// Compute k-core numbers for all nodes.
 ///
 /// Uses undirected view: edge u→v is treated as u--v.
-/// Employs the standard "k-peeling" algorithm.
 ///
 /// Returns vector of core numbers in node index order.
 pub fn kcore(graph: &DiGraph) -> Vec<u32> {
+    k_core(graph).into_iter().map(|c| c as u32).collect()
+}
+
+/// Compute the coreness (maximum core number) of every node.
+///
+/// The graph is treated as undirected by combining in- and out-neighbors
+/// (duplicate u↔v pairs and self-loops count once / not at all).
+/// Uses the Batagelj–Zaversnik peeling algorithm with a bucket queue,
+/// so the peeling itself is O(V + E).
+///
+/// Returns vector of core numbers in node index order.
+pub fn k_core(graph: &DiGraph) -> Vec<usize> {
     let n = graph.len();
     if n == 0 {
         return Vec::new();
     }
 
-    // Build undirected degree and neighbor lists
-    // For directed graph, treat as undirected (symmetric edges)
-    let mut neighbors: Vec<HashSet<usize>> = vec![HashSet::new(); n];
-
-    for u in 0..n {
-        for &v in graph.successors_slice(u) {
-            neighbors[u].insert(v);
-            neighbors[v].insert(u);
-        }
-    }
-
-    // Compute undirected degree
-    let mut degree: Vec<usize> = neighbors.iter().map(|s| s.len()).collect();
+    // Build undirected neighbor lists (deduplicated, no self-loops)
+    let neighbors: Vec<Vec<usize>> = (0..n).map(|v| graph.undirected_neighbors(v)).collect();
 
-    // Use bucket-based k-peeling (more efficient for sparse graphs)
-    let max_deg = degree.iter().cloned().max().unwrap_or(0);
+    let mut degree: Vec<usize> = neighbors.iter().map(|nbrs| nbrs.len()).collect();
+    let max_deg = degree.iter().copied().max().unwrap_or(0);
 
-    // Build degree buckets
-    let mut bucket: Vec<Vec<usize>> = vec![Vec::new(); max_deg + 1];
-    for i in 0..n {
-        bucket[degree[i]].push(i);
+    // bin[d] = start offset of degree-d nodes in `vert`
+    let mut bin = vec![0usize; max_deg + 1];
+    for &d in &degree {
+        bin[d] += 1;
+    }
+    let mut start = 0;
+    for count in bin.iter_mut() {
+        let c = *count;
+        *count = start;
+        start += c;
     }
 
-    let mut core = vec![0u32; n];
-    let mut processed = vec![false; n];
-    let mut current_core = 0usize;
-
-    for _ in 0..n {
-        // Find node with minimum degree (from lowest non-empty bucket)
-        while current_core <= max_deg && bucket[current_core].is_empty() {
-            current_core += 1;
-        }
-
-        if current_core > max_deg {
-            break;
-        }
-
-        // Get a node from this bucket
-        let v = bucket[current_core].pop().unwrap();
-        if processed[v] {
-            continue;
-        }
-
-        processed[v] = true;
-        core[v] = current_core as u32;
-
-        // Update neighbors' degrees
-        for &nbr in &neighbors[v] {
-            if processed[nbr] {
-                continue;
-            }
+    // vert: nodes sorted by degree; pos: position of each node in vert
+    let mut vert = vec![0usize; n];
+    let mut pos = vec![0usize; n];
+    for v in 0..n {
+        pos[v] = bin[degree[v]];
+        vert[pos[v]] = v;
+        bin[degree[v]] += 1;
+    }
+    for d in (1..=max_deg).rev() {
+        bin[d] = bin[d - 1];
+    }
+    bin[0] = 0;
 
-            let old_deg = degree[nbr];
-            if old_deg > current_core {
-                // Remove from old bucket (if still there)
-                bucket[old_deg].retain(|&x| x != nbr);
-                degree[nbr] -= 1;
-                let new_deg = degree[nbr];
-                // Add to new bucket (may go below current_core)
-                bucket[new_deg].push(nbr);
+    // Peel nodes in order of current degree
+    for i in 0..n {
+        let v = vert[i];
+        for &u in &neighbors[v] {
+            if degree[u] > degree[v] {
+                // Move u to the front of its bucket, then shrink the bucket
+                let du = degree[u];
+                let pu = pos[u];
+                let pw = bin[du];
+                let w = vert[pw];
+                if u != w {
+                    vert.swap(pu, pw);
+                    pos[u] = pw;
+                    pos[w] = pu;
+                }
+                bin[du] += 1;
+                degree[u] -= 1;
             }
         }
     }
 
-    core
+    degree
 }
 
 /// Get the maximum core number (degeneracy of the graph).
@@ -128,8 +126,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let cores = kcore(&graph);
         // a has degree 1, b has degree 2, c has degree 1
@@ -151,9 +149,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let cores = kcore(&graph);
         // All nodes have degree 2 in undirected view
@@ -170,9 +168,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(a, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(a, d).unwrap();
 
         let cores = kcore(&graph);
         // Hub a has degree 3, spokes have degree 1
@@ -195,21 +193,21 @@ mod tests {
         let e = graph.add_node("e");
 
         // Make clique (all pairs connected)
-        graph.add_edge(a, b);
-        graph.add_edge(b, a);
-        graph.add_edge(a, c);
-        graph.add_edge(c, a);
-        graph.add_edge(a, d);
-        graph.add_edge(d, a);
-        graph.add_edge(b, c);
-        graph.add_edge(c, b);
-        graph.add_edge(b, d);
-        graph.add_edge(d, b);
-        graph.add_edge(c, d);
-        graph.add_edge(d, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(a, d).unwrap();
+        graph.add_edge(d, a).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, b).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(d, b).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, c).unwrap();
 
         // Add pendant
-        graph.add_edge(a, e);
+        graph.add_edge(a, e).unwrap();
 
         let cores = kcore(&graph);
         // a, b, c, d form a 3-core (each has degree >= 3 in the clique)
@@ -240,9 +238,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         assert_eq!(degeneracy(&graph), 2);
     }
@@ -255,9 +253,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d"); // Isolated
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let in_2core = nodes_in_kcore(&graph, 2);
         assert_eq!(in_2core.len(), 3);
@@ -283,11 +281,11 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let cores = kcore(&graph);
         // All nodes connected in diamond shape
@@ -298,4 +296,28 @@ mod tests {
         // 2-core includes all nodes
         assert!(cores.iter().all(|&c| c >= 2), "All should be in 2-core");
     }
+
+    #[test]
+    fn test_k_core_clique_with_pendants() {
+        // Clique of 4 (one edge per pair) plus two pendants hanging off it
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        let f = graph.add_node("f");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(a, d).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(e, a).unwrap();
+        graph.add_edge(c, f).unwrap();
+
+        let cores = k_core(&graph);
+        assert_eq!(cores, vec![3, 3, 3, 3, 1, 1]);
+        assert_eq!(kcore(&graph), vec![3, 3, 3, 3, 1, 1]);
+    }
 }
@@ -0,0 +1,158 @@
+//! Maximum matching between two caller-chosen groups of nodes.
+//!
+//! Used for assignment views: with beads on one side, owners on the other
+//! and an edge for each "could take this" relation, a maximum matching
+//! assigns as many beads as possible, each to a different owner.
+
+use crate::graph::DiGraph;
+use std::collections::VecDeque;
+
+/// Maximum matching between `left` and `right`, as (left, right) pairs.
+///
+/// Only edges between the two sides count, in either direction; edges
+/// within a side are ignored. Each left node is matched in turn through a
+/// BFS for an augmenting path (Kuhn's algorithm), O(V·E) overall. Pairs
+/// come back in the order of `left`. Duplicate, out-of-range and removed
+/// nodes are skipped, and a node listed on both sides only counts as left.
+///
+/// Left nodes missing from the result had no partner left over; see
+/// `unmatched` to list them.
+pub fn max_bipartite_matching(
+    graph: &DiGraph,
+    left: &[usize],
+    right: &[usize],
+) -> Vec<(usize, usize)> {
+    let n = graph.len();
+    // 1 = left, 2 = right
+    let mut side = vec![0u8; n];
+    let mut lefts = Vec::new();
+    for &v in left {
+        if v < n && !graph.is_removed(v) && side[v] == 0 {
+            side[v] = 1;
+            lefts.push(v);
+        }
+    }
+    for &v in right {
+        if v < n && !graph.is_removed(v) && side[v] == 0 {
+            side[v] = 2;
+        }
+    }
+
+    let partners: Vec<Vec<usize>> = (0..n)
+        .map(|u| {
+            if side[u] != 1 {
+                return Vec::new();
+            }
+            let mut ns: Vec<usize> = graph
+                .out_neighbors(u)
+                .chain(graph.in_neighbors(u))
+                .filter(|&v| side[v] == 2)
+                .collect();
+            ns.sort_unstable();
+            ns.dedup();
+            ns
+        })
+        .collect();
+
+    let mut match_of = vec![usize::MAX; n];
+    // Right node -> left node that reached it in the current search
+    let mut via = vec![usize::MAX; n];
+    let mut visited = vec![false; n];
+    let mut touched = Vec::new();
+    let mut queue = VecDeque::new();
+
+    for &start in &lefts {
+        queue.clear();
+        queue.push_back(start);
+        let mut free = None;
+        'search: while let Some(u) = queue.pop_front() {
+            for &v in &partners[u] {
+                if visited[v] {
+                    continue;
+                }
+                visited[v] = true;
+                touched.push(v);
+                via[v] = u;
+                if match_of[v] == usize::MAX {
+                    free = Some(v);
+                    break 'search;
+                }
+                queue.push_back(match_of[v]);
+            }
+        }
+
+        // Flip the alternating path back to `start`
+        let mut next = free;
+        while let Some(v) = next {
+            let u = via[v];
+            let previous = match_of[u];
+            match_of[u] = v;
+            match_of[v] = u;
+            next = (u != start).then_some(previous);
+        }
+        for v in touched.drain(..) {
+            visited[v] = false;
+        }
+    }
+
+    lefts
+        .into_iter()
+        .filter(|&u| match_of[u] != usize::MAX)
+        .map(|u| (u, match_of[u]))
+        .collect()
+}
+
+/// Nodes of `side` that appear in no pair of `matching`, in `side` order.
+pub fn unmatched(matching: &[(usize, usize)], side: &[usize]) -> Vec<usize> {
+    side.iter()
+        .copied()
+        .filter(|&v| !matching.iter().any(|&(a, b)| a == v || b == v))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph_with(nodes: &[&str], edges: &[(usize, usize)]) -> DiGraph {
+        let mut graph = DiGraph::new();
+        for id in nodes {
+            graph.add_node(id);
+        }
+        for &(u, v) in edges {
+            graph.add_edge(u, v).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_perfect_matching_needs_augmenting() {
+        // Tasks 0..3, people 3..6. Greedy would give t0 -> p3 and strand t1
+        let graph = graph_with(
+            &["t0", "t1", "t2", "p3", "p4", "p5"],
+            &[(0, 3), (0, 4), (1, 3), (2, 4), (5, 2)],
+        );
+        let matching = max_bipartite_matching(&graph, &[0, 1, 2], &[3, 4, 5]);
+        assert_eq!(matching, vec![(0, 4), (1, 3), (2, 5)]);
+        assert!(unmatched(&matching, &[3, 4, 5]).is_empty());
+    }
+
+    #[test]
+    fn test_imperfect_matching() {
+        // Three tasks only p3 can take, one task for p4; p5 is idle.
+        // The edge p3 -> p4 lies within a side and is ignored
+        let graph = graph_with(
+            &["t0", "t1", "t2", "p3", "p4", "p5"],
+            &[(0, 3), (1, 3), (2, 3), (2, 4), (3, 4)],
+        );
+        let left = [0, 1, 2, 0, 99];
+        let right = [3, 4, 5];
+        let matching = max_bipartite_matching(&graph, &left, &right);
+        assert_eq!(matching.len(), 2);
+        assert!(matching.contains(&(2, 4)));
+        assert_eq!(unmatched(&matching, &[0, 1, 2]).len(), 1);
+        assert_eq!(unmatched(&matching, &right), vec![5]);
+
+        assert!(max_bipartite_matching(&graph, &[], &right).is_empty());
+    }
+}
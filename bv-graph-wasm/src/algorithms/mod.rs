@@ -4,16 +4,30 @@
 
 pub mod articulation;
 pub mod betweenness;
+pub mod closeness;
+pub mod coloring;
+pub mod community;
+pub mod components;
 pub mod coverage;
 pub mod critical_path;
 pub mod cycles;
+pub mod diamonds;
+pub mod distance;
+pub mod dominators;
 pub mod eigenvector;
 pub mod hits;
+pub mod incremental_scc;
 pub mod k_paths;
 pub mod kcore;
+pub mod matching;
+pub mod negative_cycle;
 pub mod pagerank;
 pub mod parallel_cut;
+pub mod paths;
+pub mod reachability;
+pub mod similarity;
 pub mod slack;
+pub mod stats;
 pub mod subgraph;
 pub mod topo;
 pub mod topk_set;
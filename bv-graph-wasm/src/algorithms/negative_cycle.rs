@@ -0,0 +1,231 @@
+//! Negative cycle detection via Bellman-Ford.
+//!
+//! When edge weights encode estimated durations with credits/offsets,
+//! a negative-weight loop signals inconsistent scheduling constraints.
+
+use crate::graph::DiGraph;
+
+/// Find one negative-weight cycle, if any exists.
+///
+/// Runs Bellman-Ford from a virtual source connected to every node with a
+/// zero-weight edge, so disconnected parts of the graph are all covered.
+///
+/// # Arguments
+/// * `graph` - The directed graph
+/// * `weights` - One weight per edge, in edge iteration order
+///   (by source index, then successor order — the order used by `toJson`)
+///
+/// # Returns
+/// * `Ok(Some(cycle))` - Nodes of a negative cycle in traversal order
+/// * `Ok(None)` - No negative cycle
+/// * `Err(msg)` - Weight slice length doesn't match the edge count, or a weight is NaN
+pub fn negative_cycle(graph: &DiGraph, weights: &[f64]) -> Result<Option<Vec<usize>>, String> {
+    let n = graph.len();
+    let edges: Vec<(usize, usize)> = graph.edges().collect();
+
+    if weights.len() != edges.len() {
+        return Err(format!(
+            "expected {} edge weights, got {}",
+            edges.len(),
+            weights.len()
+        ));
+    }
+    if let Some(i) = weights.iter().position(|w| w.is_nan()) {
+        let (from, to) = edges[i];
+        return Err(format!("weight of edge {} -> {} is NaN", from, to));
+    }
+
+    // Virtual source: every node starts at distance 0
+    let mut dist = vec![0.0f64; n];
+    let mut pred = vec![usize::MAX; n];
+    let mut last_relaxed = None;
+
+    for _ in 0..n {
+        last_relaxed = None;
+        for (&(u, v), &w) in edges.iter().zip(weights) {
+            if dist[u] + w < dist[v] {
+                dist[v] = dist[u] + w;
+                pred[v] = u;
+                last_relaxed = Some(v);
+            }
+        }
+        if last_relaxed.is_none() {
+            return Ok(None);
+        }
+    }
+
+    // A relaxation in the n-th pass means a negative cycle exists
+    Ok(last_relaxed.map(|v| cycle_through_pred(&pred, v)))
+}
+
+/// Shortest paths from `source` using the graph's stored edge weights
+/// (1.0 where none were set), allowing negative weights.
+///
+/// Returns the distance to every node, None where unreachable; an
+/// out-of-range or removed source reaches nothing. Complexity O(V·E), with
+/// an early exit once a pass changes nothing.
+///
+/// # Errors
+/// If a negative cycle is reachable from `source`, returns its nodes in
+/// traversal order, since distances through it are unbounded. Cycles the
+/// source cannot reach don't matter; `negative_cycle` checks the whole graph.
+pub fn bellman_ford(graph: &DiGraph, source: usize) -> Result<Vec<Option<f64>>, Vec<usize>> {
+    let n = graph.len();
+    let mut dist: Vec<Option<f64>> = vec![None; n];
+    if source >= n || graph.is_removed(source) {
+        return Ok(dist);
+    }
+    let edges: Vec<(usize, usize, f64)> = (0..n)
+        .flat_map(|u| graph.weighted_successors(u).map(move |(v, w)| (u, v, w)))
+        .collect();
+
+    dist[source] = Some(0.0);
+    let mut pred = vec![usize::MAX; n];
+    for pass in 0..n {
+        let mut last_relaxed = None;
+        for &(u, v, w) in &edges {
+            let Some(du) = dist[u] else { continue };
+            if dist[v].is_none_or(|dv| du + w < dv) {
+                dist[v] = Some(du + w);
+                pred[v] = u;
+                last_relaxed = Some(v);
+            }
+        }
+        match last_relaxed {
+            None => break,
+            // Still relaxing in the n-th pass: a reachable negative cycle
+            Some(v) if pass + 1 == n => return Err(cycle_through_pred(&pred, v)),
+            Some(_) => {}
+        }
+    }
+    Ok(dist)
+}
+
+/// Extract the cycle that `v`'s predecessor chain runs into.
+///
+/// `v` must have been relaxed in the n-th Bellman-Ford pass. Walking back
+/// n steps is then sure to land on the cycle, which is returned in
+/// traversal order.
+fn cycle_through_pred(pred: &[usize], mut v: usize) -> Vec<usize> {
+    for _ in 0..pred.len() {
+        v = pred[v];
+    }
+
+    let mut cycle = vec![v];
+    let mut u = pred[v];
+    while u != v {
+        cycle.push(u);
+        u = pred[u];
+    }
+    cycle.reverse();
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weight lookup in edge iteration order.
+    fn weights_for(graph: &DiGraph, weight: impl Fn(usize, usize) -> f64) -> Vec<f64> {
+        graph.edges().map(|(u, v)| weight(u, v)).collect()
+    }
+
+    #[test]
+    fn test_negative_triangle() {
+        // a -> b -> c -> a with total weight -1, plus a disconnected pair
+        let mut graph = DiGraph::new();
+        let x = graph.add_node("x");
+        let y = graph.add_node("y");
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(x, y).unwrap();
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let weights = weights_for(&graph, |u, _| if u == c { -4.0 } else { 1.0 });
+        let cycle = negative_cycle(&graph, &weights).unwrap().unwrap();
+        assert_eq!(cycle.len(), 3);
+
+        // Traversal order: every consecutive pair (with wrap-around) is an edge
+        for i in 0..cycle.len() {
+            let from = cycle[i];
+            let to = cycle[(i + 1) % cycle.len()];
+            assert!(graph.has_edge(from, to));
+        }
+    }
+
+    #[test]
+    fn test_positive_cycle_has_no_negative_cycle() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let weights = vec![1.0; graph.edge_count()];
+        assert_eq!(negative_cycle(&graph, &weights), Ok(None));
+    }
+
+    #[test]
+    fn test_negative_cycle_errors() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+
+        assert!(negative_cycle(&graph, &[1.0]).is_err());
+        assert!(negative_cycle(&graph, &[1.0, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_edge() {
+        // s -> a (4), s -> b (1), a -> c (1), b -> a (-2): s -> b -> a is cheaper
+        let mut graph = DiGraph::new();
+        let s = graph.add_node("s");
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let far = graph.add_node("far");
+        graph.add_weighted_edge(s, a, 4.0).unwrap();
+        graph.add_weighted_edge(s, b, 1.0).unwrap();
+        graph.add_weighted_edge(a, c, 1.0).unwrap();
+        graph.add_weighted_edge(b, a, -2.0).unwrap();
+
+        let dist = bellman_ford(&graph, s).unwrap();
+        assert_eq!(dist, vec![Some(0.0), Some(-1.0), Some(1.0), Some(0.0), None]);
+        assert_eq!(bellman_ford(&graph, 42), Ok(vec![None; 5]));
+
+        // A negative cycle the source can't reach doesn't matter
+        let back = graph.add_node("back");
+        graph.add_weighted_edge(far, back, 1.0).unwrap();
+        graph.add_weighted_edge(back, far, -5.0).unwrap();
+        assert_eq!(bellman_ford(&graph, s).unwrap()[..4], dist[..4]);
+        assert_eq!(bellman_ford(&graph, far).unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn test_bellman_ford_reports_negative_cycle() {
+        // s -> a -> b -> c -> a with the loop summing to -1
+        let mut graph = DiGraph::new();
+        let s = graph.add_node("s");
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(s, a).unwrap();
+        graph.add_edge(a, b).unwrap();
+        graph.add_weighted_edge(b, c, -3.0).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let mut cycle = bellman_ford(&graph, s).unwrap_err();
+        for i in 0..cycle.len() {
+            assert!(graph.has_edge(cycle[i], cycle[(i + 1) % cycle.len()]));
+        }
+        cycle.sort_unstable();
+        assert_eq!(cycle, vec![a, b, c]);
+    }
+}
@@ -4,6 +4,7 @@
 //! High PageRank issues are central bottlenecks that many other issues depend on.
 
 use crate::graph::DiGraph;
+use serde::Serialize;
 
 /// PageRank configuration parameters.
 pub struct PageRankConfig {
@@ -32,25 +33,55 @@ impl Default for PageRankConfig {
 ///
 /// Returns vector of scores in node index order.
 pub fn pagerank(graph: &DiGraph, config: &PageRankConfig) -> Vec<f64> {
+    pagerank_with_info(graph, config).scores
+}
+
+/// PageRank scores with convergence details.
+#[derive(Debug, Clone, Serialize)]
+pub struct PageRankResult {
+    /// Score per node, summing to 1.0 over live nodes (removed nodes get 0.0)
+    pub scores: Vec<f64>,
+    /// Power iterations performed
+    pub iterations: u32,
+    /// Whether the L1 change fell below the tolerance before `max_iterations`
+    pub converged: bool,
+}
+
+/// Run `pagerank` and report how many iterations it took and whether it
+/// converged, so callers can tell a settled ranking from a truncated one.
+/// Graphs without live nodes count as converged after zero iterations.
+pub fn pagerank_with_info(graph: &DiGraph, config: &PageRankConfig) -> PageRankResult {
     let n = graph.len();
-    if n == 0 {
-        return Vec::new();
+    // Removed nodes keep their index but hold no rank
+    let live = graph.active_len();
+    if live == 0 {
+        return PageRankResult {
+            scores: vec![0.0; n],
+            iterations: 0,
+            converged: true,
+        };
     }
+    let live_mask: Vec<f64> = (0..n)
+        .map(|i| if graph.is_removed(i) { 0.0 } else { 1.0 })
+        .collect();
 
     let d = config.damping;
-    let base = (1.0 - d) / n as f64;
+    let base = (1.0 - d) / live as f64;
 
     // Initialize with uniform distribution
-    let mut scores = vec![1.0 / n as f64; n];
+    let mut scores: Vec<f64> = live_mask.iter().map(|m| m / live as f64).collect();
     let mut new_scores = vec![0.0; n];
 
     // Pre-compute out-degrees
     let out_degrees: Vec<usize> = (0..n).map(|i| graph.out_degree(i)).collect();
 
+    let mut iterations = 0;
+    let mut converged = false;
     for _ in 0..config.max_iterations {
+        iterations += 1;
         // Reset new scores to base value
-        for s in &mut new_scores {
-            *s = base;
+        for (s, m) in new_scores.iter_mut().zip(&live_mask) {
+            *s = base * m;
         }
 
         // Handle dangling nodes (no outgoing edges)
@@ -59,16 +90,16 @@ pub fn pagerank(graph: &DiGraph, config: &PageRankConfig) -> Vec<f64> {
             .filter(|&i| out_degrees[i] == 0)
             .map(|i| scores[i])
             .sum();
-        let dangling_contrib = d * dangling_sum / n as f64;
+        let dangling_contrib = d * dangling_sum / live as f64;
 
         // Add dangling contribution to all nodes
-        for s in &mut new_scores {
-            *s += dangling_contrib;
+        for (s, m) in new_scores.iter_mut().zip(&live_mask) {
+            *s += dangling_contrib * m;
         }
 
         // Accumulate contributions from predecessors
         for v in 0..n {
-            for &u in graph.predecessors_slice(v) {
+            for u in graph.in_neighbors(v) {
                 if out_degrees[u] > 0 {
                     new_scores[v] += d * scores[u] / out_degrees[u] as f64;
                 }
@@ -85,11 +116,16 @@ pub fn pagerank(graph: &DiGraph, config: &PageRankConfig) -> Vec<f64> {
         std::mem::swap(&mut scores, &mut new_scores);
 
         if diff < config.tolerance {
+            converged = true;
             break;
         }
     }
 
-    scores
+    PageRankResult {
+        scores,
+        iterations,
+        converged,
+    }
 }
 
 /// Compute PageRank with default parameters (damping=0.85, tolerance=1e-6).
@@ -126,8 +162,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let scores = pagerank(&graph, &PageRankConfig::default());
         assert!(scores[c] > scores[b], "c should have higher rank than b");
@@ -141,9 +177,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let scores = pagerank(&graph, &PageRankConfig::default());
         // Scores should be roughly equal in a cycle
@@ -159,9 +195,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(a, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(a, d).unwrap();
 
         let scores = pagerank(&graph, &PageRankConfig::default());
         // b, c, d are sinks with equal incoming from a
@@ -187,9 +223,9 @@ mod tests {
         let c = graph.add_node("c");
         let d = graph.add_node("d");
         let e = graph.add_node("e");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(d, e);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(d, e).unwrap();
 
         let scores = pagerank(&graph, &PageRankConfig::default());
         // c is the deepest sink, should have highest score
@@ -203,9 +239,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let scores = pagerank(&graph, &PageRankConfig::default());
         let sum: f64 = scores.iter().sum();
@@ -223,7 +259,7 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
+        graph.add_edge(a, b).unwrap();
         // c has no edges - it's a dangling node
 
         let scores = pagerank(&graph, &PageRankConfig::default());
@@ -237,6 +273,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pagerank_skips_removed_nodes() {
+        // a -> b, c removed: same scores as the two-node graph
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(c, b).unwrap();
+        graph.remove_node(c);
+
+        let mut pair = DiGraph::new();
+        pair.add_node("a");
+        pair.add_node("b");
+        pair.add_edge(0, 1).unwrap();
+
+        let scores = pagerank(&graph, &PageRankConfig::default());
+        let expected = pagerank(&pair, &PageRankConfig::default());
+        assert_eq!(scores.len(), 3);
+        assert_eq!(scores[c], 0.0);
+        assert!((scores[a] - expected[0]).abs() < 1e-9);
+        assert!((scores[b] - expected[1]).abs() < 1e-9);
+    }
+
     #[test]
     fn test_pagerank_convergence() {
         // Large enough graph to test convergence
@@ -246,12 +306,12 @@ mod tests {
         }
         // Create some edges
         for i in 0..19 {
-            graph.add_edge(i, i + 1);
+            graph.add_edge(i, i + 1).unwrap();
         }
         // Add some cross edges
-        graph.add_edge(0, 10);
-        graph.add_edge(5, 15);
-        graph.add_edge(10, 0);
+        graph.add_edge(0, 10).unwrap();
+        graph.add_edge(5, 15).unwrap();
+        graph.add_edge(10, 0).unwrap();
 
         let scores = pagerank(&graph, &PageRankConfig::default());
         assert_eq!(scores.len(), 20);
@@ -263,6 +323,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pagerank_with_info_convergence_flag() {
+        // 200 nodes all pointing at a hub: far from the uniform start
+        let mut graph = DiGraph::new();
+        for i in 0..200 {
+            graph.add_node(&format!("n{}", i));
+        }
+        for i in 1..200 {
+            graph.add_edge(i, 0).unwrap();
+            graph.add_edge(i, (i + 1) % 200).unwrap();
+        }
+        let config = PageRankConfig {
+            max_iterations: 1,
+            ..PageRankConfig::default()
+        };
+        let result = pagerank_with_info(&graph, &config);
+        assert_eq!(result.iterations, 1);
+        assert!(!result.converged);
+        assert!((result.scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let result = pagerank_with_info(&graph, &PageRankConfig::default());
+        assert!(result.converged);
+        assert!(result.iterations > 1 && result.iterations < 100);
+
+        // A symmetric cycle is already at its fixed point
+        let mut cycle = DiGraph::new();
+        for i in 0..4 {
+            cycle.add_node(&format!("c{}", i));
+        }
+        for i in 0..4 {
+            cycle.add_edge(i, (i + 1) % 4).unwrap();
+        }
+        let result = pagerank_with_info(&cycle, &PageRankConfig::default());
+        assert!(result.converged);
+        assert_eq!(result.iterations, 1);
+        assert!(result.scores.iter().all(|s| (s - 0.25).abs() < 1e-12));
+
+        let empty = pagerank_with_info(&DiGraph::new(), &PageRankConfig::default());
+        assert!(empty.scores.is_empty() && empty.converged);
+    }
+
     #[test]
     fn test_pagerank_diamond() {
         //     a
@@ -275,10 +376,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let scores = pagerank(&graph, &PageRankConfig::default());
         // d should have highest score (most incoming paths)
@@ -52,9 +52,8 @@ pub fn parallel_cut_suggestions(
         .filter(|&v| {
             !closed_set.get(v).copied().unwrap_or(false)
                 && graph
-                    .predecessors_slice(v)
-                    .iter()
-                    .all(|&p| closed_set.get(p).copied().unwrap_or(false))
+                    .in_neighbors(v)
+                    .all(|p| closed_set.get(p).copied().unwrap_or(false))
         })
         .count();
 
@@ -69,15 +68,14 @@ pub fn parallel_cut_suggestions(
         .map(|v| {
             // Count how many dependents would become actionable if v is closed
             let new_actionable = graph
-                .successors_slice(v)
-                .iter()
-                .filter(|&&w| {
+                .out_neighbors(v)
+                .filter(|&w| {
                     // w must be open
                     !closed_set.get(w).copied().unwrap_or(false)
                     // All of w's other predecessors must be closed
-                    && graph.predecessors_slice(w).iter()
-                        .filter(|&&p| p != v)
-                        .all(|&p| closed_set.get(p).copied().unwrap_or(false))
+                    && graph.in_neighbors(w)
+                        .filter(|&p| p != v)
+                        .all(|p| closed_set.get(p).copied().unwrap_or(false))
                 })
                 .count();
 
@@ -117,15 +115,13 @@ pub fn unblock_ranking(graph: &DiGraph, closed_set: &[bool], limit: usize) -> Ve
         .filter(|&v| !closed_set.get(v).copied().unwrap_or(false))
         .map(|v| {
             let unblocks = graph
-                .successors_slice(v)
-                .iter()
-                .filter(|&&w| {
+                .out_neighbors(v)
+                .filter(|&w| {
                     !closed_set.get(w).copied().unwrap_or(false)
                         && graph
-                            .predecessors_slice(w)
-                            .iter()
-                            .filter(|&&p| p != v)
-                            .all(|&p| closed_set.get(p).copied().unwrap_or(false))
+                            .in_neighbors(w)
+                            .filter(|&p| p != v)
+                            .all(|p| closed_set.get(p).copied().unwrap_or(false))
                 })
                 .count();
             (v, unblocks)
@@ -152,7 +148,7 @@ mod tests {
             g.add_node(&format!("n{}", i));
         }
         for (from, to) in edges {
-            g.add_edge(*from, *to);
+            g.add_edge(*from, *to).unwrap();
         }
         g
     }
@@ -0,0 +1,928 @@
+//! Path counting and path queries between two nodes.
+//!
+//! Answers "how many independent dependency chains connect these beads?"
+//! and, for impact reports, "how far apart is every pair of beads?"
+
+use crate::algorithms::subgraph::Direction;
+use crate::algorithms::topo::{topological_sort, CycleError};
+use crate::graph::DiGraph;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// Count the distinct simple directed paths from `from` to `to`.
+///
+/// Only nodes that lie on some `from` -> `to` path are considered, then a
+/// DP over their topological order sums path counts. A cycle elsewhere in
+/// the graph doesn't matter; a cycle on a connecting path makes the number
+/// of walks infinite and is reported as an error. Counts saturate at
+/// `u64::MAX`. `from == to` counts the empty path, so it returns 1.
+///
+/// Complexity: O(V + E)
+pub fn count_paths(graph: &DiGraph, from: usize, to: usize) -> Result<u64, &'static str> {
+    let n = graph.len();
+    if from >= n || to >= n || graph.is_removed(from) || graph.is_removed(to) {
+        return Err("node index out of range");
+    }
+
+    // Nodes on some from -> to path: reachable from `from` and reaching `to`
+    let forward = reach(n, from, |v| graph.successors_slice(v));
+    let backward = reach(n, to, |v| graph.predecessors_slice(v));
+    let relevant: Vec<bool> = (0..n).map(|v| forward[v] && backward[v]).collect();
+    if !relevant[from] {
+        return Ok(0);
+    }
+
+    // Kahn's algorithm over the relevant subgraph
+    let mut in_degree = vec![0usize; n];
+    for v in (0..n).filter(|&v| relevant[v]) {
+        in_degree[v] = graph.in_neighbors(v).filter(|&u| relevant[u]).count();
+    }
+    if in_degree[from] > 0 {
+        return Err("a cycle lies on a path between the nodes");
+    }
+
+    let mut paths = vec![0u64; n];
+    paths[from] = 1;
+    let mut queue = VecDeque::from([from]);
+    let mut processed = 0;
+    let total = relevant.iter().filter(|&&r| r).count();
+
+    while let Some(u) = queue.pop_front() {
+        processed += 1;
+        for w in graph.out_neighbors(u) {
+            if !relevant[w] {
+                continue;
+            }
+            paths[w] = paths[w].saturating_add(paths[u]);
+            in_degree[w] -= 1;
+            if in_degree[w] == 0 {
+                queue.push_back(w);
+            }
+        }
+    }
+
+    if processed < total {
+        return Err("a cycle lies on a path between the nodes");
+    }
+    Ok(paths[to])
+}
+
+/// Fewest-edge directed path from `from` to `to` (BFS).
+///
+/// The path includes both endpoints, and `from == to` gives `[from]`.
+/// Among equally short paths, successors are tried in adjacency order.
+/// None if `to` is unreachable or either index is out of range or removed.
+pub fn shortest_path(graph: &DiGraph, from: usize, to: usize) -> Option<Vec<usize>> {
+    let n = graph.len();
+    if from >= n || to >= n || graph.is_removed(from) || graph.is_removed(to) {
+        return None;
+    }
+
+    let mut parent = vec![usize::MAX; n];
+    parent[from] = from;
+    let mut queue = VecDeque::from([from]);
+    while let Some(v) = queue.pop_front() {
+        if v == to {
+            break;
+        }
+        for w in graph.out_neighbors(v) {
+            if parent[w] == usize::MAX {
+                parent[w] = v;
+                queue.push_back(w);
+            }
+        }
+    }
+    if parent[to] == usize::MAX {
+        return None;
+    }
+
+    let mut path = vec![to];
+    let mut v = to;
+    while v != from {
+        v = parent[v];
+        path.push(v);
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Directed hop distance from `from` to every node (BFS).
+///
+/// The result always has one entry per node index: `Some(0)` for `from`
+/// itself and None for unreachable or removed nodes. An out-of-range or
+/// removed source reaches nothing, so every entry is None.
+pub fn shortest_path_lengths(graph: &DiGraph, from: usize) -> Vec<Option<u32>> {
+    bfs_distances(graph, &[from], Direction::Forward)
+}
+
+/// Hop distance from the nearest of `sources` to every node, in one BFS.
+///
+/// `Forward` follows edges as stored, `Backward` follows them in reverse
+/// (distance to the nearest source), and `Both` ignores direction. Unlike
+/// `reachable_subgraph`, `Both` lets a path change direction along the way.
+/// Duplicate sources are harmless and out-of-range or removed ones are
+/// skipped; with no valid source every entry is None.
+pub fn bfs_distances(graph: &DiGraph, sources: &[usize], direction: Direction) -> Vec<Option<u32>> {
+    let n = graph.len();
+    let mut dist = vec![None; n];
+    let mut queue = VecDeque::new();
+    for &s in sources {
+        if s < n && !graph.is_removed(s) && dist[s].is_none() {
+            dist[s] = Some(0);
+            queue.push_back(s);
+        }
+    }
+
+    let (forward, backward) = match direction {
+        Direction::Forward => (true, false),
+        Direction::Backward => (false, true),
+        Direction::Both => (true, true),
+    };
+    while let Some(v) = queue.pop_front() {
+        let next = dist[v].map(|d: u32| d + 1);
+        let out: &[u32] = if forward { graph.successors_slice(v) } else { &[] };
+        let inc: &[u32] = if backward { graph.predecessors_slice(v) } else { &[] };
+        for w in out.iter().chain(inc).map(|&w| w as usize) {
+            if dist[w].is_none() {
+                dist[w] = next;
+                queue.push_back(w);
+            }
+        }
+    }
+    dist
+}
+
+/// Result of `all_simple_paths`, shaped like `CycleEnumerationResult`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PathEnumerationResult {
+    /// Paths found, each from `from` to `to` inclusive
+    pub paths: Vec<Vec<usize>>,
+    /// Whether a limit cut the search short
+    pub truncated: bool,
+    /// Number of paths found
+    pub count: usize,
+}
+
+/// Enumerate the simple directed paths from `from` to `to`.
+///
+/// Runs an iterative DFS that never revisits a node on the current path,
+/// so cycles cannot trap it, and only enters nodes that can still reach
+/// `to`. Stops after `max_paths` paths and never extends a path beyond
+/// `max_len` edges. `truncated` is set when either limit stopped the
+/// search; after a `max_len` cut it is conservative, since the cut branch
+/// may not have led to `to` along a simple path. `from == to` yields the
+/// single path `[from]`. Invalid or removed endpoints give no paths.
+pub fn all_simple_paths(
+    graph: &DiGraph,
+    from: usize,
+    to: usize,
+    max_paths: usize,
+    max_len: usize,
+) -> PathEnumerationResult {
+    let n = graph.len();
+    let mut result = PathEnumerationResult {
+        paths: Vec::new(),
+        truncated: false,
+        count: 0,
+    };
+    if from >= n || to >= n || graph.is_removed(from) || graph.is_removed(to) {
+        return result;
+    }
+    let useful = reach(n, to, |v| graph.predecessors_slice(v));
+    if !useful[from] {
+        return result;
+    }
+    if max_paths == 0 {
+        result.truncated = true;
+        return result;
+    }
+
+    // cursor[i] is the next successor of path[i] to try
+    let mut on_path = vec![false; n];
+    let mut path = vec![from];
+    let mut cursor = vec![0usize];
+    on_path[from] = true;
+    while let Some(&v) = path.last() {
+        if v == to {
+            result.paths.push(path.clone());
+            if result.paths.len() >= max_paths {
+                result.truncated = true;
+                break;
+            }
+        } else {
+            let succs = graph.successors_slice(v);
+            let pos = cursor.last_mut().expect("cursor per path node");
+            let next = (*pos..succs.len()).find(|&k| {
+                let w = succs[k] as usize;
+                useful[w] && !on_path[w]
+            });
+            if let Some(k) = next {
+                if path.len() <= max_len {
+                    *pos = k + 1;
+                    let w = succs[k] as usize;
+                    on_path[w] = true;
+                    path.push(w);
+                    cursor.push(0);
+                    continue;
+                }
+                result.truncated = true;
+            }
+        }
+        on_path[v] = false;
+        path.pop();
+        cursor.pop();
+    }
+
+    result.count = result.paths.len();
+    result
+}
+
+/// Cheapest distances and predecessors from one source, from `dijkstra`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DijkstraResult {
+    /// Total weight of the cheapest path found to each node; None if unreached
+    pub dist: Vec<Option<f64>>,
+    /// Node before each node on that path; None for the source and unreached nodes
+    pub pred: Vec<Option<usize>>,
+}
+
+impl DijkstraResult {
+    /// Follow `pred` back from `target` to get the path, source first.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        self.dist.get(target).copied().flatten()?;
+        let mut path = vec![target];
+        let mut v = target;
+        while let Some(u) = self.pred[v] {
+            path.push(u);
+            v = u;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Min-heap entry for `dijkstra`; ordered so the smallest distance pops first.
+#[derive(PartialEq)]
+struct Frontier {
+    dist: f64,
+    node: usize,
+}
+
+impl Eq for Frontier {}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .total_cmp(&self.dist)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Cheapest directed paths from `source` by total edge weight (Dijkstra).
+///
+/// Edges without an explicit weight cost 1.0. With `target`, the search
+/// stops as soon as the target is settled: its distance and path are final,
+/// but other entries may be unreached or only upper bounds.
+///
+/// Complexity: O((V + E) log V)
+///
+/// # Errors
+/// Returns an error if `source` is out of range or removed, or if any edge
+/// weight is negative or NaN. The message names the first such edge; for
+/// negative weights use `bellman_ford` instead.
+pub fn dijkstra(
+    graph: &DiGraph,
+    source: usize,
+    target: Option<usize>,
+) -> Result<DijkstraResult, String> {
+    let n = graph.len();
+    if source >= n || graph.is_removed(source) {
+        return Err(format!("source {} is out of range or removed", source));
+    }
+    for u in 0..n {
+        for (v, w) in graph.weighted_successors(u) {
+            if w.is_nan() || w < 0.0 {
+                return Err(format!(
+                    "edge {} -> {} has weight {}; Dijkstra needs non-negative weights, \
+                     use Bellman-Ford (bellman_ford) instead",
+                    u, v, w
+                ));
+            }
+        }
+    }
+
+    let mut dist: Vec<Option<f64>> = vec![None; n];
+    let mut pred: Vec<Option<usize>> = vec![None; n];
+    let mut settled = vec![false; n];
+    dist[source] = Some(0.0);
+    let mut heap = BinaryHeap::from([Frontier {
+        dist: 0.0,
+        node: source,
+    }]);
+
+    while let Some(Frontier { dist: d, node: u }) = heap.pop() {
+        if settled[u] {
+            continue;
+        }
+        settled[u] = true;
+        if target == Some(u) {
+            break;
+        }
+        for (v, w) in graph.weighted_successors(u) {
+            let candidate = d + w;
+            if !settled[v] && dist[v].is_none_or(|old| candidate < old) {
+                dist[v] = Some(candidate);
+                pred[v] = Some(u);
+                heap.push(Frontier {
+                    dist: candidate,
+                    node: v,
+                });
+            }
+        }
+    }
+
+    Ok(DijkstraResult { dist, pred })
+}
+
+/// Hop-count distance matrix and next-hop matrix, both indexed `[from][to]`.
+pub type AllPairs = (Vec<Vec<Option<usize>>>, Vec<Vec<Option<usize>>>);
+
+/// All-pairs shortest directed paths by hop count (Floyd–Warshall).
+///
+/// Returns `(dist, next)`: `dist[i][j]` is the fewest edges from `i` to `j`
+/// and `next[i][j]` the node after `i` on one such path, both None when `j`
+/// is unreachable. Every live node is at distance 0 from itself; removed
+/// nodes reach nothing. Use `reconstruct_path` to expand `next` into a path.
+///
+/// # Size warning
+/// Takes O(V³) time and two V×V matrices of memory. That is fine for a few
+/// hundred beads, but at 2,000 nodes it is already ~8·10⁹ steps and over
+/// 100 MB; prefer per-source BFS for large graphs.
+pub fn floyd_warshall(graph: &DiGraph) -> AllPairs {
+    let n = graph.len();
+    let mut dist = vec![vec![None; n]; n];
+    let mut next = vec![vec![None; n]; n];
+    for u in (0..n).filter(|&u| !graph.is_removed(u)) {
+        dist[u][u] = Some(0);
+        next[u][u] = Some(u);
+        for v in graph.out_neighbors(u).filter(|&v| v != u) {
+            dist[u][v] = Some(1);
+            next[u][v] = Some(v);
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let Some(ik) = dist[i][k] else { continue };
+            for j in 0..n {
+                let Some(kj) = dist[k][j] else { continue };
+                if dist[i][j].is_none_or(|ij| ik + kj < ij) {
+                    dist[i][j] = Some(ik + kj);
+                    next[i][j] = next[i][k];
+                }
+            }
+        }
+    }
+
+    (dist, next)
+}
+
+/// Expand a `floyd_warshall` next-hop matrix into the path from `i` to `j`.
+///
+/// The path includes both endpoints; None if `j` is unreachable from `i`
+/// or either index is out of range.
+pub fn reconstruct_path(next: &[Vec<Option<usize>>], i: usize, j: usize) -> Option<Vec<usize>> {
+    let mut path = vec![i];
+    let mut v = i;
+    while v != j {
+        v = (*next.get(v)?.get(j)?)?;
+        path.push(v);
+    }
+    next.get(j)?.get(j)?.as_ref()?;
+    Some(path)
+}
+
+/// The most expensive dependency chain, with a schedule for every node.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CriticalPath {
+    /// Nodes on the chain, first prerequisite first
+    pub nodes: Vec<usize>,
+    /// Sum of the costs of `nodes`
+    pub total_cost: f64,
+    /// Earliest start of each node: the largest finish time among its
+    /// prerequisites, 0 for roots and removed nodes
+    pub earliest_start: Vec<f64>,
+}
+
+/// Find the maximum-cost path through a DAG by DP over a topological order.
+///
+/// `node_cost[v]` is the effort of node `v`; with `None`, or for indices
+/// past the end of the slice, each node costs 1. A node finishes at its
+/// earliest start plus its cost, and the path ends at the node that finishes
+/// last. Ties go to the lower index, both for the end node and for the
+/// prerequisite followed backwards, so the result is deterministic.
+///
+/// # Errors
+/// Returns a `CycleError` if the graph contains a cycle.
+pub fn critical_path(
+    graph: &DiGraph,
+    node_cost: Option<&[f64]>,
+) -> Result<CriticalPath, CycleError> {
+    let n = graph.len();
+    let order = topological_sort(graph)?;
+    let cost = |v: usize| node_cost.and_then(|c| c.get(v)).copied().unwrap_or(1.0);
+
+    let mut earliest_start = vec![0.0; n];
+    let mut via: Vec<Option<usize>> = vec![None; n];
+    for &v in &order {
+        for u in graph.in_neighbors(v) {
+            let finish = earliest_start[u] + cost(u);
+            let better = match via[v] {
+                None => true,
+                Some(p) => finish > earliest_start[v] || (finish == earliest_start[v] && u < p),
+            };
+            if better {
+                earliest_start[v] = finish;
+                via[v] = Some(u);
+            }
+        }
+    }
+
+    let mut end: Option<(usize, f64)> = None;
+    for v in (0..n).filter(|&v| !graph.is_removed(v)) {
+        let finish = earliest_start[v] + cost(v);
+        if end.is_none_or(|(_, best)| finish > best) {
+            end = Some((v, finish));
+        }
+    }
+
+    let Some((last, total_cost)) = end else {
+        return Ok(CriticalPath {
+            nodes: Vec::new(),
+            total_cost: 0.0,
+            earliest_start,
+        });
+    };
+    let mut nodes = vec![last];
+    while let Some(u) = via[*nodes.last().unwrap()] {
+        nodes.push(u);
+    }
+    nodes.reverse();
+
+    Ok(CriticalPath {
+        nodes,
+        total_cost,
+        earliest_start,
+    })
+}
+
+/// Longest simple path found by a depth-bounded search. A heuristic, not exact.
+///
+/// Finding the longest simple path is NP-hard on general (cyclic) graphs,
+/// so this runs a DFS from every live node that never revisits a node on
+/// the current path and stops extending once a path has `cutoff` nodes.
+/// It returns the longest path seen, first node first; ties keep the one
+/// found first, and `cutoff == 0` returns an empty path. The search is
+/// still exponential in `cutoff` on dense graphs, so keep it small there.
+pub fn longest_simple_path(graph: &DiGraph, cutoff: usize) -> Vec<usize> {
+    let n = graph.len();
+    let limit = cutoff.min(graph.active_len());
+    let mut best: Vec<usize> = Vec::new();
+    let mut on_path = vec![false; n];
+
+    for start in (0..n).filter(|&v| !graph.is_removed(v)) {
+        if best.len() >= limit {
+            break;
+        }
+        // Iterative DFS; cursor[i] is the next successor of path[i] to try
+        let mut path = vec![start];
+        let mut cursor = vec![0usize];
+        on_path[start] = true;
+        while let Some(&v) = path.last() {
+            if path.len() > best.len() {
+                best.clone_from(&path);
+                if best.len() >= limit {
+                    break;
+                }
+            }
+            let succs = graph.successors_slice(v);
+            let pos = cursor.last_mut().expect("cursor per path node");
+            let next = if path.len() < limit {
+                (*pos..succs.len()).find(|&k| !on_path[succs[k] as usize])
+            } else {
+                None
+            };
+            match next {
+                Some(k) => {
+                    *pos = k + 1;
+                    let w = succs[k] as usize;
+                    on_path[w] = true;
+                    path.push(w);
+                    cursor.push(0);
+                }
+                None => {
+                    on_path[v] = false;
+                    path.pop();
+                    cursor.pop();
+                }
+            }
+        }
+        for &v in &path {
+            on_path[v] = false;
+        }
+    }
+    best
+}
+
+/// Mark every node reachable from `start` via `next` (BFS).
+fn reach<'a, F>(n: usize, start: usize, next: F) -> Vec<bool>
+where
+    F: Fn(usize) -> &'a [u32],
+{
+    let mut seen = vec![false; n];
+    seen[start] = true;
+    let mut queue = VecDeque::from([start]);
+    while let Some(v) = queue.pop_front() {
+        for &w in next(v) {
+            let w = w as usize;
+            if !seen[w] {
+                seen[w] = true;
+                queue.push_back(w);
+            }
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_paths_diamond_and_chain() {
+        //     a
+        //    / \
+        //   b   c
+        //    \ /
+        //     d -> e
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, e).unwrap();
+
+        assert_eq!(count_paths(&graph, a, d), Ok(2));
+        assert_eq!(count_paths(&graph, a, e), Ok(2));
+        assert_eq!(count_paths(&graph, b, e), Ok(1));
+        assert_eq!(count_paths(&graph, a, a), Ok(1));
+        assert_eq!(count_paths(&graph, e, a), Ok(0));
+        assert!(count_paths(&graph, a, 99).is_err());
+    }
+
+    #[test]
+    fn test_count_paths_cycles() {
+        // a -> b -> c, b <-> x (cycle on the path), plus unrelated y <-> z
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let y = graph.add_node("y");
+        let z = graph.add_node("z");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(y, z).unwrap();
+        graph.add_edge(z, y).unwrap();
+
+        // A cycle off the path doesn't affect the count
+        assert_eq!(count_paths(&graph, a, c), Ok(1));
+
+        let x = graph.add_node("x");
+        graph.add_edge(b, x).unwrap();
+        graph.add_edge(x, b).unwrap();
+        assert!(count_paths(&graph, a, c).is_err());
+    }
+
+    #[test]
+    fn test_count_paths_saturates() {
+        // 70 stacked diamonds: 2^70 paths overflow u64
+        let mut graph = DiGraph::new();
+        let mut prev = graph.add_node("s0");
+        for i in 0..70 {
+            let l = graph.add_node(&format!("l{}", i));
+            let r = graph.add_node(&format!("r{}", i));
+            let next = graph.add_node(&format!("s{}", i + 1));
+            graph.add_edge(prev, l).unwrap();
+            graph.add_edge(prev, r).unwrap();
+            graph.add_edge(l, next).unwrap();
+            graph.add_edge(r, next).unwrap();
+            prev = next;
+        }
+        assert_eq!(count_paths(&graph, 0, prev), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_floyd_warshall_chain() {
+        // a -> b -> c, plus a longer detour a -> x -> y -> c
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let x = graph.add_node("x");
+        let y = graph.add_node("y");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(a, x).unwrap();
+        graph.add_edge(x, y).unwrap();
+        graph.add_edge(y, c).unwrap();
+
+        let (dist, next) = floyd_warshall(&graph);
+        assert_eq!(dist[a][c], Some(2));
+        assert_eq!(reconstruct_path(&next, a, c), Some(vec![a, b, c]));
+        assert_eq!(reconstruct_path(&next, a, a), Some(vec![a]));
+        assert_eq!(dist[c][a], None);
+        assert_eq!(reconstruct_path(&next, c, a), None);
+        assert_eq!(reconstruct_path(&next, a, 99), None);
+
+        graph.remove_node(b);
+        let (dist, next) = floyd_warshall(&graph);
+        assert_eq!(dist[a][c], Some(3));
+        assert_eq!(reconstruct_path(&next, a, c), Some(vec![a, x, y, c]));
+        assert_eq!(reconstruct_path(&next, b, b), None);
+    }
+
+    #[test]
+    fn test_critical_path_unit_costs_diamond() {
+        // a -> b -> d and a -> c -> d tie; the lower index wins
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        g.add_edge(a, c).unwrap();
+        g.add_edge(a, b).unwrap();
+        g.add_edge(c, d).unwrap();
+        g.add_edge(b, d).unwrap();
+
+        let path = critical_path(&g, None).unwrap();
+        assert_eq!(path.nodes, vec![a, b, d]);
+        assert_eq!(path.total_cost, 3.0);
+        assert_eq!(path.earliest_start, vec![0.0, 1.0, 1.0, 2.0]);
+
+        // A heavier c shifts the path and d's start
+        let path = critical_path(&g, Some(&[1.0, 2.0, 5.0, 1.0])).unwrap();
+        assert_eq!(path.nodes, vec![a, c, d]);
+        assert_eq!(path.total_cost, 7.0);
+        assert_eq!(path.earliest_start, vec![0.0, 1.0, 1.0, 6.0]);
+
+        g.add_edge(d, a).unwrap();
+        assert_eq!(critical_path(&g, None).unwrap_err().nodes, vec![a, b, c, d]);
+    }
+
+    #[test]
+    fn test_critical_path_disconnected() {
+        // x -> y is short but expensive; p -> q -> r is long but cheap
+        let mut g = DiGraph::new();
+        let p = g.add_node("p");
+        let q = g.add_node("q");
+        let r = g.add_node("r");
+        let x = g.add_node("x");
+        let y = g.add_node("y");
+        let gone = g.add_node("gone");
+        g.add_edge(p, q).unwrap();
+        g.add_edge(q, r).unwrap();
+        g.add_edge(x, y).unwrap();
+        g.add_edge(gone, p).unwrap();
+        g.remove_node(gone);
+
+        let path = critical_path(&g, None).unwrap();
+        assert_eq!(path.nodes, vec![p, q, r]);
+        assert_eq!(path.earliest_start, vec![0.0, 1.0, 2.0, 0.0, 1.0, 0.0]);
+
+        // Missing trailing costs default to 1
+        let path = critical_path(&g, Some(&[1.0, 1.0, 1.0, 4.0])).unwrap();
+        assert_eq!(path.nodes, vec![x, y]);
+        assert_eq!(path.total_cost, 5.0);
+
+        let empty = critical_path(&DiGraph::new(), None).unwrap();
+        assert!(empty.nodes.is_empty());
+        assert_eq!(empty.total_cost, 0.0);
+    }
+
+    #[test]
+    fn test_longest_simple_path_cyclic() {
+        // 0 -> 1 -> 2 -> 0, 2 -> 3 -> 1, 3 -> 4
+        let mut g = DiGraph::new();
+        for i in 0..5 {
+            g.add_node(&format!("n{}", i));
+        }
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 1), (3, 4)] {
+            g.add_edge(u, v).unwrap();
+        }
+
+        let path = longest_simple_path(&g, 100);
+        assert_eq!(path, vec![0, 1, 2, 3, 4]);
+        let mut unique = path.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), path.len());
+        assert!(path.windows(2).all(|w| g.has_edge(w[0], w[1])));
+
+        // The cutoff bounds the length
+        assert_eq!(longest_simple_path(&g, 3), vec![0, 1, 2]);
+        assert!(longest_simple_path(&g, 0).is_empty());
+        assert!(longest_simple_path(&DiGraph::new(), 5).is_empty());
+    }
+
+    #[test]
+    fn test_shortest_path_dag_and_missing() {
+        // a -> b -> c -> d plus the shortcut a -> c; e is unreachable
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        let e = g.add_node("e");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(c, d).unwrap();
+        g.add_edge(a, c).unwrap();
+
+        assert_eq!(shortest_path(&g, a, d), Some(vec![a, c, d]));
+        assert_eq!(shortest_path(&g, b, b), Some(vec![b]));
+        assert_eq!(shortest_path(&g, d, a), None);
+        assert_eq!(shortest_path(&g, a, e), None);
+        assert_eq!(shortest_path(&g, a, 99), None);
+
+        let dist = shortest_path_lengths(&g, a);
+        assert_eq!(dist.len(), g.len());
+        assert_eq!(dist, vec![Some(0), Some(1), Some(1), Some(2), None]);
+        assert_eq!(shortest_path_lengths(&g, 99), vec![None; 5]);
+    }
+
+    #[test]
+    fn test_shortest_path_through_scc() {
+        // s -> x -> y -> z -> x, z -> t: the only route loops through {x, y, z}
+        let mut g = DiGraph::new();
+        let s = g.add_node("s");
+        let x = g.add_node("x");
+        let y = g.add_node("y");
+        let z = g.add_node("z");
+        let t = g.add_node("t");
+        g.add_edge(s, x).unwrap();
+        g.add_edge(x, y).unwrap();
+        g.add_edge(y, z).unwrap();
+        g.add_edge(z, x).unwrap();
+        g.add_edge(z, t).unwrap();
+
+        assert_eq!(shortest_path(&g, s, t), Some(vec![s, x, y, z, t]));
+        assert_eq!(shortest_path(&g, z, y), Some(vec![z, x, y]));
+        assert_eq!(shortest_path_lengths(&g, y)[y], Some(0));
+        assert_eq!(shortest_path_lengths(&g, y)[t], Some(2));
+
+        g.remove_node(y);
+        assert_eq!(shortest_path(&g, s, t), None);
+        assert_eq!(shortest_path(&g, y, y), None);
+    }
+
+    #[test]
+    fn test_bfs_distances_directions() {
+        // a -> b -> c <- d, plus an isolated e
+        let mut g = DiGraph::new();
+        for id in ["a", "b", "c", "d", "e"] {
+            g.add_node(id);
+        }
+        g.add_edge(0, 1).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(3, 2).unwrap();
+
+        let forward = bfs_distances(&g, &[0, 3], Direction::Forward);
+        assert_eq!(forward, vec![Some(0), Some(1), Some(1), Some(0), None]);
+        let backward = bfs_distances(&g, &[2], Direction::Backward);
+        assert_eq!(backward, vec![Some(2), Some(1), Some(0), Some(1), None]);
+        assert_eq!(bfs_distances(&g, &[0], Direction::Backward)[1], None);
+        // Undirected: a reaches d by turning around at c
+        let both = bfs_distances(&g, &[0, 0, 99], Direction::Both);
+        assert_eq!(both, vec![Some(0), Some(1), Some(2), Some(3), None]);
+        assert_eq!(bfs_distances(&g, &[], Direction::Both), vec![None; 5]);
+    }
+
+    /// s -> a -> b -> t costs 3 in three hops; s -> t directly costs 10.
+    fn weighted_detour() -> DiGraph {
+        let mut g = DiGraph::new();
+        for id in ["s", "a", "b", "t", "x"] {
+            g.add_node(id);
+        }
+        g.add_weighted_edge(0, 3, 10.0).unwrap();
+        g.add_weighted_edge(0, 1, 1.0).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_weighted_edge(2, 3, 1.0).unwrap();
+        g.add_weighted_edge(3, 4, 0.5).unwrap();
+        g
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheaper_longer_path() {
+        let g = weighted_detour();
+        let result = dijkstra(&g, 0, None).unwrap();
+        assert_eq!(
+            result.dist,
+            vec![Some(0.0), Some(1.0), Some(2.0), Some(3.0), Some(3.5)]
+        );
+        assert_eq!(result.path_to(3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(shortest_path(&g, 0, 3), Some(vec![0, 3]));
+        assert_eq!(dijkstra(&g, 3, None).unwrap().path_to(0), None);
+    }
+
+    #[test]
+    fn test_dijkstra_early_termination() {
+        let g = weighted_detour();
+        let full = dijkstra(&g, 0, None).unwrap();
+        let early = dijkstra(&g, 0, Some(2)).unwrap();
+        assert_eq!(early.dist[2], full.dist[2]);
+        assert_eq!(early.path_to(2), Some(vec![0, 1, 2]));
+        // t was only reached through the expensive direct edge so far
+        assert_eq!(early.dist[3], Some(10.0));
+        assert_eq!(early.dist[4], None);
+
+        let early = dijkstra(&g, 0, Some(3)).unwrap();
+        assert_eq!(early.path_to(3), full.path_to(3));
+    }
+
+    #[test]
+    fn test_dijkstra_rejects_negative_weight() {
+        let mut g = weighted_detour();
+        g.add_weighted_edge(4, 1, -2.0).unwrap();
+        let err = dijkstra(&g, 0, None).unwrap_err();
+        assert!(err.starts_with("edge 4 -> 1 has weight -2;"), "{}", err);
+        assert!(err.contains("Bellman-Ford"));
+        assert!(dijkstra(&g, 42, None).is_err());
+    }
+
+    #[test]
+    fn test_all_simple_paths_diamond_and_side_cycle() {
+        // a -> b -> d, a -> c -> d
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(a, c).unwrap();
+        g.add_edge(b, d).unwrap();
+        g.add_edge(c, d).unwrap();
+
+        let result = all_simple_paths(&g, a, d, 10, 10);
+        assert_eq!(result.paths, vec![vec![a, b, d], vec![a, c, d]]);
+        assert_eq!(result.count, 2);
+        assert!(!result.truncated);
+
+        // A side cycle b -> e -> b and a loop back into a must not trap the search
+        let e = g.add_node("e");
+        g.add_edge(b, e).unwrap();
+        g.add_edge(e, b).unwrap();
+        g.add_edge(d, a).unwrap();
+        let result = all_simple_paths(&g, a, d, 10, 10);
+        assert_eq!(result.paths, vec![vec![a, b, d], vec![a, c, d]]);
+
+        assert_eq!(all_simple_paths(&g, c, c, 10, 10).paths, vec![vec![c]]);
+        assert_eq!(all_simple_paths(&g, e, 99, 10, 10).count, 0);
+    }
+
+    #[test]
+    fn test_all_simple_paths_limits() {
+        // a -> b -> c -> d plus the shortcut a -> d
+        let mut g = DiGraph::new();
+        for id in ["a", "b", "c", "d"] {
+            g.add_node(id);
+        }
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (0, 3)] {
+            g.add_edge(u, v).unwrap();
+        }
+
+        let result = all_simple_paths(&g, 0, 3, 1, 10);
+        assert_eq!(result.paths, vec![vec![0, 1, 2, 3]]);
+        assert!(result.truncated);
+
+        let result = all_simple_paths(&g, 0, 3, 10, 2);
+        assert_eq!(result.paths, vec![vec![0, 3]]);
+        assert!(result.truncated);
+
+        let result = all_simple_paths(&g, 0, 3, 10, 3);
+        assert_eq!(result.count, 2);
+        assert!(!result.truncated);
+    }
+}
@@ -0,0 +1,690 @@
+//! Precomputed reachability for repeated "can X reach Y?" queries.
+//!
+//! Hovering a node in the viewer asks this hundreds of times, so instead of
+//! a BFS per query we store, for every strongly connected component, the set
+//! of nodes it can reach as a bitset. Members of one SCC reach exactly the
+//! same nodes, so they share a row. Rows only span the weakly connected
+//! component the SCC lives in, since nothing outside it is reachable; that
+//! keeps a graph of many small islands far below the dense V² bits.
+//! The same index backs `transitive_reduction`.
+
+use crate::algorithms::components::{weakly_connected_components, WCCResult};
+use crate::algorithms::cycles::{tarjan_scc, SCCResult};
+use crate::algorithms::subgraph::{reachable_from, reachable_to};
+use crate::algorithms::topo::is_dag;
+use crate::graph::{flat_pairs, DiGraph};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Descendant bitsets for every node, built by `transitive_closure`.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct ReachabilityIndex {
+    /// SCC of each node (row index), `usize::MAX` for removed nodes
+    scc_of: Vec<usize>,
+    /// Weakly connected component of each node
+    wcc_of: Vec<usize>,
+    /// Bit position of each node within its weak component's rows
+    local: Vec<usize>,
+    /// Nodes reachable from each SCC, its own members included
+    rows: Vec<Vec<u64>>,
+}
+
+#[wasm_bindgen]
+impl ReachabilityIndex {
+    /// True if `to` can be reached from `from`; every live node reaches itself.
+    /// False for removed or out-of-range nodes. O(1).
+    #[wasm_bindgen(js_name = reaches)]
+    pub fn reaches(&self, from: usize, to: usize) -> bool {
+        let (Some(&scc), Some(&wcc)) = (self.scc_of.get(from), self.wcc_of.get(to)) else {
+            return false;
+        };
+        if scc == usize::MAX || wcc != self.wcc_of[from] {
+            return false;
+        }
+        let bit = self.local[to];
+        self.rows[scc][bit / 64] & (1 << (bit % 64)) != 0
+    }
+
+    /// Number of nodes reachable from `node`, not counting itself.
+    #[wasm_bindgen(js_name = descendantCount)]
+    pub fn descendant_count(&self, node: usize) -> usize {
+        match self.scc_of.get(node) {
+            Some(&scc) if scc != usize::MAX => {
+                let reached: u32 = self.rows[scc].iter().map(|w| w.count_ones()).sum();
+                reached as usize - 1
+            }
+            _ => 0,
+        }
+    }
+
+    /// Answer many queries at once from a flat [from, to, from, to, ...] array.
+    /// Returns one 0/1 byte per pair; throws if the array has odd length.
+    #[wasm_bindgen(js_name = reachesBatch)]
+    pub fn reaches_batch(&self, pairs: &[usize]) -> Result<Vec<u8>, JsError> {
+        let pairs = flat_pairs(pairs).map_err(|e| JsError::new(&e))?;
+        Ok(pairs.into_iter().map(|(a, b)| u8::from(self.reaches(a, b))).collect())
+    }
+}
+
+/// Everything `node` transitively depends on (its blockers' blockers, ...),
+/// sorted ascending.
+///
+/// One BFS over the stored predecessor lists. The node itself is left out,
+/// even when it lies on a cycle; its SCC co-members are included. Empty for
+/// out-of-range nodes. For many queries, build a `ReachabilityIndex`.
+pub fn ancestors(graph: &DiGraph, node: usize) -> Vec<usize> {
+    let mut nodes = reachable_to(graph, node);
+    nodes.retain(|&v| v != node);
+    nodes.sort_unstable();
+    nodes
+}
+
+/// Everything that transitively depends on `node`, sorted ascending.
+///
+/// The mirror image of `ancestors`, following outgoing edges.
+pub fn descendants(graph: &DiGraph, node: usize) -> Vec<usize> {
+    let mut nodes = reachable_from(graph, node);
+    nodes.retain(|&v| v != node);
+    nodes.sort_unstable();
+    nodes
+}
+
+/// Live nodes that no source (in-degree 0 node) reaches, sorted ascending.
+///
+/// One traversal from all sources at once. What remains is every cycle
+/// without an entry from outside, including a node whose only incoming
+/// edge is a self-loop, plus everything downstream of such a cycle. An
+/// acyclic graph always yields an empty list.
+pub fn unreachable_from_sources(graph: &DiGraph) -> Vec<usize> {
+    let mut seen = vec![false; graph.len()];
+    let mut stack = graph.sources();
+    for &s in &stack {
+        seen[s] = true;
+    }
+    while let Some(v) = stack.pop() {
+        for w in graph.out_neighbors(v) {
+            if !seen[w] {
+                seen[w] = true;
+                stack.push(w);
+            }
+        }
+    }
+    (0..graph.len())
+        .filter(|&v| !seen[v] && !graph.is_removed(v))
+        .collect()
+}
+
+/// Lowest common ancestors of `a` and `b`, sorted ascending.
+///
+/// A common ancestor reaches both nodes; a node counts as its own ancestor,
+/// so if `a` blocks `b` the answer is `[a]`. It is lowest when none of its
+/// proper descendants is also a common ancestor. A DAG can have several
+/// incomparable ones, so all are returned. Cyclic graphs are handled on the
+/// SCC condensation, with each component reported by its smallest node.
+/// Empty when there is no common ancestor or either node is invalid.
+pub fn lca(graph: &DiGraph, a: usize, b: usize) -> Vec<usize> {
+    lca_with(graph, &tarjan_scc(graph), a, b)
+}
+
+/// `lca` for many pairs, sharing one SCC decomposition.
+pub fn lca_batch(graph: &DiGraph, pairs: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let scc = tarjan_scc(graph);
+    pairs.iter().map(|&(a, b)| lca_with(graph, &scc, a, b)).collect()
+}
+
+fn lca_with(graph: &DiGraph, scc: &SCCResult, a: usize, b: usize) -> Vec<usize> {
+    let valid = |v: usize| v < graph.len() && !graph.is_removed(v);
+    if !valid(a) || !valid(b) {
+        return Vec::new();
+    }
+
+    // Components holding an ancestor of both
+    let mut common = vec![0u8; scc.components.len()];
+    for (bit, node) in [(1, a), (2, b)] {
+        for v in reachable_to(graph, node) {
+            common[scc.component_of(v)] |= bit;
+        }
+    }
+    let is_common = |c: usize| common[c] == 3;
+
+    // Ancestors of a common ancestor are common too, so a component is lowest
+    // exactly when no successor component is common
+    let mut lowest: Vec<usize> = (0..scc.components.len())
+        .filter(|&c| is_common(c))
+        .filter(|&c| {
+            scc.components[c].iter().all(|&u| {
+                graph.out_neighbors(u).all(|v| {
+                    let d = scc.component_of(v);
+                    d == c || !is_common(d)
+                })
+            })
+        })
+        .map(|c| scc.components[c][0])
+        .collect();
+    lowest.sort_unstable();
+    lowest
+}
+
+/// Build the reachability index with no memory limit.
+///
+/// See `transitive_closure_within` for large graphs.
+pub fn transitive_closure(graph: &DiGraph) -> ReachabilityIndex {
+    transitive_closure_within(graph, usize::MAX).expect("no memory budget")
+}
+
+/// Estimated heap size in bytes of the index `transitive_closure` would
+/// build: the bitset rows plus three lookup entries per node.
+pub fn estimate_closure_bytes(graph: &DiGraph) -> usize {
+    let scc = tarjan_scc(graph);
+    let wcc = weakly_connected_components(graph);
+    estimate_bytes(graph.len(), &scc, &wcc)
+}
+
+fn estimate_bytes(n: usize, scc: &SCCResult, wcc: &WCCResult) -> usize {
+    let row_words: usize = scc.components.iter().map(|c| row_width(wcc, c)).sum();
+    row_words * 8 + n * 3 * std::mem::size_of::<usize>()
+}
+
+/// Words in the row of SCC `members`: one bit per node of its weak component.
+fn row_width(wcc: &WCCResult, members: &[usize]) -> usize {
+    wcc.components[wcc.component_of[members[0]]]
+        .len()
+        .div_ceil(64)
+}
+
+/// Build the reachability index if it fits in `max_bytes`.
+///
+/// SCC rows are filled in reverse topological order of the condensation,
+/// each one the union of its own members and the rows of the SCCs it has
+/// edges into. Time O(V + E·W) where W is the row width in words of the
+/// largest weak component.
+///
+/// # Errors
+/// Returns an error, without allocating the rows, if
+/// `estimate_closure_bytes` exceeds `max_bytes`.
+pub fn transitive_closure_within(
+    graph: &DiGraph,
+    max_bytes: usize,
+) -> Result<ReachabilityIndex, String> {
+    let n = graph.len();
+    let scc = tarjan_scc(graph);
+    let wcc = weakly_connected_components(graph);
+    let bytes = estimate_bytes(n, &scc, &wcc);
+    if bytes > max_bytes {
+        return Err(format!(
+            "reachability index needs about {} bytes, over the budget of {}",
+            bytes, max_bytes
+        ));
+    }
+
+    let scc_of: Vec<usize> = (0..n).map(|v| scc.component_of(v)).collect();
+    let mut local = vec![usize::MAX; n];
+    for component in &wcc.components {
+        for (i, &v) in component.iter().enumerate() {
+            local[v] = i;
+        }
+    }
+
+    // Reverse Kahn over the condensation: an SCC is ready once every SCC
+    // it has edges into has its row
+    let k = scc.components.len();
+    let mut pending = vec![0usize; k];
+    for (u, v) in graph.edges() {
+        if scc_of[u] != scc_of[v] {
+            pending[scc_of[u]] += 1;
+        }
+    }
+    let mut rows: Vec<Vec<u64>> = vec![Vec::new(); k];
+    let mut ready: Vec<usize> = (0..k).filter(|&c| pending[c] == 0).collect();
+    while let Some(c) = ready.pop() {
+        let members = &scc.components[c];
+        let mut row = vec![0u64; row_width(&wcc, members)];
+        for &u in members {
+            row[local[u] / 64] |= 1 << (local[u] % 64);
+            for w in graph.out_neighbors(u) {
+                let d = scc_of[w];
+                if d != c {
+                    for (word, &other) in row.iter_mut().zip(&rows[d]) {
+                        *word |= other;
+                    }
+                }
+            }
+        }
+        rows[c] = row;
+
+        for &u in members {
+            for w in graph.in_neighbors(u) {
+                let d = scc_of[w];
+                if d != c {
+                    pending[d] -= 1;
+                    if pending[d] == 0 {
+                        ready.push(d);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(ReachabilityIndex {
+        scc_of,
+        wcc_of: wcc.component_of,
+        local,
+        rows,
+    })
+}
+
+/// Edges that a transitive reduction would drop.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransitiveReductionResult {
+    /// Edges implied by a longer path, in `edges()` order
+    pub redundant: Vec<(usize, usize)>,
+    /// Edges inside a strongly connected component, which are never reported
+    pub intra_scc_edges: usize,
+}
+
+impl TransitiveReductionResult {
+    /// Remove the redundant edges from `graph`, returning how many went.
+    pub fn apply(&self, graph: &mut DiGraph) -> usize {
+        self.redundant
+            .iter()
+            .filter(|&&(u, v)| graph.remove_edge(u, v))
+            .count()
+    }
+}
+
+/// Find the edges implied by longer dependency chains (A→C when A→B→C).
+///
+/// Works on the SCC condensation, so cyclic graphs are fine: an edge
+/// between two components is redundant when its target is also reachable
+/// through a different component the source points to. Edges inside a
+/// component are only counted. Several edges joining the same pair of
+/// components are all kept, since dropping them is a choice rather than
+/// an implication. On a DAG this is the usual transitive reduction.
+///
+/// Builds a `transitive_closure`, so it needs the same memory; see
+/// `transitive_reduction_within` for large graphs.
+pub fn transitive_reduction(graph: &DiGraph) -> TransitiveReductionResult {
+    transitive_reduction_within(graph, usize::MAX).expect("no memory budget")
+}
+
+/// `transitive_reduction` if its reachability index fits in `max_bytes`.
+///
+/// # Errors
+/// Returns the `transitive_closure_within` error when the index would
+/// exceed `max_bytes`.
+pub fn transitive_reduction_within(
+    graph: &DiGraph,
+    max_bytes: usize,
+) -> Result<TransitiveReductionResult, String> {
+    let n = graph.len();
+    let index = transitive_closure_within(graph, max_bytes)?;
+    let scc = tarjan_scc(graph);
+
+    // Distinct successor components of each component, deduplicated with
+    // a marker holding the last component that recorded each target
+    let mut next: Vec<Vec<usize>> = vec![Vec::new(); scc.components.len()];
+    let mut seen = vec![usize::MAX; scc.components.len()];
+    for (a, members) in scc.components.iter().enumerate() {
+        for &u in members {
+            for v in graph.out_neighbors(u) {
+                let b = scc.component_of(v);
+                if a != b && seen[b] != a {
+                    seen[b] = a;
+                    next[a].push(b);
+                }
+            }
+        }
+    }
+
+    let mut result = TransitiveReductionResult {
+        redundant: Vec::new(),
+        intra_scc_edges: 0,
+    };
+    for u in 0..n {
+        let a = scc.component_of(u);
+        for v in graph.out_neighbors(u) {
+            let b = scc.component_of(v);
+            if a == b {
+                result.intra_scc_edges += 1;
+                continue;
+            }
+            let implied = next[a]
+                .iter()
+                .any(|&c| c != b && index.reaches(scc.components[c][0], v));
+            if implied {
+                result.redundant.push((u, v));
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Lint-style list of DAG edges implied by a longer path.
+///
+/// Same edges as `transitive_reduction(graph).redundant`, for reporting
+/// ("A→C is redundant because A→B→C exists") without touching the graph.
+/// Returns an empty list if the graph has a cycle, since a dependency cycle
+/// is the bigger problem to report; check `is_dag` to tell the cases apart.
+pub fn redundant_edges(graph: &DiGraph) -> Vec<(usize, usize)> {
+    redundant_edges_within(graph, usize::MAX).expect("no memory budget")
+}
+
+/// `redundant_edges` if its reachability index fits in `max_bytes`.
+///
+/// # Errors
+/// As for `transitive_reduction_within`; a cyclic graph is not an error.
+pub fn redundant_edges_within(
+    graph: &DiGraph,
+    max_bytes: usize,
+) -> Result<Vec<(usize, usize)>, String> {
+    if !is_dag(graph) {
+        return Ok(Vec::new());
+    }
+    Ok(transitive_reduction_within(graph, max_bytes)?.redundant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sparse random digraph from an LCG, with every tenth node removed.
+    fn random_graph(n: usize, edges: usize, seed: u64) -> DiGraph {
+        let mut state = seed;
+        let mut next = |bound: usize| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (state >> 33) as usize % bound
+        };
+        let mut graph = DiGraph::new();
+        for i in 0..n {
+            graph.add_node(&format!("n{}", i));
+        }
+        for _ in 0..edges {
+            graph.add_edge(next(n), next(n)).unwrap();
+        }
+        for v in (0..n).step_by(10) {
+            graph.remove_node(v);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_closure_matches_bfs() {
+        for seed in 0..5 {
+            // Spans more than one 64-bit word per row
+            let graph = random_graph(150, 180, seed);
+            let index = transitive_closure(&graph);
+            for from in 0..graph.len() {
+                let mut expected = vec![false; graph.len()];
+                if !graph.is_removed(from) {
+                    for v in reachable_from(&graph, from) {
+                        expected[v] = true;
+                    }
+                }
+                for (to, &reached) in expected.iter().enumerate() {
+                    assert_eq!(index.reaches(from, to), reached, "{} -> {}", from, to);
+                }
+                let count = expected.iter().filter(|&&r| r).count();
+                assert_eq!(index.descendant_count(from), count.saturating_sub(1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_closure_scc_members_and_batch() {
+        // a -> b -> c -> a, c -> d; e alone
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(c, d).unwrap();
+
+        let index = transitive_closure(&graph);
+        for u in [a, b, c] {
+            for v in [a, b, c, d] {
+                assert!(index.reaches(u, v));
+            }
+            assert_eq!(index.descendant_count(u), 3);
+        }
+        assert!(!index.reaches(d, a));
+        assert!(!index.reaches(a, e));
+        assert!(!index.reaches(a, 99));
+        assert_eq!(index.descendant_count(e), 0);
+        assert_eq!(index.reaches_batch(&[b, d, d, b, e, e]).unwrap(), vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_closure_memory_guard() {
+        let graph = random_graph(200, 400, 7);
+        let needed = estimate_closure_bytes(&graph);
+        let err = transitive_closure_within(&graph, needed - 1).unwrap_err();
+        assert!(err.contains("over the budget"), "{}", err);
+        assert!(transitive_closure_within(&graph, needed).is_ok());
+        assert!(transitive_reduction_within(&graph, needed - 1).is_err());
+        assert_eq!(
+            transitive_reduction_within(&graph, needed),
+            Ok(transitive_reduction(&graph))
+        );
+
+        let mut dag = DiGraph::new();
+        for (u, v) in [("a", "b"), ("b", "c"), ("a", "c")] {
+            let (u, v) = (dag.add_node(u), dag.add_node(v));
+            dag.add_edge(u, v).unwrap();
+        }
+        let needed = estimate_closure_bytes(&dag);
+        assert!(redundant_edges_within(&dag, needed - 1).is_err());
+        assert_eq!(redundant_edges_within(&dag, needed), Ok(vec![(0, 2)]));
+
+        // Islands are cheaper than one dense matrix
+        let mut islands = DiGraph::new();
+        for i in 0..1000 {
+            let v = islands.add_node(&format!("n{}", i));
+            if i % 2 == 1 {
+                islands.add_edge(v - 1, v).unwrap();
+            }
+        }
+        assert!(estimate_closure_bytes(&islands) < 1000 * 1000 / 8);
+    }
+
+    #[test]
+    fn test_transitive_reduction_shortcut() {
+        // a -> b -> c plus the shortcut a -> c, and an unrelated d -> c
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(d, c).unwrap();
+
+        let result = transitive_reduction(&graph);
+        assert_eq!(result.redundant, vec![(a, c)]);
+        assert_eq!(result.intra_scc_edges, 0);
+
+        assert_eq!(result.apply(&mut graph), 1);
+        assert!(!graph.has_edge(a, c));
+        assert!(transitive_reduction(&graph).redundant.is_empty());
+        assert!(transitive_closure(&graph).reaches(a, c));
+    }
+
+    #[test]
+    fn test_redundant_edges_triangle() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B");
+        let c = graph.add_node("C");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(a, c).unwrap();
+        assert_eq!(redundant_edges(&graph), vec![(a, c)]);
+        assert_eq!(graph.edge_count(), 3);
+
+        // Closing the loop makes it cyclic: nothing is reported
+        graph.add_edge(c, a).unwrap();
+        assert!(redundant_edges(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_lca_diamond_and_disjoint() {
+        //   apex
+        //   /  \
+        //  l    r     x (unrelated)
+        //   \  /
+        //   join
+        let mut graph = DiGraph::new();
+        let apex = graph.add_node("apex");
+        let l = graph.add_node("l");
+        let r = graph.add_node("r");
+        let join = graph.add_node("join");
+        let x = graph.add_node("x");
+        graph.add_edge(apex, l).unwrap();
+        graph.add_edge(apex, r).unwrap();
+        graph.add_edge(l, join).unwrap();
+        graph.add_edge(r, join).unwrap();
+
+        assert_eq!(lca(&graph, l, r), vec![apex]);
+        assert_eq!(lca(&graph, l, join), vec![l]);
+        assert!(lca(&graph, l, x).is_empty());
+        assert!(lca(&graph, l, 99).is_empty());
+        assert_eq!(
+            lca_batch(&graph, &[(l, r), (join, join), (x, apex)]),
+            vec![vec![apex], vec![join], vec![]]
+        );
+    }
+
+    #[test]
+    fn test_lca_incomparable_and_cyclic() {
+        // p and q both block s1 and s2, and neither reaches the other
+        let mut graph = DiGraph::new();
+        let p = graph.add_node("p");
+        let q = graph.add_node("q");
+        let s1 = graph.add_node("s1");
+        let s2 = graph.add_node("s2");
+        let top = graph.add_node("top");
+        for (u, v) in [(p, s1), (p, s2), (q, s1), (q, s2), (top, p), (top, q)] {
+            graph.add_edge(u, v).unwrap();
+        }
+        assert_eq!(lca(&graph, s1, s2), vec![p, q]);
+
+        // Tying p and q into a cycle collapses them to one component
+        graph.add_edge(q, p).unwrap();
+        graph.add_edge(p, q).unwrap();
+        assert_eq!(lca(&graph, s1, s2), vec![p]);
+    }
+
+    #[test]
+    fn test_transitive_reduction_keeps_cycles() {
+        // x -> y -> z -> x is a cycle with a chord x -> z; s feeds it twice
+        let mut graph = DiGraph::new();
+        let s = graph.add_node("s");
+        let x = graph.add_node("x");
+        let y = graph.add_node("y");
+        let z = graph.add_node("z");
+        let t = graph.add_node("t");
+        graph.add_edge(x, y).unwrap();
+        graph.add_edge(y, z).unwrap();
+        graph.add_edge(z, x).unwrap();
+        graph.add_edge(x, z).unwrap();
+        graph.add_edge(s, x).unwrap();
+        graph.add_edge(s, y).unwrap();
+        graph.add_edge(s, t).unwrap();
+
+        // s -> x and s -> y join the same pair of components: both stay
+        let result = transitive_reduction(&graph);
+        assert!(result.redundant.is_empty());
+        assert_eq!(result.intra_scc_edges, 4);
+
+        // Once s -> t -> z also reaches the cycle, both are implied
+        graph.add_edge(t, z).unwrap();
+        let result = transitive_reduction(&graph);
+        assert_eq!(result.redundant, vec![(s, x), (s, y)]);
+        assert_eq!(result.intra_scc_edges, 4);
+
+        result.apply(&mut graph);
+        assert_eq!(graph.edge_count(), 6);
+        for (u, v) in [(x, y), (y, z), (z, x), (x, z)] {
+            assert!(graph.has_edge(u, v));
+        }
+    }
+
+    #[test]
+    fn test_ancestors_descendants_diamond() {
+        // a -> b -> d, a -> c -> d, d -> e
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(d, e).unwrap();
+
+        assert_eq!(ancestors(&graph, d), vec![a, b, c]);
+        assert_eq!(descendants(&graph, d), vec![e]);
+        assert_eq!(descendants(&graph, a), vec![b, c, d, e]);
+        assert!(ancestors(&graph, a).is_empty());
+        assert!(descendants(&graph, 99).is_empty());
+    }
+
+    #[test]
+    fn test_ancestors_descendants_cycle_and_isolated() {
+        // s -> x -> y -> z -> x, z -> t; lone is isolated
+        let mut graph = DiGraph::new();
+        let s = graph.add_node("s");
+        let x = graph.add_node("x");
+        let y = graph.add_node("y");
+        let z = graph.add_node("z");
+        let t = graph.add_node("t");
+        let lone = graph.add_node("lone");
+        graph.add_edge(s, x).unwrap();
+        graph.add_edge(x, y).unwrap();
+        graph.add_edge(y, z).unwrap();
+        graph.add_edge(z, x).unwrap();
+        graph.add_edge(z, t).unwrap();
+
+        assert_eq!(ancestors(&graph, y), vec![s, x, z]);
+        assert_eq!(descendants(&graph, y), vec![x, z, t]);
+        assert!(ancestors(&graph, lone).is_empty());
+        assert!(descendants(&graph, lone).is_empty());
+    }
+
+    #[test]
+    fn test_unreachable_from_sources() {
+        // a -> b -> c is fully reachable
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        assert!(unreachable_from_sources(&graph).is_empty());
+
+        // x <-> y has no way in; z hangs off it, w only has a self-loop
+        let x = graph.add_node("x");
+        let y = graph.add_node("y");
+        let z = graph.add_node("z");
+        let w = graph.add_node("w");
+        let gone = graph.add_node("gone");
+        graph.add_edge(x, y).unwrap();
+        graph.add_edge(y, x).unwrap();
+        graph.add_edge(y, z).unwrap();
+        graph.add_edge(w, w).unwrap();
+        graph.add_edge(gone, x).unwrap();
+        graph.remove_node(gone);
+        assert_eq!(unreachable_from_sources(&graph), vec![x, y, z, w]);
+
+        // An entry edge from the chain makes the cycle reachable
+        graph.add_edge(c, x).unwrap();
+        assert_eq!(unreachable_from_sources(&graph), vec![w]);
+    }
+}
@@ -0,0 +1,125 @@
+//! Neighborhood similarity between nodes.
+//!
+//! Two beads that depend on and are depended on by the same things are
+//! probably related, even if nobody linked them. The Jaccard index of their
+//! neighbor sets measures that overlap, and drives "you might want to link"
+//! suggestions.
+
+use crate::graph::DiGraph;
+
+/// |a ∩ b| / |a ∪ b| for sorted sets, 0.0 when both are empty.
+fn jaccard(a: &[usize], b: &[usize]) -> f64 {
+    let (mut i, mut j, mut common) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                common += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    let union = a.len() + b.len() - common;
+    if union == 0 {
+        0.0
+    } else {
+        common as f64 / union as f64
+    }
+}
+
+/// Jaccard similarity of the neighbor sets of each pair, in pair order.
+///
+/// A node's neighbor set is its predecessors and successors together,
+/// ignoring direction and self-loops. A pair where both sets are empty
+/// scores 0.0, as does any pair with a removed or out-of-range node.
+pub fn jaccard_similarity(graph: &DiGraph, pairs: &[(usize, usize)]) -> Vec<f64> {
+    pairs
+        .iter()
+        .map(|&(u, v)| jaccard(&graph.undirected_neighbors(u), &graph.undirected_neighbors(v)))
+        .collect()
+}
+
+/// The `k` nodes most similar to `node` by `jaccard_similarity`.
+///
+/// Only nodes two hops away (sharing at least one neighbor with `node`)
+/// are scored, since every other node scores 0.0; this keeps the cost to
+/// the size of the 2-hop neighborhood instead of the whole graph. Direct
+/// neighbors are included when they also share a neighbor. Sorted by
+/// descending score, ties by ascending index.
+pub fn top_similar(graph: &DiGraph, node: usize, k: usize) -> Vec<(usize, f64)> {
+    let own = graph.undirected_neighbors(node);
+    let mut candidates: Vec<usize> = own
+        .iter()
+        .flat_map(|&u| graph.out_neighbors(u).chain(graph.in_neighbors(u)))
+        .filter(|&c| c != node)
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    let mut ranked: Vec<(usize, f64)> = candidates
+        .into_iter()
+        .map(|c| (c, jaccard(&own, &graph.undirected_neighbors(c))))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(k);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph_with(n: usize, edges: &[(usize, usize)]) -> DiGraph {
+        let mut graph = DiGraph::new();
+        for i in 0..n {
+            graph.add_node(&format!("n{}", i));
+        }
+        for &(u, v) in edges {
+            graph.add_edge(u, v).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_jaccard_identical_disjoint_and_empty() {
+        // 0 and 1 both depend on 2 and are blocked by 3; 4 -> 5 is separate
+        let graph = graph_with(7, &[(0, 2), (1, 2), (3, 0), (3, 1), (4, 5), (0, 0)]);
+        let scores = jaccard_similarity(&graph, &[(0, 1), (0, 4), (6, 6), (0, 99)]);
+        assert_eq!(scores, vec![1.0, 0.0, 0.0, 0.0]);
+
+        // {2, 3} vs {0, 1}: nothing in common
+        assert_eq!(jaccard_similarity(&graph, &[(0, 2)]), vec![0.0]);
+        assert!(jaccard_similarity(&graph, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_top_similar_ranking() {
+        // Node 0 has neighbors {4, 5, 6}. Node 1 shares all three (1.0),
+        // node 2 shares {4, 5} and adds 7 (0.5), node 3 shares only 6 and
+        // adds 7 and 8 (0.2). Node 9 shares nothing and is never scored.
+        let graph = graph_with(
+            10,
+            &[
+                (0, 4),
+                (0, 5),
+                (6, 0),
+                (1, 4),
+                (1, 5),
+                (6, 1),
+                (2, 4),
+                (5, 2),
+                (2, 7),
+                (3, 6),
+                (3, 7),
+                (8, 3),
+                (9, 8),
+            ],
+        );
+        let top = top_similar(&graph, 0, 10);
+        assert_eq!(top, vec![(1, 1.0), (2, 0.5), (3, 0.2)]);
+        assert_eq!(top_similar(&graph, 0, 2), vec![(1, 1.0), (2, 0.5)]);
+        assert!(top_similar(&graph, 99, 3).is_empty());
+    }
+}
@@ -0,0 +1,457 @@
+//! Structural summary statistics for the viewer sidebar.
+
+use crate::algorithms::components::weakly_connected_components;
+use crate::algorithms::cycles::has_cycles;
+use crate::graph::DiGraph;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// In/out-degree summary of a graph.
+///
+/// Removed nodes are not counted. On an empty graph every field is zero
+/// and both histograms are empty.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DegreeStats {
+    pub node_count: usize,
+    pub min_in_degree: usize,
+    pub max_in_degree: usize,
+    pub avg_in_degree: f64,
+    pub min_out_degree: usize,
+    pub max_out_degree: usize,
+    pub avg_out_degree: f64,
+    /// Nodes with in-degree 0 (isolated nodes count as sources and sinks)
+    pub sources: usize,
+    /// Nodes with out-degree 0
+    pub sinks: usize,
+    /// `(in_degree, node count)` pairs, ascending by degree
+    pub in_histogram: Vec<(usize, usize)>,
+    /// `(out_degree, node count)` pairs, ascending by degree
+    pub out_histogram: Vec<(usize, usize)>,
+}
+
+/// Compute degree statistics in a single pass over the nodes.
+///
+/// A self-loop adds one to both the in- and out-degree of its node.
+pub fn degree_stats(graph: &DiGraph) -> DegreeStats {
+    let mut stats = DegreeStats {
+        node_count: 0,
+        min_in_degree: usize::MAX,
+        max_in_degree: 0,
+        avg_in_degree: 0.0,
+        min_out_degree: usize::MAX,
+        max_out_degree: 0,
+        avg_out_degree: 0.0,
+        sources: 0,
+        sinks: 0,
+        in_histogram: Vec::new(),
+        out_histogram: Vec::new(),
+    };
+    let mut in_counts: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut out_counts: BTreeMap<usize, usize> = BTreeMap::new();
+    let (mut in_total, mut out_total) = (0usize, 0usize);
+
+    for (v, (in_deg, out_deg)) in graph.degree_sequence().into_iter().enumerate() {
+        if graph.is_removed(v) {
+            continue;
+        }
+        stats.node_count += 1;
+        stats.min_in_degree = stats.min_in_degree.min(in_deg);
+        stats.max_in_degree = stats.max_in_degree.max(in_deg);
+        stats.min_out_degree = stats.min_out_degree.min(out_deg);
+        stats.max_out_degree = stats.max_out_degree.max(out_deg);
+        in_total += in_deg;
+        out_total += out_deg;
+        if in_deg == 0 {
+            stats.sources += 1;
+        }
+        if out_deg == 0 {
+            stats.sinks += 1;
+        }
+        *in_counts.entry(in_deg).or_default() += 1;
+        *out_counts.entry(out_deg).or_default() += 1;
+    }
+
+    if stats.node_count == 0 {
+        stats.min_in_degree = 0;
+        stats.min_out_degree = 0;
+        return stats;
+    }
+
+    stats.avg_in_degree = in_total as f64 / stats.node_count as f64;
+    stats.avg_out_degree = out_total as f64 / stats.node_count as f64;
+    stats.in_histogram = in_counts.into_iter().collect();
+    stats.out_histogram = out_counts.into_iter().collect();
+    stats
+}
+
+/// Degrees and source/sink role of one node, as returned by `node_roles`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct NodeRole {
+    pub in_deg: usize,
+    pub out_deg: usize,
+    /// No prerequisites other than itself
+    pub is_source: bool,
+    /// Blocks nothing other than itself
+    pub is_sink: bool,
+    /// No edges to or from any other node
+    pub is_isolated: bool,
+    pub has_self_loop: bool,
+}
+
+/// Degree and role of every node in one O(V + E) pass, in index order.
+///
+/// A self-loop adds one to both degrees, as in `degree_stats`, but is not
+/// a dependency on anything else: a node whose only edges are self-loops
+/// is a source, a sink and isolated. So a self-looped node keeps its role
+/// in the flow of work, and the loop shows up only in `has_self_loop` (and
+/// in cycle checks). Removed nodes get an all-zero, all-false entry.
+pub fn node_roles(graph: &DiGraph) -> Vec<NodeRole> {
+    (0..graph.len())
+        .map(|v| {
+            if graph.is_removed(v) {
+                return NodeRole::default();
+            }
+            let preds = graph.predecessors_slice(v);
+            let succs = graph.successors_slice(v);
+            let has_self_loop = succs.contains(&(v as u32));
+            let loops = usize::from(has_self_loop);
+            let is_source = preds.len() == loops;
+            let is_sink = succs.len() == loops;
+            NodeRole {
+                in_deg: preds.len(),
+                out_deg: succs.len(),
+                is_source,
+                is_sink,
+                is_isolated: is_source && is_sink,
+                has_self_loop,
+            }
+        })
+        .collect()
+}
+
+/// Local clustering coefficient of each node on the undirected projection.
+///
+/// A node's neighbors are the nodes it shares an edge with in either
+/// direction, self-loops aside. Its coefficient is the fraction of neighbor
+/// pairs that are themselves joined by an edge (again either direction).
+/// Nodes with fewer than two neighbors, and removed nodes, get 0.0.
+///
+/// Triangles are listed once each by orienting every undirected edge
+/// toward the endpoint of higher (degree, index) rank and intersecting
+/// oriented lists with a marker array, so a hub never scans the lists of
+/// its neighbors. Complexity: O(E·√E) over the undirected edges.
+pub fn clustering_coefficients(graph: &DiGraph) -> Vec<f64> {
+    let n = graph.len();
+    let neighbors: Vec<Vec<usize>> = (0..n).map(|v| graph.undirected_neighbors(v)).collect();
+    let rank = |v: usize| (neighbors[v].len(), v);
+    let higher: Vec<Vec<usize>> = (0..n)
+        .map(|v| neighbors[v].iter().copied().filter(|&u| rank(u) > rank(v)).collect())
+        .collect();
+
+    let mut triangles = vec![0usize; n];
+    let mut marked = vec![usize::MAX; n];
+    for v in 0..n {
+        for &u in &higher[v] {
+            marked[u] = v;
+        }
+        for &u in &higher[v] {
+            for &w in &higher[u] {
+                if marked[w] == v {
+                    triangles[v] += 1;
+                    triangles[u] += 1;
+                    triangles[w] += 1;
+                }
+            }
+        }
+    }
+
+    (0..n)
+        .map(|v| {
+            let k = neighbors[v].len();
+            if k < 2 {
+                0.0
+            } else {
+                triangles[v] as f64 / (k * (k - 1) / 2) as f64
+            }
+        })
+        .collect()
+}
+
+/// Mean of `clustering_coefficients` over the live nodes (0.0 if none).
+pub fn average_clustering(graph: &DiGraph) -> f64 {
+    clustering_with_info(graph).average
+}
+
+/// Local clustering coefficients together with their mean.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClusteringResult {
+    /// Coefficient per node, as from `clustering_coefficients`
+    pub coefficients: Vec<f64>,
+    /// Mean over the live nodes, 0.0 if there are none
+    pub average: f64,
+}
+
+/// Run `clustering_coefficients` and average them in the same call.
+pub fn clustering_with_info(graph: &DiGraph) -> ClusteringResult {
+    let coefficients = clustering_coefficients(graph);
+    let live = graph.active_len();
+    let average = if live == 0 {
+        0.0
+    } else {
+        coefficients.iter().sum::<f64>() / live as f64
+    };
+    ClusteringResult {
+        coefficients,
+        average,
+    }
+}
+
+/// Overall shape of a dependency graph, for the overview badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum GraphClass {
+    /// No live nodes
+    Empty,
+    /// Acyclic, and every weak component is a tree
+    Forest,
+    /// Connected and acyclic with exactly n - 1 edges
+    Tree,
+    /// Acyclic, but some pair of nodes is joined by two undirected routes
+    Dag,
+    /// Contains a directed cycle (including a self-loop)
+    Cyclic,
+}
+
+/// Classify the graph as empty, a tree, a forest, a DAG or cyclic.
+///
+/// Edge direction only matters for the cycle check. An acyclic graph has no
+/// self-loops or antiparallel pairs, so its undirected edge count is
+/// `edge_count`, and it is a forest exactly when that equals the node count
+/// minus the number of weak components. A single node is a tree.
+pub fn classify(graph: &DiGraph) -> GraphClass {
+    let n = graph.active_len();
+    if n == 0 {
+        return GraphClass::Empty;
+    }
+    if has_cycles(graph) {
+        return GraphClass::Cyclic;
+    }
+    let components = weakly_connected_components(graph).count;
+    if graph.edge_count() != n - components {
+        GraphClass::Dag
+    } else if components == 1 {
+        GraphClass::Tree
+    } else {
+        GraphClass::Forest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degree_stats_empty() {
+        let stats = degree_stats(&DiGraph::new());
+        assert_eq!(stats.node_count, 0);
+        assert_eq!(stats.min_in_degree, 0);
+        assert_eq!(stats.max_out_degree, 0);
+        assert_eq!(stats.avg_in_degree, 0.0);
+        assert!(stats.in_histogram.is_empty());
+        assert!(stats.out_histogram.is_empty());
+    }
+
+    #[test]
+    fn test_degree_stats_small_graph() {
+        // a -> b, a -> c, b -> c, c -> c (self-loop), d isolated
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_node("d");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, c).unwrap();
+
+        let stats = degree_stats(&graph);
+        assert_eq!(stats.node_count, 4);
+        assert_eq!((stats.min_in_degree, stats.max_in_degree), (0, 3));
+        assert_eq!((stats.min_out_degree, stats.max_out_degree), (0, 2));
+        assert_eq!(stats.avg_in_degree, 1.0);
+        assert_eq!(stats.avg_out_degree, 1.0);
+        assert_eq!(stats.sources, 2); // a and d
+        assert_eq!(stats.sinks, 1); // d (c has its self-loop)
+        assert_eq!(stats.in_histogram, vec![(0, 2), (1, 1), (3, 1)]);
+        assert_eq!(stats.out_histogram, vec![(0, 1), (1, 2), (2, 1)]);
+
+        for histogram in [&stats.in_histogram, &stats.out_histogram] {
+            let total: usize = histogram.iter().map(|&(_, count)| count).sum();
+            assert_eq!(total, stats.node_count);
+        }
+    }
+
+    #[test]
+    fn test_degree_stats_skips_removed() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.add_edge(a, b).unwrap();
+        graph.remove_node(b);
+
+        let stats = degree_stats(&graph);
+        assert_eq!(stats.node_count, 1);
+        assert_eq!(stats.in_histogram, vec![(0, 1)]);
+        assert_eq!(stats.sinks, 1);
+    }
+
+    #[test]
+    fn test_node_roles_every_role() {
+        // a -> b -> c, d isolated, e only loops on itself, f loops and feeds b,
+        // g removed
+        let mut graph = DiGraph::new();
+        let [a, b, c, d, e, f, g] =
+            ["a", "b", "c", "d", "e", "f", "g"].map(|id| graph.add_node(id));
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(e, e).unwrap();
+        graph.add_edge(f, f).unwrap();
+        graph.add_edge(f, b).unwrap();
+        graph.add_edge(g, a).unwrap();
+        graph.remove_node(g);
+
+        let roles = node_roles(&graph);
+        let role = |in_deg, out_deg, is_source, is_sink, has_self_loop| NodeRole {
+            in_deg,
+            out_deg,
+            is_source,
+            is_sink,
+            is_isolated: is_source && is_sink,
+            has_self_loop,
+        };
+        assert_eq!(roles[a], role(0, 1, true, false, false));
+        assert_eq!(roles[b], role(2, 1, false, false, false));
+        assert_eq!(roles[c], role(1, 0, false, true, false));
+        assert_eq!(roles[d], role(0, 0, true, true, false));
+        // Self-loops count toward both degrees but don't change the role
+        assert_eq!(roles[e], role(1, 1, true, true, true));
+        assert!(roles[e].is_isolated);
+        assert_eq!(roles[f], role(1, 2, true, false, true));
+        assert_eq!(roles[g], NodeRole::default());
+    }
+
+    #[test]
+    fn test_classify_each_variant() {
+        let mut graph = DiGraph::new();
+        assert_eq!(classify(&graph), GraphClass::Empty);
+
+        // A lone node, then a -> b, a -> c: trees regardless of direction
+        let a = graph.add_node("a");
+        assert_eq!(classify(&graph), GraphClass::Tree);
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(c, a).unwrap();
+        assert_eq!(classify(&graph), GraphClass::Tree);
+
+        // A second island makes it a forest
+        let d = graph.add_node("d");
+        assert_eq!(classify(&graph), GraphClass::Forest);
+
+        // b -> d and c -> d close an undirected loop: a DAG
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
+        assert_eq!(classify(&graph), GraphClass::Dag);
+
+        graph.add_edge(d, c).unwrap();
+        assert_eq!(classify(&graph), GraphClass::Cyclic);
+
+        // Removing d leaves the original tree
+        graph.remove_node(d);
+        assert_eq!(classify(&graph), GraphClass::Tree);
+    }
+
+    #[test]
+    fn test_clustering_triangle_and_star() {
+        // Triangle a -> b -> c -> a (b -> a too), plus a star hub -> l1..l3
+        // where a is also a leaf of the hub
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        let hub = graph.add_node("hub");
+        for i in 0..3 {
+            let leaf = graph.add_node(&format!("l{}", i));
+            graph.add_edge(hub, leaf).unwrap();
+        }
+        graph.add_edge(hub, a).unwrap();
+
+        let cc = clustering_coefficients(&graph);
+        assert_eq!(cc[b], 1.0);
+        assert_eq!(cc[c], 1.0);
+        assert_eq!(cc[hub], 0.0);
+        assert_eq!(cc[hub + 1], 0.0);
+        // a's neighbors b, c, hub: only b-c of the three pairs is linked
+        assert!((cc[a] - 1.0 / 3.0).abs() < 1e-12);
+
+        let expected = (1.0 / 3.0 + 2.0) / 7.0;
+        assert!((average_clustering(&graph) - expected).abs() < 1e-12);
+        assert_eq!(average_clustering(&DiGraph::new()), 0.0);
+
+        let info = clustering_with_info(&graph);
+        assert_eq!(info.coefficients, cc);
+        assert!((info.average - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_clustering_pure_triangle() {
+        let mut graph = DiGraph::new();
+        for id in ["x", "y", "z"] {
+            graph.add_node(id);
+        }
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        graph.add_edge(0, 2).unwrap();
+        let info = clustering_with_info(&graph);
+        assert_eq!(info.coefficients, vec![1.0; 3]);
+        assert_eq!(info.average, 1.0);
+    }
+
+    #[test]
+    fn test_clustering_matches_pair_count() {
+        // Random graph with hubs; compare against checking every neighbor pair
+        let n = 60;
+        let mut graph = DiGraph::new();
+        for i in 0..n {
+            graph.add_node(&format!("n{}", i));
+        }
+        let mut state = 11u64;
+        for _ in 0..400 {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            let u = (state >> 33) as usize % n;
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            // Low indices are picked far more often, so they become hubs
+            let v = (state >> 33) as usize % (1 + u % 7 * 8);
+            graph.add_edge(u, v).unwrap();
+        }
+        graph.remove_node(5);
+
+        let cc = clustering_coefficients(&graph);
+        for (v, &c) in cc.iter().enumerate() {
+            let ns = graph.undirected_neighbors(v);
+            let k = ns.len();
+            let links = ns
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &a)| ns[i + 1..].iter().map(move |&b| (a, b)))
+                .filter(|&(a, b)| graph.has_edge(a, b) || graph.has_edge(b, a))
+                .count();
+            let expected = if k < 2 { 0.0 } else { links as f64 / (k * (k - 1) / 2) as f64 };
+            assert!((c - expected).abs() < 1e-12, "node {}: {} vs {}", v, c, expected);
+        }
+        assert!(cc.iter().any(|&c| c > 0.0));
+    }
+}
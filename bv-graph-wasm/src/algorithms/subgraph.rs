@@ -4,8 +4,22 @@
 //! Essential for filtered-view analysis where you want to run algorithms on
 //! a subset of issues (e.g., "PageRank for just 'auth' label issues").
 
+use crate::csr::Adjacency;
 use crate::graph::DiGraph;
 use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// Which edges a reachability search follows.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Outgoing edges (descendants)
+    Forward,
+    /// Incoming edges (ancestors)
+    Backward,
+    /// Both, i.e. ancestors and descendants of the roots
+    Both,
+}
 
 /// Extract a subgraph containing only the specified node indices.
 ///
@@ -21,37 +35,63 @@ use std::collections::HashMap;
 /// New DiGraph containing only the specified nodes and their interconnecting edges.
 /// Node indices in the new graph are renumbered 0..n.
 pub fn extract_subgraph(graph: &DiGraph, node_indices: &[usize]) -> DiGraph {
-    let n = graph.len();
-    if node_indices.is_empty() || n == 0 {
-        return DiGraph::new();
-    }
+    induced_subgraph(graph, node_indices).0
+}
 
+/// Extract the induced subgraph over `node_indices`, with an index mapping.
+///
+/// Nodes are numbered in first-occurrence order of `node_indices`;
+/// duplicates are collapsed and out-of-range or removed indices skipped.
+/// Node attributes and edge weights/kinds are copied.
+///
+/// # Returns
+/// `(subgraph, mapping)` where `mapping[new_index]` is the original index,
+/// so results computed on the subgraph can be translated back.
+pub fn induced_subgraph(graph: &DiGraph, node_indices: &[usize]) -> (DiGraph, Vec<usize>) {
     // Create mapping: old index -> new index
     let mut index_map: HashMap<usize, usize> = HashMap::with_capacity(node_indices.len());
+    let mut mapping: Vec<usize> = Vec::with_capacity(node_indices.len());
     let mut new_graph = DiGraph::with_capacity(node_indices.len(), node_indices.len() * 2);
 
     // Add nodes to new graph
     for &old_idx in node_indices {
-        if old_idx < n {
-            if let Some(id) = graph.node_id(old_idx) {
-                let new_idx = new_graph.add_node(&id);
-                index_map.insert(old_idx, new_idx);
+        if index_map.contains_key(&old_idx) {
+            continue;
+        }
+        if let Some(id) = graph.label(old_idx) {
+            let new_idx = new_graph.add_node(id);
+            index_map.insert(old_idx, new_idx);
+            mapping.push(old_idx);
+            for (key, value) in graph.node_attrs(old_idx).into_iter().flatten() {
+                new_graph.set_node_attr(new_idx, key, value.clone());
             }
         }
     }
 
     // Add edges between retained nodes
-    for &old_from in node_indices {
-        if let Some(&new_from) = index_map.get(&old_from) {
-            for &old_to in graph.successors_slice(old_from) {
-                if let Some(&new_to) = index_map.get(&old_to) {
-                    new_graph.add_edge(new_from, new_to);
+    let weighted = graph.is_weighted();
+    let kinded = graph.has_edge_kinds();
+    for (new_from, &old_from) in mapping.iter().enumerate() {
+        for (old_to, weight) in graph.weighted_successors(old_from) {
+            if let Some(&new_to) = index_map.get(&old_to) {
+                let added = if weighted {
+                    new_graph.add_weighted_edge(new_from, new_to, weight)
+                } else {
+                    new_graph.add_edge(new_from, new_to)
+                };
+                added.expect("both endpoints were copied");
+                if kinded {
+                    if let Some(kind) = graph.edge_kind(old_from, old_to) {
+                        new_graph
+                            .add_edge_kind(new_from, new_to, kind.0)
+                            .expect("kind came from a stored edge");
+                    }
                 }
             }
         }
     }
 
-    new_graph
+    (new_graph, mapping)
 }
 
 /// Extract a subgraph by node IDs (string lookup).
@@ -71,11 +111,65 @@ pub fn reachable_subgraph_from(graph: &DiGraph, source: usize) -> DiGraph {
     extract_subgraph(graph, &reachable)
 }
 
+/// Induced subgraph over everything reachable from `roots`, with an index mapping.
+///
+/// Multi-source BFS in the given direction; `Both` takes the union of the
+/// forward and backward closures (not alternating paths). Roots are always
+/// included; out-of-range or removed roots are skipped. Nodes keep their
+/// relative order, and the mapping is as for `induced_subgraph`.
+pub fn reachable_subgraph(
+    graph: &DiGraph,
+    roots: &[usize],
+    direction: Direction,
+) -> (DiGraph, Vec<usize>) {
+    let n = graph.len();
+    let mut reached = vec![false; n];
+    let roots: Vec<usize> = roots
+        .iter()
+        .copied()
+        .filter(|&r| r < n && !graph.is_removed(r))
+        .collect();
+
+    let passes: &[bool] = match direction {
+        Direction::Forward => &[true],
+        Direction::Backward => &[false],
+        Direction::Both => &[true, false],
+    };
+    for &forward in passes {
+        let mut visited = vec![false; n];
+        let mut queue = std::collections::VecDeque::new();
+        for &r in &roots {
+            if !visited[r] {
+                visited[r] = true;
+                queue.push_back(r);
+            }
+        }
+
+        while let Some(v) = queue.pop_front() {
+            reached[v] = true;
+            let next = if forward {
+                graph.successors_slice(v)
+            } else {
+                graph.predecessors_slice(v)
+            };
+            for w in next.iter().map(|&w| w as usize) {
+                if !visited[w] {
+                    visited[w] = true;
+                    queue.push_back(w);
+                }
+            }
+        }
+    }
+
+    let nodes: Vec<usize> = (0..n).filter(|&v| reached[v]).collect();
+    induced_subgraph(graph, &nodes)
+}
+
 /// Get nodes reachable from a source node (outgoing direction).
 ///
 /// Uses BFS to find all nodes that can be reached by following
-/// outgoing edges from the source.
-pub fn reachable_from(graph: &DiGraph, source: usize) -> Vec<usize> {
+/// outgoing edges from the source. Works on a frozen `CsrGraph` too.
+pub fn reachable_from<G: Adjacency>(graph: &G, source: usize) -> Vec<usize> {
     let n = graph.len();
     if source >= n {
         return Vec::new();
@@ -90,7 +184,8 @@ pub fn reachable_from(graph: &DiGraph, source: usize) -> Vec<usize> {
     queue.push_back(source);
 
     while let Some(v) = queue.pop_front() {
-        for &w in graph.successors_slice(v) {
+        for &w in graph.successors(v) {
+            let w = w as usize;
             if !visited[w] {
                 visited[w] = true;
                 result.push(w);
@@ -121,7 +216,7 @@ pub fn reachable_to(graph: &DiGraph, target: usize) -> Vec<usize> {
     queue.push_back(target);
 
     while let Some(v) = queue.pop_front() {
-        for &w in graph.predecessors_slice(v) {
+        for w in graph.in_neighbors(v) {
             if !visited[w] {
                 visited[w] = true;
                 result.push(w);
@@ -133,6 +228,37 @@ pub fn reachable_to(graph: &DiGraph, target: usize) -> Vec<usize> {
     result
 }
 
+/// Check whether `target` is reachable from `source` (a node reaches itself).
+///
+/// BFS like `reachable_from`, but stops as soon as `target` is found.
+pub fn can_reach<G: Adjacency>(graph: &G, source: usize, target: usize) -> bool {
+    let n = graph.len();
+    if source >= n || target >= n {
+        return false;
+    }
+
+    let mut visited = vec![false; n];
+    let mut queue = std::collections::VecDeque::new();
+
+    visited[source] = true;
+    queue.push_back(source);
+
+    while let Some(v) = queue.pop_front() {
+        if v == target {
+            return true;
+        }
+        for &w in graph.successors(v) {
+            let w = w as usize;
+            if !visited[w] {
+                visited[w] = true;
+                queue.push_back(w);
+            }
+        }
+    }
+
+    false
+}
+
 /// Get nodes in the dependency cone of a target (all ancestors + target + all descendants).
 pub fn dependency_cone(graph: &DiGraph, node: usize) -> Vec<usize> {
     let from = reachable_to(graph, node);
@@ -180,9 +306,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let sub = extract_subgraph(&graph, &[b, c]);
         assert_eq!(sub.node_count(), 2);
@@ -197,8 +323,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let sub = extract_subgraph(&graph, &[a, c]);
         assert_eq!(sub.node_count(), 2);
@@ -217,10 +343,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         // Full subgraph should preserve structure
         let sub = extract_subgraph(&graph, &[a, b, c, d]);
@@ -239,8 +365,8 @@ mod tests {
         graph.add_node("bv-1");
         graph.add_node("bv-2");
         graph.add_node("bv-3");
-        graph.add_edge(0, 1);
-        graph.add_edge(1, 2);
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
 
         let sub = extract_subgraph_by_ids(&graph, &["bv-1", "bv-2"]);
         assert_eq!(sub.node_count(), 2);
@@ -258,9 +384,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(b, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(b, d).unwrap();
 
         let from_a = reachable_from(&graph, a);
         assert_eq!(from_a.len(), 4); // a, b, c, d
@@ -282,8 +408,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let to_c = reachable_to(&graph, c);
         assert_eq!(to_c.len(), 3); // c, b, a
@@ -307,14 +433,68 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(b, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(b, d).unwrap();
 
         let cone = dependency_cone(&graph, b);
         assert_eq!(cone.len(), 4); // a, b, c, d
     }
 
+    #[test]
+    fn test_reachable_subgraph_directions() {
+        // Diamond a -> b, a -> c, b -> d, c -> d
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(e, b).unwrap();
+
+        let (sub, mapping) = reachable_subgraph(&graph, &[a], Direction::Forward);
+        assert_eq!(mapping, vec![a, b, c, d]);
+        assert_eq!(sub.edge_count(), 4);
+
+        // Ancestors of the sink only
+        let (_, mapping) = reachable_subgraph(&graph, &[b, 99], Direction::Backward);
+        assert_eq!(mapping, vec![a, b, e]);
+
+        let (_, mapping) = reachable_subgraph(&graph, &[c], Direction::Both);
+        assert_eq!(mapping, vec![a, c, d]);
+
+        let (sub, mapping) = reachable_subgraph(&graph, &[99], Direction::Forward);
+        assert!(mapping.is_empty());
+        assert_eq!(sub.node_count(), 0);
+    }
+
+    #[test]
+    fn test_reachable_subgraph_cycle_root() {
+        // x -> a -> b -> c -> a -> y: a root inside the cycle pulls in the SCC
+        let mut graph = DiGraph::new();
+        let x = graph.add_node("x");
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let y = graph.add_node("y");
+        graph.add_edge(x, a).unwrap();
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(a, y).unwrap();
+
+        let (sub, mapping) = reachable_subgraph(&graph, &[b], Direction::Forward);
+        assert_eq!(mapping, vec![a, b, c, y]);
+        assert!(sub.has_cycles());
+
+        let (_, mapping) = reachable_subgraph(&graph, &[b], Direction::Backward);
+        assert_eq!(mapping, vec![x, a, b, c]);
+    }
+
     #[test]
     fn test_reachable_subgraph() {
         // a -> b -> c
@@ -328,9 +508,9 @@ mod tests {
         let c = graph.add_node("c");
         let d = graph.add_node("d");
         graph.add_node("e");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(b, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(b, d).unwrap();
 
         let sub = reachable_subgraph_from(&graph, a);
         assert_eq!(sub.node_count(), 4); // a, b, c, d (not e)
@@ -344,8 +524,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let sub = extract_subgraph(&graph, &[a, b, c]);
 
@@ -375,7 +555,7 @@ mod tests {
         let mut graph = DiGraph::new();
         graph.add_node("a");
         graph.add_node("b");
-        graph.add_edge(0, 1);
+        graph.add_edge(0, 1).unwrap();
 
         // Duplicate indices should be handled
         let sub = extract_subgraph(&graph, &[0, 0, 1, 1]);
@@ -383,4 +563,45 @@ mod tests {
         assert_eq!(sub.node_count(), 2);
         assert_eq!(sub.edge_count(), 1);
     }
+
+    #[test]
+    fn test_induced_subgraph_one_scc() {
+        use crate::algorithms::cycles::tarjan_scc;
+        // SCC {a, b, c} -> SCC {d, e}
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_weighted_edge(a, b, 3.0).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(c, d).unwrap();
+        graph.add_edge(d, e).unwrap();
+        graph.add_edge(e, d).unwrap();
+        graph.set_node_attr(e, "status", serde_json::json!("open"));
+
+        let (sub, mapping) = induced_subgraph(&graph, &[e, d, d, 42]);
+        assert_eq!(mapping, vec![e, d]);
+        assert_eq!(sub.node_count(), 2);
+        assert_eq!(sub.edge_count(), 2);
+        assert_eq!(sub.attr_str(0, "status"), Some("open"));
+
+        let (sub, mapping) = induced_subgraph(&graph, &[a, b, c]);
+        assert_eq!(sub.edge_count(), 3);
+        assert_eq!(sub.edge_weight(0, 1), Some(3.0));
+        assert_eq!(sub.edge_weight(1, 2), Some(1.0));
+
+        // Components translate back through the mapping
+        let scc = tarjan_scc(&sub);
+        assert_eq!(scc.components.len(), 1);
+        let mut original: Vec<usize> = scc.components[0].iter().map(|&v| mapping[v]).collect();
+        original.sort_unstable();
+        assert_eq!(original, vec![a, b, c]);
+
+        let (empty, mapping) = induced_subgraph(&graph, &[]);
+        assert_eq!(empty.node_count(), 0);
+        assert!(mapping.is_empty());
+    }
 }
@@ -138,7 +138,7 @@ mod tests {
             g.add_node(&format!("n{}", i));
         }
         for (from, to) in edges {
-            g.add_edge(*from, *to);
+            g.add_edge(*from, *to).unwrap();
         }
         g
     }
@@ -3,9 +3,12 @@
 //! Orders nodes such that for every edge u→v, u comes before v.
 //! Essential for execution planning and critical path analysis.
 
+use crate::algorithms::cycles::{tarjan_scc, SCCResult};
 use crate::graph::DiGraph;
+use serde::Serialize;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
+use std::fmt;
 
 /// Topological sort result.
 pub struct TopoSortResult {
@@ -15,21 +18,40 @@ pub struct TopoSortResult {
     pub is_dag: bool,
 }
 
+/// Error from `topological_sort` when the graph is not a DAG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// Members of one cyclic strongly connected component, ascending.
+    /// It is the cyclic component with the smallest node index.
+    pub nodes: Vec<usize>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains a cycle through nodes {:?}", self.nodes)
+    }
+}
+
+impl std::error::Error for CycleError {}
+
 /// Topological sort using Kahn's algorithm with deterministic ordering.
 ///
-/// Uses a min-heap to ensure consistent output across runs.
-/// Returns None if the graph contains cycles.
+/// Uses a min-heap so that, among nodes that are ready, the lowest index
+/// always comes first; the output is identical across runs.
 ///
 /// # Arguments
 /// * `graph` - The directed graph to sort
 ///
 /// # Returns
-/// * `Some(order)` - Vector of node indices in topological order
-/// * `None` - If the graph contains cycles
-pub fn topological_sort(graph: &DiGraph) -> Option<Vec<usize>> {
+/// * `Ok(order)` - Live node indices in topological order
+///
+/// # Errors
+/// If the graph contains a cycle, returns a `CycleError` naming the members
+/// of one offending strongly connected component.
+pub fn topological_sort(graph: &DiGraph) -> Result<Vec<usize>, CycleError> {
     let n = graph.len();
     if n == 0 {
-        return Some(Vec::new());
+        return Ok(Vec::new());
     }
 
     // Compute in-degrees
@@ -37,7 +59,7 @@ pub fn topological_sort(graph: &DiGraph) -> Option<Vec<usize>> {
 
     // Min-heap for deterministic ordering (process lowest index first)
     let mut heap: BinaryHeap<Reverse<usize>> = (0..n)
-        .filter(|&i| in_degree[i] == 0)
+        .filter(|&i| in_degree[i] == 0 && !graph.is_removed(i))
         .map(Reverse)
         .collect();
 
@@ -46,7 +68,7 @@ pub fn topological_sort(graph: &DiGraph) -> Option<Vec<usize>> {
     while let Some(Reverse(u)) = heap.pop() {
         order.push(u);
 
-        for &v in graph.successors_slice(u) {
+        for v in graph.out_neighbors(u) {
             in_degree[v] -= 1;
             if in_degree[v] == 0 {
                 heap.push(Reverse(v));
@@ -54,25 +76,192 @@ pub fn topological_sort(graph: &DiGraph) -> Option<Vec<usize>> {
         }
     }
 
-    if order.len() == n {
-        Some(order)
+    if order.len() == graph.active_len() {
+        Ok(order)
     } else {
-        None // Cycle detected
+        Err(first_cycle(graph))
     }
 }
 
+/// The cyclic strongly connected component with the smallest node index.
+fn first_cycle(graph: &DiGraph) -> CycleError {
+    let nodes = tarjan_scc(graph)
+        .components
+        .into_iter()
+        .find(|c| c.len() > 1 || graph.has_edge(c[0], c[0]))
+        .unwrap_or_default();
+    CycleError { nodes }
+}
+
+/// SCC component ids in topological order of the condensation.
+///
+/// Position 0 is a component that depends on nothing, and every edge
+/// between components points to a later one, so positions work as project
+/// phases. This reverses `SCCResult::reverse_topological_order`, which Tarjan
+/// produces as it goes; no extra traversal is needed. `scc` must come from
+/// `tarjan_scc(graph)`: a masked decomposition is only ordered for its own
+/// subset of edges.
+pub fn scc_topological_order(graph: &DiGraph, scc: &SCCResult) -> Vec<usize> {
+    let order: Vec<usize> = scc.reverse_topological_order().iter().rev().copied().collect();
+    debug_assert!({
+        let mut position = vec![0; order.len()];
+        for (i, &c) in order.iter().enumerate() {
+            position[c] = i;
+        }
+        graph.edges().all(|(u, v)| {
+            let (a, b) = (scc.component_of(u), scc.component_of(v));
+            a == b || position[a] < position[b]
+        })
+    });
+    order
+}
+
 /// Check if the graph is a DAG (directed acyclic graph).
 ///
 /// A graph is a DAG if and only if it has a valid topological order.
 pub fn is_dag(graph: &DiGraph) -> bool {
-    topological_sort(graph).is_some()
+    topological_sort(graph).is_ok()
+}
+
+/// Assign each node to a layer for Sugiyama-style drawing.
+///
+/// A node's level is the length of the longest path reaching it from any
+/// source, so sources sit at level 0 and every edge points to a strictly
+/// deeper level. Removed nodes get level 0.
+///
+/// # Errors
+/// Returns an error if the graph contains a cycle.
+pub fn topological_levels(graph: &DiGraph) -> Result<Vec<usize>, &'static str> {
+    let order = topological_sort(graph).map_err(|_| "graph contains a cycle")?;
+    let mut levels = vec![0usize; graph.len()];
+    for u in order {
+        for v in graph.out_neighbors(u) {
+            levels[v] = levels[v].max(levels[u] + 1);
+        }
+    }
+    Ok(levels)
+}
+
+/// Phase number of each node that tolerates cycles.
+///
+/// Strongly connected components are collapsed into single nodes, and a
+/// node's depth is the longest path in that condensation DAG from any source
+/// component to its own component. Beads on a common cycle share a depth,
+/// and on a DAG the result equals `topological_levels`. Removed nodes get 0.
+pub fn blocking_depth(graph: &DiGraph) -> Vec<usize> {
+    let n = graph.len();
+    let scc = tarjan_scc(graph);
+    let comp_of: Vec<usize> = (0..n).map(|v| scc.component_of(v)).collect();
+
+    // Kahn's algorithm over the condensation, one entry per crossing edge
+    let k = scc.components.len();
+    let mut in_degree = vec![0usize; k];
+    for (u, v) in graph.edges() {
+        if comp_of[u] != comp_of[v] {
+            in_degree[comp_of[v]] += 1;
+        }
+    }
+    let mut depth = vec![0usize; k];
+    let mut ready: Vec<usize> = (0..k).filter(|&c| in_degree[c] == 0).collect();
+    while let Some(c) = ready.pop() {
+        for &u in &scc.components[c] {
+            for v in graph.out_neighbors(u) {
+                let d = comp_of[v];
+                if d == c {
+                    continue;
+                }
+                depth[d] = depth[d].max(depth[c] + 1);
+                in_degree[d] -= 1;
+                if in_degree[d] == 0 {
+                    ready.push(d);
+                }
+            }
+        }
+    }
+
+    comp_of
+        .iter()
+        .map(|&c| if c == usize::MAX { 0 } else { depth[c] })
+        .collect()
+}
+
+/// Nodes grouped into dependency layers ("what can run in parallel").
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Generations {
+    /// `layers[k]` holds the nodes of layer k, ascending
+    pub layers: Vec<Vec<usize>>,
+    /// Layer of each node index; `usize::MAX` for removed nodes
+    pub layer_of: Vec<usize>,
+}
+
+/// Group nodes into layers by repeatedly peeling off the nodes that have
+/// no remaining prerequisites.
+///
+/// Layer 0 holds nodes without incoming edges, and layer k holds nodes whose
+/// predecessors all sit in layers below k. Layers match `topological_levels`.
+///
+/// # Errors
+/// Returns a `CycleError` if the graph contains a cycle; see
+/// `topological_generations_lenient` for a version that always succeeds.
+pub fn topological_generations(graph: &DiGraph) -> Result<Generations, CycleError> {
+    let n = graph.len();
+    let mut in_degree: Vec<usize> = (0..n).map(|i| graph.in_degree(i)).collect();
+    let mut layer_of = vec![usize::MAX; n];
+    let mut layers: Vec<Vec<usize>> = Vec::new();
+    let mut placed = 0;
+
+    let mut current: Vec<usize> = (0..n)
+        .filter(|&i| in_degree[i] == 0 && !graph.is_removed(i))
+        .collect();
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        for &u in &current {
+            layer_of[u] = layers.len();
+            for v in graph.out_neighbors(u) {
+                in_degree[v] -= 1;
+                if in_degree[v] == 0 {
+                    next.push(v);
+                }
+            }
+        }
+        next.sort_unstable();
+        placed += current.len();
+        layers.push(std::mem::replace(&mut current, next));
+    }
+
+    if placed == graph.active_len() {
+        Ok(Generations { layers, layer_of })
+    } else {
+        Err(first_cycle(graph))
+    }
+}
+
+/// Dependency layers that tolerate cycles.
+///
+/// Each strongly connected component is layered as a single node of the
+/// condensation, so all members of a cycle share one layer (see
+/// `blocking_depth`). On a DAG this equals `topological_generations`.
+pub fn topological_generations_lenient(graph: &DiGraph) -> Generations {
+    let mut layer_of = blocking_depth(graph);
+    let mut layers: Vec<Vec<usize>> = Vec::new();
+    for (v, layer) in layer_of.iter_mut().enumerate() {
+        if graph.is_removed(v) {
+            *layer = usize::MAX;
+            continue;
+        }
+        if layers.len() <= *layer {
+            layers.resize_with(*layer + 1, Vec::new);
+        }
+        layers[*layer].push(v);
+    }
+    Generations { layers, layer_of }
 }
 
 /// Compute topological sort with detailed result.
 pub fn topological_sort_result(graph: &DiGraph) -> TopoSortResult {
     match topological_sort(graph) {
-        Some(order) => TopoSortResult { order, is_dag: true },
-        None => TopoSortResult {
+        Ok(order) => TopoSortResult { order, is_dag: true },
+        Err(_) => TopoSortResult {
             order: Vec::new(),
             is_dag: false,
         },
@@ -87,7 +276,7 @@ mod tests {
     fn test_empty_graph() {
         let g = DiGraph::new();
         let result = topological_sort(&g);
-        assert_eq!(result, Some(vec![]));
+        assert_eq!(result, Ok(vec![]));
     }
 
     #[test]
@@ -95,7 +284,7 @@ mod tests {
         let mut g = DiGraph::new();
         g.add_node("a");
         let result = topological_sort(&g);
-        assert_eq!(result, Some(vec![0]));
+        assert_eq!(result, Ok(vec![0]));
     }
 
     #[test]
@@ -105,8 +294,8 @@ mod tests {
         let a = g.add_node("a");
         let b = g.add_node("b");
         let c = g.add_node("c");
-        g.add_edge(a, b);
-        g.add_edge(b, c);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
 
         let result = topological_sort(&g).unwrap();
         assert_eq!(result, vec![0, 1, 2]); // a, b, c
@@ -124,10 +313,10 @@ mod tests {
         let b = g.add_node("b");
         let c = g.add_node("c");
         let d = g.add_node("d");
-        g.add_edge(a, b);
-        g.add_edge(a, c);
-        g.add_edge(b, d);
-        g.add_edge(c, d);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(a, c).unwrap();
+        g.add_edge(b, d).unwrap();
+        g.add_edge(c, d).unwrap();
 
         let result = topological_sort(&g).unwrap();
         // Valid orders: [a, b, c, d] or [a, c, b, d]
@@ -145,12 +334,37 @@ mod tests {
         let a = g.add_node("a");
         let b = g.add_node("b");
         let c = g.add_node("c");
-        g.add_edge(a, b);
-        g.add_edge(b, c);
-        g.add_edge(c, a);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(c, a).unwrap();
 
-        let result = topological_sort(&g);
-        assert!(result.is_none());
+        let err = topological_sort(&g).unwrap_err();
+        assert_eq!(err.nodes, vec![a, b, c]);
+    }
+
+    #[test]
+    fn test_cycle_error_names_only_cycle_members() {
+        // x -> a -> b -> c -> b, c -> y: only b and c form the cycle
+        let mut g = DiGraph::new();
+        let x = g.add_node("x");
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let y = g.add_node("y");
+        g.add_edge(x, a).unwrap();
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(c, b).unwrap();
+        g.add_edge(c, y).unwrap();
+
+        let err = topological_sort(&g).unwrap_err();
+        assert_eq!(err.nodes, vec![b, c]);
+        assert_eq!(err.to_string(), "graph contains a cycle through nodes [2, 3]");
+
+        g.remove_edge(c, b);
+        let order = topological_sort(&g).unwrap();
+        let pos = |v: usize| order.iter().position(|&x| x == v).unwrap();
+        assert!(g.edges().all(|(u, v)| pos(u) < pos(v)));
     }
 
     #[test]
@@ -158,10 +372,10 @@ mod tests {
         // a -> a
         let mut g = DiGraph::new();
         let a = g.add_node("a");
-        g.add_edge(a, a);
+        g.add_edge(a, a).unwrap();
 
         let result = topological_sort(&g);
-        assert!(result.is_none());
+        assert_eq!(result, Err(CycleError { nodes: vec![a] }));
     }
 
     #[test]
@@ -172,8 +386,8 @@ mod tests {
         let b = g.add_node("b");
         let c = g.add_node("c");
         let d = g.add_node("d");
-        g.add_edge(a, b);
-        g.add_edge(c, d);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(c, d).unwrap();
 
         let result = topological_sort(&g).unwrap();
         // With min-heap: [0, 2, 1, 3] - process by index
@@ -189,19 +403,157 @@ mod tests {
         assert!(c_pos < d_pos);
     }
 
+    #[test]
+    fn test_removed_nodes_excluded() {
+        // a -> b -> c with b removed: a and c are unordered live nodes
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.remove_node(b);
+
+        assert_eq!(topological_sort(&g), Ok(vec![a, c]));
+    }
+
+    #[test]
+    fn test_topological_levels_diamond() {
+        // a -> b -> c -> d and a -> d: d sits below the longer branch
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        g.add_node("e");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(c, d).unwrap();
+        g.add_edge(a, d).unwrap();
+
+        assert_eq!(topological_levels(&g), Ok(vec![0, 1, 2, 3, 0]));
+
+        g.add_edge(d, a).unwrap();
+        assert!(topological_levels(&g).is_err());
+    }
+
+    #[test]
+    fn test_blocking_depth_cycle_feeds_chain() {
+        // a -> b -> c -> a, then c -> d -> e; x -> e and x -> y -> x
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        let e = g.add_node("e");
+        let x = g.add_node("x");
+        let y = g.add_node("y");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(c, a).unwrap();
+        g.add_edge(c, d).unwrap();
+        g.add_edge(d, e).unwrap();
+        g.add_edge(x, e).unwrap();
+        g.add_edge(x, y).unwrap();
+        g.add_edge(y, x).unwrap();
+
+        assert_eq!(blocking_depth(&g), vec![0, 0, 0, 1, 2, 0, 0]);
+
+        // Without cycles it agrees with the layered levels
+        g.remove_edge(c, a);
+        g.remove_edge(y, x);
+        assert_eq!(Ok(blocking_depth(&g)), topological_levels(&g));
+    }
+
+    #[test]
+    fn test_scc_topological_order_cycle_feeds_downstream() {
+        // x -> {a -> b -> c -> a} -> d, with d added first so that component
+        // ids (ordered by smallest node) run opposite to the phases
+        let mut graph = DiGraph::new();
+        let d = graph.add_node("d");
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let x = graph.add_node("x");
+        graph.add_edge(x, a).unwrap();
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(c, d).unwrap();
+
+        let scc = tarjan_scc(&graph);
+        let order = scc_topological_order(&graph, &scc);
+        let phases: Vec<&[usize]> = order.iter().map(|&id| &scc.components[id][..]).collect();
+        assert_eq!(phases, [&[x][..], &[a, b, c], &[d]]);
+        assert_eq!(scc.reverse_topological_order(), [0, 1, 2]);
+        assert!(scc_topological_order(&DiGraph::new(), &tarjan_scc(&DiGraph::new())).is_empty());
+    }
+
+    #[test]
+    fn test_generations_chain_and_wide_dag() {
+        // a -> b -> c: one node per layer
+        let mut chain = DiGraph::new();
+        let a = chain.add_node("a");
+        let b = chain.add_node("b");
+        let c = chain.add_node("c");
+        chain.add_edge(a, b).unwrap();
+        chain.add_edge(b, c).unwrap();
+        let gens = topological_generations(&chain).unwrap();
+        assert_eq!(gens.layers, vec![vec![a], vec![b], vec![c]]);
+        assert_eq!(gens.layer_of, vec![0, 1, 2]);
+
+        // Three independent roots feed two merges and one final sink
+        let mut wide = DiGraph::new();
+        for i in 0..6 {
+            wide.add_node(&format!("n{}", i));
+        }
+        for (u, v) in [(0, 3), (1, 3), (1, 4), (2, 4), (3, 5), (4, 5), (0, 5)] {
+            wide.add_edge(u, v).unwrap();
+        }
+        let gens = topological_generations(&wide).unwrap();
+        assert_eq!(gens.layers, vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(Ok(gens.layer_of.clone()), topological_levels(&wide));
+        assert_eq!(topological_generations_lenient(&wide), gens);
+
+        assert!(topological_generations(&DiGraph::new()).unwrap().layers.is_empty());
+    }
+
+    #[test]
+    fn test_generations_with_cycle() {
+        // root -> a -> b -> a, b -> tail, plus a removed node
+        let mut g = DiGraph::new();
+        let root = g.add_node("root");
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let tail = g.add_node("tail");
+        let gone = g.add_node("gone");
+        g.add_edge(root, a).unwrap();
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, a).unwrap();
+        g.add_edge(b, tail).unwrap();
+        g.remove_node(gone);
+
+        let err = topological_generations(&g).unwrap_err();
+        assert_eq!(err.nodes, vec![a, b]);
+
+        let gens = topological_generations_lenient(&g);
+        assert_eq!(gens.layers, vec![vec![root], vec![a, b], vec![tail]]);
+        assert_eq!(gens.layer_of, vec![0, 1, 1, 2, usize::MAX]);
+    }
+
     #[test]
     fn test_is_dag() {
         let mut dag = DiGraph::new();
         let a = dag.add_node("a");
         let b = dag.add_node("b");
-        dag.add_edge(a, b);
+        dag.add_edge(a, b).unwrap();
         assert!(is_dag(&dag));
 
         let mut cyclic = DiGraph::new();
         let x = cyclic.add_node("x");
         let y = cyclic.add_node("y");
-        cyclic.add_edge(x, y);
-        cyclic.add_edge(y, x);
+        cyclic.add_edge(x, y).unwrap();
+        cyclic.add_edge(y, x).unwrap();
         assert!(!is_dag(&cyclic));
     }
 
@@ -213,14 +565,14 @@ mod tests {
             g.add_node(&format!("node{}", i));
         }
         // Add some edges
-        g.add_edge(0, 5);
-        g.add_edge(1, 5);
-        g.add_edge(2, 6);
-        g.add_edge(3, 7);
-        g.add_edge(5, 8);
-        g.add_edge(6, 8);
-        g.add_edge(7, 9);
-        g.add_edge(8, 9);
+        g.add_edge(0, 5).unwrap();
+        g.add_edge(1, 5).unwrap();
+        g.add_edge(2, 6).unwrap();
+        g.add_edge(3, 7).unwrap();
+        g.add_edge(5, 8).unwrap();
+        g.add_edge(6, 8).unwrap();
+        g.add_edge(7, 9).unwrap();
+        g.add_edge(8, 9).unwrap();
 
         let result1 = topological_sort(&g).unwrap();
         let result2 = topological_sort(&g).unwrap();
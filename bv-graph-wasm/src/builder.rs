@@ -0,0 +1,264 @@
+//! Label-based graph construction with validation at the end.
+//!
+//! Importers and tests describe a graph by bead IDs and let `build` assign
+//! indices, instead of threading `add_node` results into `add_edge` calls.
+//! Every problem is collected and reported together.
+
+use crate::graph::{DiGraph, EdgeKind, GraphError};
+use std::fmt;
+
+/// One recorded builder call, replayed by `build`.
+#[derive(Debug, Clone)]
+enum Op {
+    Node(String),
+    Edge(String, String, EdgeKind),
+}
+
+/// Builds a `DiGraph` from node labels.
+///
+/// In the default lenient mode, indices follow the order in which labels
+/// are first mentioned (by `node` or as an edge endpoint), and repeated
+/// declarations are harmless. In strict mode only declared labels become
+/// nodes, in declaration order, and duplicates or undeclared edge endpoints
+/// are errors. A node may be declared after edges that use it.
+#[derive(Debug, Clone, Default)]
+pub struct GraphBuilder {
+    ops: Vec<Op>,
+    strict: bool,
+    forbid_self_loops: bool,
+}
+
+impl GraphBuilder {
+    /// Start an empty, lenient builder that allows self-loops.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require every node to be declared exactly once.
+    pub fn strict(&mut self, strict: bool) -> &mut Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Report self-loops as errors instead of adding them.
+    pub fn forbid_self_loops(&mut self, forbid: bool) -> &mut Self {
+        self.forbid_self_loops = forbid;
+        self
+    }
+
+    /// Declare a node.
+    pub fn node(&mut self, id: &str) -> &mut Self {
+        self.ops.push(Op::Node(id.to_string()));
+        self
+    }
+
+    /// Add a plain (`EdgeKind::BLOCKS`) edge from -> to.
+    pub fn edge(&mut self, from: &str, to: &str) -> &mut Self {
+        self.edge_kind(from, to, EdgeKind::BLOCKS)
+    }
+
+    /// Add an edge from -> to with a dependency kind.
+    pub fn edge_kind(&mut self, from: &str, to: &str, kind: EdgeKind) -> &mut Self {
+        self.ops.push(Op::Edge(from.to_string(), to.to_string(), kind));
+        self
+    }
+
+    /// Validate the recorded calls and build the graph.
+    ///
+    /// # Errors
+    /// Lists every problem found, nothing is built if there are any.
+    /// Duplicate declarations come first, then edge problems in call order.
+    pub fn build(&self) -> Result<DiGraph, BuildError> {
+        let mut graph = DiGraph::new();
+        let mut problems = Vec::new();
+
+        if self.strict {
+            for op in &self.ops {
+                if let Op::Node(id) = op {
+                    if graph.node_index(id).is_some() {
+                        problems.push(BuildProblem::DuplicateNode(id.clone()));
+                    }
+                    graph.add_node(id);
+                }
+            }
+        }
+
+        for op in &self.ops {
+            let (from, to, kind) = match op {
+                Op::Node(id) => {
+                    if !self.strict {
+                        graph.add_node(id);
+                    }
+                    continue;
+                }
+                Op::Edge(from, to, kind) => (from, to, *kind),
+            };
+
+            let mut resolve = |id: &String| {
+                if !self.strict {
+                    return Some(graph.add_node(id));
+                }
+                let index = graph.node_index(id);
+                if index.is_none() {
+                    problems.push(BuildProblem::UnknownNode(id.clone()));
+                }
+                index
+            };
+            let (Some(u), Some(v)) = (resolve(from), resolve(to)) else {
+                continue;
+            };
+
+            if u == v && self.forbid_self_loops {
+                problems.push(BuildProblem::SelfLoop(from.clone()));
+                continue;
+            }
+            let added = if kind == EdgeKind::default() {
+                graph.add_edge(u, v)
+            } else {
+                graph.add_edge_kind(u, v, kind.0)
+            };
+            if let Err(GraphError::InvalidKind { kind }) = added {
+                problems.push(BuildProblem::InvalidKind {
+                    from: from.clone(),
+                    to: to.clone(),
+                    kind,
+                });
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(graph)
+        } else {
+            Err(BuildError { problems })
+        }
+    }
+}
+
+/// A single problem found by `GraphBuilder::build`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildProblem {
+    /// Strict mode: the label was declared more than once
+    DuplicateNode(String),
+    /// Strict mode: an edge endpoint was never declared
+    UnknownNode(String),
+    /// Self-loop on this label while self-loops are forbidden
+    SelfLoop(String),
+    /// Edge kind above `EdgeKind::MAX`
+    InvalidKind { from: String, to: String, kind: u8 },
+}
+
+impl fmt::Display for BuildProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildProblem::DuplicateNode(id) => write!(f, "node {:?} declared twice", id),
+            BuildProblem::UnknownNode(id) => write!(f, "edge references undeclared node {:?}", id),
+            BuildProblem::SelfLoop(id) => write!(f, "self-loop on {:?} is not allowed", id),
+            BuildProblem::InvalidKind { from, to, kind } => write!(
+                f,
+                "edge {:?} -> {:?} has kind {} (max {})",
+                from,
+                to,
+                kind,
+                EdgeKind::MAX
+            ),
+        }
+    }
+}
+
+/// Every problem that stopped `GraphBuilder::build`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+    pub problems: Vec<BuildProblem>,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has {} problem(s)", self.problems.len())?;
+        for (i, problem) in self.problems.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { ":" } else { ";" }, problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_matches_manual_graph() {
+        let built = GraphBuilder::new()
+            .node("bd-1")
+            .node("bd-2")
+            .edge("bd-1", "bd-2")
+            .edge_kind("bd-2", "bd-3", EdgeKind::RELATED)
+            .edge("bd-1", "bd-2")
+            .node("bd-4")
+            .build()
+            .unwrap();
+
+        let mut manual = DiGraph::new();
+        let a = manual.add_node("bd-1");
+        let b = manual.add_node("bd-2");
+        let c = manual.add_node("bd-3");
+        manual.add_node("bd-4");
+        manual.add_edge(a, b).unwrap();
+        manual.add_edge_kind(b, c, EdgeKind::RELATED.0).unwrap();
+
+        assert_eq!(built.to_json(), manual.to_json());
+    }
+
+    #[test]
+    fn test_builder_strict_reports_all_problems() {
+        let mut builder = GraphBuilder::new();
+        builder
+            .strict(true)
+            .forbid_self_loops(true)
+            .edge("a", "b")
+            .node("a")
+            .node("b")
+            .node("a")
+            .edge("a", "ghost")
+            .edge("b", "b")
+            .edge_kind("a", "b", EdgeKind(40));
+
+        let err = builder.build().unwrap_err();
+        assert_eq!(
+            err.problems,
+            vec![
+                BuildProblem::DuplicateNode("a".into()),
+                BuildProblem::UnknownNode("ghost".into()),
+                BuildProblem::SelfLoop("b".into()),
+                BuildProblem::InvalidKind {
+                    from: "a".into(),
+                    to: "b".into(),
+                    kind: 40
+                },
+            ]
+        );
+        assert!(err.to_string().starts_with("graph has 4 problem(s): node \"a\" declared twice;"));
+
+        // The same calls in lenient mode, with self-loops allowed, succeed
+        // apart from the bad kind
+        builder.strict(false).forbid_self_loops(false);
+        assert_eq!(builder.build().unwrap_err().problems.len(), 1);
+    }
+
+    #[test]
+    fn test_builder_lenient_auto_creates() {
+        let graph = GraphBuilder::new()
+            .edge("x", "y")
+            .node("y")
+            .node("z")
+            .edge("y", "y")
+            .build()
+            .unwrap();
+        let labels: Vec<&str> = (0..graph.len()).filter_map(|v| graph.label(v)).collect();
+        assert_eq!(labels, ["x", "y", "z"]);
+        assert!(graph.has_edge(0, 1));
+        assert!(graph.has_edge(1, 1));
+        assert_eq!(graph.edge_count(), 2);
+    }
+}
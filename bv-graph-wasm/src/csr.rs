@@ -0,0 +1,166 @@
+//! Compressed sparse row (CSR) adjacency for read-only analysis.
+//!
+//! `DiGraph` keeps one successor `Vec` per node so edges can be added and
+//! removed cheaply. Once a graph is fully loaded, `DiGraph::freeze` packs
+//! those lists into two flat arrays, which traversal-heavy algorithms walk
+//! with far better cache locality. Algorithms that only need successors
+//! are written against the `Adjacency` trait and accept either form.
+
+use crate::graph::DiGraph;
+
+/// Read-only successor access shared by `DiGraph` and `CsrGraph`.
+pub trait Adjacency {
+    /// Number of node indices, including removed nodes.
+    fn len(&self) -> usize;
+
+    /// Successors of `node` (empty if out of range).
+    fn successors(&self, node: usize) -> &[u32];
+
+    /// Whether `node` has been removed (tombstoned).
+    fn is_removed(&self, node: usize) -> bool;
+
+    /// True if there are no node indices at all.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check whether the edge from -> to exists.
+    fn has_edge(&self, from: usize, to: usize) -> bool {
+        u32::try_from(to).is_ok_and(|to| self.successors(from).contains(&to))
+    }
+}
+
+impl Adjacency for DiGraph {
+    fn len(&self) -> usize {
+        DiGraph::len(self)
+    }
+
+    fn successors(&self, node: usize) -> &[u32] {
+        self.successors_slice(node)
+    }
+
+    fn is_removed(&self, node: usize) -> bool {
+        DiGraph::is_removed(self, node)
+    }
+}
+
+/// Immutable snapshot of a `DiGraph` in CSR layout.
+///
+/// The successors of `v` are `targets[offsets[v]..offsets[v + 1]]`, in the
+/// same order as `DiGraph::successors_slice`. Node indices are unchanged,
+/// so results computed on the snapshot apply directly to the source graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrGraph {
+    offsets: Vec<usize>,
+    targets: Vec<u32>,
+    removed: Vec<bool>,
+}
+
+impl CsrGraph {
+    /// Number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Snapshot keeping only the edges whose kind is selected by `kind_mask`
+    /// (see `DiGraph::successors_of_kind`). Built in one O(V + E) pass, so
+    /// masked algorithms never look up an edge's kind by position.
+    pub(crate) fn masked(graph: &DiGraph, kind_mask: u32) -> Self {
+        let n = graph.len();
+        let mut offsets = Vec::with_capacity(n + 1);
+        let mut targets = Vec::with_capacity(graph.edge_count());
+        offsets.push(0);
+        for v in 0..n {
+            targets.extend(graph.successors_of_kind(v, kind_mask).map(|w| w as u32));
+            offsets.push(targets.len());
+        }
+        CsrGraph {
+            offsets,
+            targets,
+            removed: (0..n).map(|v| graph.is_removed(v)).collect(),
+        }
+    }
+}
+
+impl From<&DiGraph> for CsrGraph {
+    fn from(graph: &DiGraph) -> Self {
+        let n = graph.len();
+        let mut offsets = Vec::with_capacity(n + 1);
+        let mut targets = Vec::with_capacity(graph.edge_count());
+        offsets.push(0);
+        for v in 0..n {
+            targets.extend_from_slice(graph.successors_slice(v));
+            offsets.push(targets.len());
+        }
+        CsrGraph {
+            offsets,
+            targets,
+            removed: (0..n).map(|v| graph.is_removed(v)).collect(),
+        }
+    }
+}
+
+impl Adjacency for CsrGraph {
+    fn len(&self) -> usize {
+        self.removed.len()
+    }
+
+    fn successors(&self, node: usize) -> &[u32] {
+        match (self.offsets.get(node), self.offsets.get(node + 1)) {
+            (Some(&start), Some(&end)) => &self.targets[start..end],
+            _ => &[],
+        }
+    }
+
+    fn is_removed(&self, node: usize) -> bool {
+        self.removed.get(node).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::cycles::{enumerate_cycles, tarjan_scc};
+    use crate::algorithms::subgraph::{can_reach, reachable_from};
+
+    fn sample() -> DiGraph {
+        // Two 3-cycles joined by a bridge, a tail, and a removed node
+        let mut g = DiGraph::new();
+        for i in 0..9 {
+            g.add_node(&format!("n{}", i));
+        }
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6), (7, 7)] {
+            g.add_edge(u, v).unwrap();
+        }
+        g.add_edge(8, 0).unwrap();
+        g.remove_node(8);
+        g
+    }
+
+    #[test]
+    fn test_freeze_preserves_adjacency() {
+        let g = sample();
+        let csr = g.freeze();
+        assert_eq!(csr.len(), g.len());
+        assert_eq!(csr.edge_count(), g.edge_count());
+        for v in 0..g.len() {
+            assert_eq!(csr.successors(v), g.successors_slice(v));
+            assert_eq!(csr.is_removed(v), g.is_removed(v));
+        }
+        assert!(csr.successors(99).is_empty());
+        assert!(csr.has_edge(7, 7));
+        assert!(DiGraph::new().freeze().is_empty());
+    }
+
+    #[test]
+    fn test_frozen_and_unfrozen_agree() {
+        let g = sample();
+        let csr = g.freeze();
+
+        assert_eq!(tarjan_scc(&g), tarjan_scc(&csr));
+        assert_eq!(enumerate_cycles(&g, 100), enumerate_cycles(&csr, 100));
+        assert_eq!(reachable_from(&g, 3), reachable_from(&csr, 3));
+        assert_eq!(can_reach(&csr, 0, 6), can_reach(&g, 0, 6));
+        assert!(!can_reach(&csr, 6, 0));
+    }
+}
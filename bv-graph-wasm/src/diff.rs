@@ -0,0 +1,143 @@
+//! Structural diff between two graph snapshots.
+//!
+//! Nodes are matched by label, so the viewer can animate what changed
+//! between two loads of the beads data instead of re-rendering.
+
+use crate::graph::DiGraph;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Differences between an old and a new graph, all keyed by node label.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GraphDiff {
+    /// Labels only in the new graph, in new index order
+    pub added_nodes: Vec<String>,
+    /// Labels only in the old graph, in old index order
+    pub removed_nodes: Vec<String>,
+    /// `(from, to)` label pairs only in the new graph
+    pub added_edges: Vec<(String, String)>,
+    /// `(from, to)` label pairs only in the old graph, including edges of removed nodes
+    pub removed_edges: Vec<(String, String)>,
+    /// Labels in both graphs whose node attributes differ
+    pub changed_nodes: Vec<String>,
+}
+
+impl GraphDiff {
+    /// True if the two graphs were structurally identical.
+    pub fn is_empty(&self) -> bool {
+        *self == GraphDiff::default()
+    }
+}
+
+/// Compare two graphs by node label.
+///
+/// A renamed node shows up as a removal plus an addition. Edge weights and
+/// kinds are not compared. Edges are listed in `edges()` order of the graph
+/// they come from.
+pub fn graph_diff(old: &DiGraph, new: &DiGraph) -> GraphDiff {
+    let mut diff = GraphDiff::default();
+
+    for v in 0..new.len() {
+        if let Some(label) = new.label(v) {
+            match old.node_index(label) {
+                None => diff.added_nodes.push(label.to_string()),
+                Some(u) if old.node_attrs(u) != new.node_attrs(v) => {
+                    diff.changed_nodes.push(label.to_string());
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    for u in 0..old.len() {
+        if let Some(label) = old.label(u) {
+            if new.node_index(label).is_none() {
+                diff.removed_nodes.push(label.to_string());
+            }
+        }
+    }
+
+    let labeled_edges = |graph: &DiGraph| -> Vec<(String, String)> {
+        graph
+            .edges()
+            .filter_map(|(u, v)| Some((graph.label(u)?.to_string(), graph.label(v)?.to_string())))
+            .collect()
+    };
+    let old_edges = labeled_edges(old);
+    let new_edges = labeled_edges(new);
+    let old_set: HashSet<&(String, String)> = old_edges.iter().collect();
+    let new_set: HashSet<&(String, String)> = new_edges.iter().collect();
+
+    diff.added_edges = new_edges.iter().filter(|e| !old_set.contains(e)).cloned().collect();
+    diff.removed_edges = old_edges.iter().filter(|e| !new_set.contains(e)).cloned().collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chain() -> DiGraph {
+        // a -> b -> c
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph
+    }
+
+    fn pair(from: &str, to: &str) -> (String, String) {
+        (from.to_string(), to.to_string())
+    }
+
+    #[test]
+    fn test_identical_graphs() {
+        let diff = graph_diff(&chain(), &chain());
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_closing_edge_only_added() {
+        let mut new = chain();
+        new.add_edge(2, 0).unwrap();
+
+        let diff = graph_diff(&chain(), &new);
+        assert_eq!(diff.added_edges, vec![pair("c", "a")]);
+        assert!(diff.added_nodes.is_empty());
+        assert!(diff.removed_nodes.is_empty());
+        assert!(diff.removed_edges.is_empty());
+        assert!(diff.changed_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_removed_node_edges() {
+        // New graph lacks b; indices differ but labels still match
+        let mut new = DiGraph::new();
+        let c = new.add_node("c");
+        let a = new.add_node("a");
+        let d = new.add_node("d");
+        new.add_edge(a, c).unwrap();
+        new.add_edge(d, a).unwrap();
+
+        let diff = graph_diff(&chain(), &new);
+        assert_eq!(diff.removed_nodes, vec!["b"]);
+        assert_eq!(diff.added_nodes, vec!["d"]);
+        assert_eq!(diff.removed_edges, vec![pair("a", "b"), pair("b", "c")]);
+        assert_eq!(diff.added_edges, vec![pair("a", "c"), pair("d", "a")]);
+    }
+
+    #[test]
+    fn test_changed_attributes() {
+        let mut old = chain();
+        let mut new = chain();
+        old.set_node_attr(0, "status", json!("open"));
+        new.set_node_attr(0, "status", json!("closed"));
+        new.set_node_attr(1, "priority", json!(1));
+
+        let diff = graph_diff(&old, &new);
+        assert_eq!(diff.changed_nodes, vec!["a", "b"]);
+        assert!(diff.added_edges.is_empty() && diff.removed_edges.is_empty());
+    }
+}
@@ -1,12 +1,16 @@
 //! Core directed graph structure with adjacency lists.
 
+use crate::algorithms::cycles::SCCResult;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
+use std::fmt;
 use wasm_bindgen::prelude::*;
 
 /// Directed graph optimized for graph algorithms.
 /// Uses adjacency lists for O(1) neighbor access.
 #[wasm_bindgen]
+#[derive(Debug)]
 pub struct DiGraph {
     /// Node ID strings (issue IDs like "bv-123")
     nodes: Vec<String>,
@@ -15,15 +19,169 @@ pub struct DiGraph {
     node_index: HashMap<String, usize>,
 
     /// Forward adjacency: adj[u] = vec of nodes that u points to
-    /// (u depends on these nodes)
-    adj: Vec<Vec<usize>>,
+    /// (u depends on these nodes). Stored as u32, which halves memory on
+    /// 64-bit hosts; only the borrowing `*_slice` accessors expose u32.
+    adj: Vec<Vec<u32>>,
 
     /// Reverse adjacency: rev_adj[v] = vec of nodes pointing to v
     /// (these nodes depend on v)
-    rev_adj: Vec<Vec<usize>>,
+    rev_adj: Vec<Vec<u32>>,
 
     /// Edge count (for density calculation)
     edge_count: usize,
+
+    /// Edge weights parallel to adj: weights[u][i] is the weight of u -> adj[u][i].
+    /// None until the first weighted edge is added (unweighted edges weigh 1.0).
+    weights: Option<Vec<Vec<f64>>>,
+
+    /// Edge kinds parallel to adj, like `weights`.
+    /// None until the first non-default kind is set (plain edges are kind 0).
+    kinds: Option<Vec<Vec<EdgeKind>>>,
+
+    /// Per-node attributes (status, priority, ...), keyed by node index.
+    /// Only nodes with at least one attribute have an entry.
+    attrs: HashMap<usize, Map<String, Value>>,
+
+    /// Tombstones: removed[v] is true once v has been removed.
+    /// Removed indices are never reused so indices held by callers stay valid.
+    removed: Vec<bool>,
+
+    /// Number of tombstoned nodes
+    removed_count: usize,
+
+    /// Supernodes created by `contract`, mapped to the original nodes they replaced
+    members: HashMap<usize, Vec<usize>>,
+}
+
+/// Node index as stored in adjacency lists.
+///
+/// The public API takes and returns `usize` indices, but adjacency lists
+/// hold `u32`, which halves their memory on 64-bit hosts and costs nothing
+/// on wasm32. `NodeId` is the checked conversion between the two: a graph
+/// holds at most 2^32 nodes, and converting a larger index fails with
+/// `GraphError::TooManyNodes` instead of silently truncating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NodeId(pub u32);
+
+impl NodeId {
+    /// The index as `usize`.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<u32> for NodeId {
+    fn from(v: u32) -> Self {
+        NodeId(v)
+    }
+}
+
+impl From<NodeId> for usize {
+    fn from(v: NodeId) -> Self {
+        v.index()
+    }
+}
+
+impl TryFrom<usize> for NodeId {
+    type Error = GraphError;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        if index > max_node_index() {
+            return Err(GraphError::TooManyNodes { index });
+        }
+        Ok(NodeId(index as u32))
+    }
+}
+
+/// Largest index a node can take. Tests lower it so the overflow path can
+/// be driven without allocating 2^32 nodes.
+fn max_node_index() -> usize {
+    #[cfg(test)]
+    if let Some(limit) = tests::NODE_INDEX_LIMIT.with(std::cell::Cell::get) {
+        return limit;
+    }
+    u32::MAX as usize
+}
+
+/// Dependency type of an edge.
+///
+/// Kinds double as bit positions in a `u32` kind mask, so only kinds
+/// 0..=`EdgeKind::MAX` can be stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct EdgeKind(pub u8);
+
+impl EdgeKind {
+    /// Hard dependency (the default for `add_edge`)
+    pub const BLOCKS: EdgeKind = EdgeKind(0);
+    /// Informational link; doesn't constrain ordering
+    pub const RELATED: EdgeKind = EdgeKind(1);
+    pub const PARENT_CHILD: EdgeKind = EdgeKind(2);
+    pub const DISCOVERED_FROM: EdgeKind = EdgeKind(3);
+
+    /// Largest storable kind
+    pub const MAX: u8 = 31;
+    /// Mask selecting every kind
+    pub const ALL: u32 = u32::MAX;
+
+    /// Single-bit mask for this kind (0 for kinds above `MAX`).
+    pub fn mask(self) -> u32 {
+        1u32.checked_shl(self.0 as u32).unwrap_or(0)
+    }
+}
+
+/// Reason an edge could not be added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphError {
+    /// An endpoint index is not below the graph's node count
+    NodeOutOfRange { index: usize, node_count: usize },
+    /// An endpoint was removed; tombstones never regain edges
+    NodeRemoved { index: usize },
+    /// The edge kind is above `EdgeKind::MAX`
+    InvalidKind { kind: u8 },
+    /// A new node's index would not fit the u32 adjacency storage
+    TooManyNodes { index: usize },
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            GraphError::NodeOutOfRange { index, node_count } => write!(
+                f,
+                "node index {} is out of range for a graph with {} nodes",
+                index, node_count
+            ),
+            GraphError::NodeRemoved { index } => write!(f, "node {} has been removed", index),
+            GraphError::InvalidKind { kind } => {
+                write!(f, "edge kind {} exceeds the maximum of {}", kind, EdgeKind::MAX)
+            }
+            GraphError::TooManyNodes { index } => {
+                write!(f, "node index {} exceeds the u32 index range", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// Thrown to JS as an `Error` carrying the `Display` message.
+impl From<GraphError> for JsValue {
+    fn from(e: GraphError) -> Self {
+        JsError::new(&e.to_string()).into()
+    }
+}
+
+/// Split a flat `[a, b, a, b, ...]` array from JS into pairs.
+///
+/// Fails on an odd length instead of dropping the trailing element.
+pub(crate) fn flat_pairs<T: Copy>(flat: &[T]) -> Result<Vec<(T, T)>, String> {
+    if !flat.len().is_multiple_of(2) {
+        return Err(format!(
+            "expected a flat array of pairs, got {} elements",
+            flat.len()
+        ));
+    }
+    Ok(flat.chunks_exact(2).map(|p| (p[0], p[1])).collect())
 }
 
 /// Serializable graph snapshot for import/export.
@@ -31,6 +189,30 @@ pub struct DiGraph {
 pub struct GraphSnapshot {
     pub nodes: Vec<String>,
     pub edges: Vec<(usize, usize)>,
+    /// Edge weights parallel to `edges` (omitted for unweighted graphs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f64>>,
+    /// Edge kinds parallel to `edges` (omitted when every edge is kind 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kinds: Option<Vec<u8>>,
+    /// Node attributes parallel to `nodes` (omitted when no node has any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attrs: Option<Vec<Map<String, Value>>>,
+    /// Indices of removed nodes, whose `nodes` entries are empty placeholders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed: Option<Vec<usize>>,
+    /// Per-node analysis tags parallel to `nodes` (only in annotated exports)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Vec<NodeAnnotation>>,
+}
+
+/// SCC membership of a node, attached by `DiGraph::to_json_annotated`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NodeAnnotation {
+    /// Index into `SCCResult::components`
+    pub component: usize,
+    /// True if the node's component is non-trivial or the node has a self-loop
+    pub in_cycle: bool,
 }
 
 #[wasm_bindgen]
@@ -44,52 +226,433 @@ impl DiGraph {
             adj: Vec::new(),
             rev_adj: Vec::new(),
             edge_count: 0,
+            weights: None,
+            kinds: None,
+            attrs: HashMap::new(),
+            removed: Vec::new(),
+            removed_count: 0,
+            members: HashMap::new(),
         }
     }
 
     /// Create a graph with pre-allocated capacity.
+    /// Adjacency lists are per node, so the edge hint can't be split up front;
+    /// use `reserveEdges` or `addEdges` to size them.
     #[wasm_bindgen(js_name = withCapacity)]
-    pub fn with_capacity(node_capacity: usize, edge_capacity: usize) -> DiGraph {
-        let _ = edge_capacity; // Used for documentation, not pre-allocation
+    pub fn with_capacity(node_capacity: usize, _edge_hint: usize) -> DiGraph {
         DiGraph {
             nodes: Vec::with_capacity(node_capacity),
             node_index: HashMap::with_capacity(node_capacity),
             adj: Vec::with_capacity(node_capacity),
             rev_adj: Vec::with_capacity(node_capacity),
             edge_count: 0,
+            weights: None,
+            kinds: None,
+            attrs: HashMap::new(),
+            removed: Vec::with_capacity(node_capacity),
+            removed_count: 0,
+            members: HashMap::new(),
+        }
+    }
+
+    /// Reserve room for `additional` more nodes without reallocating.
+    #[wasm_bindgen(js_name = reserveNodes)]
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+        self.node_index.reserve(additional);
+        self.adj.reserve(additional);
+        self.rev_adj.reserve(additional);
+        self.removed.reserve(additional);
+    }
+
+    /// Reserve room for `additional` more outgoing edges of `node`.
+    /// Ignores invalid nodes.
+    #[wasm_bindgen(js_name = reserveEdges)]
+    pub fn reserve_edges(&mut self, node: usize, additional: usize) {
+        if let Some(succs) = self.adj.get_mut(node) {
+            succs.reserve(additional);
         }
     }
 
     /// Add a node, returns its index. Idempotent - returns existing index if already present.
+    /// Fails (throws in JS) once indices would leave the u32 range.
     #[wasm_bindgen(js_name = addNode)]
-    pub fn add_node(&mut self, id: &str) -> usize {
+    pub fn try_add_node(&mut self, id: &str) -> Result<usize, GraphError> {
         if let Some(&idx) = self.node_index.get(id) {
-            return idx;
+            return Ok(idx);
         }
         let idx = self.nodes.len();
+        NodeId::try_from(idx)?;
         self.nodes.push(id.to_string());
         self.node_index.insert(id.to_string(), idx);
         self.adj.push(Vec::new());
         self.rev_adj.push(Vec::new());
-        idx
+        self.removed.push(false);
+        if let Some(weights) = &mut self.weights {
+            weights.push(Vec::new());
+        }
+        if let Some(kinds) = &mut self.kinds {
+            kinds.push(Vec::new());
+        }
+        Ok(idx)
     }
 
     /// Add a directed edge from -> to. Idempotent.
+    /// Parallel edges are never stored, so degrees and successor lists
+    /// count each dependency once no matter how often it is added.
+    /// Fails (throws in JS) if an endpoint is out of range or removed.
     #[wasm_bindgen(js_name = addEdge)]
-    pub fn add_edge(&mut self, from: usize, to: usize) {
-        // Check bounds
-        if from >= self.nodes.len() || to >= self.nodes.len() {
-            return; // Silently ignore invalid edges
-        }
+    pub fn add_edge(&mut self, from: usize, to: usize) -> Result<(), GraphError> {
+        let (from_id, to_id) = self.check_endpoints(from, to)?;
 
         // Check if edge already exists (linear scan is fine for typical degree)
-        if self.adj[from].contains(&to) {
-            return;
+        if self.adj[from].contains(&to_id.0) {
+            return Ok(());
         }
 
-        self.adj[from].push(to);
-        self.rev_adj[to].push(from);
+        self.adj[from].push(to_id.0);
+        self.rev_adj[to].push(from_id.0);
+        if let Some(weights) = &mut self.weights {
+            weights[from].push(1.0);
+        }
+        if let Some(kinds) = &mut self.kinds {
+            kinds[from].push(EdgeKind::default());
+        }
         self.edge_count += 1;
+        Ok(())
+    }
+
+    /// Add many edges at once from a flat [from, to, from, to, ...] array.
+    /// Fails without adding anything if any endpoint is out of range or the
+    /// array has odd length.
+    #[wasm_bindgen(js_name = addEdges)]
+    pub fn add_edges_flat(&mut self, pairs: &[usize]) -> Result<(), JsError> {
+        let edges = flat_pairs(pairs).map_err(|e| JsError::new(&e))?;
+        self.add_edges(&edges).map_err(|e| JsError::new(&e))
+    }
+
+    /// Build a graph from node IDs and a flat [from, to, ...] index array.
+    /// Pass `sorted = true` to sort adjacency lists afterwards (see `sortAdjacency`).
+    #[wasm_bindgen(js_name = fromEdgeList)]
+    pub fn from_edge_list_flat(
+        labels: Vec<String>,
+        pairs: &[u32],
+        sorted: Option<bool>,
+    ) -> Result<DiGraph, JsError> {
+        let edges = flat_pairs(pairs).map_err(|e| JsError::new(&e))?;
+        DiGraph::from_edge_list(labels, &edges, sorted.unwrap_or(false))
+            .map_err(|e| JsError::new(&e))
+    }
+
+    /// Sort every successor and predecessor list ascending.
+    ///
+    /// Algorithms walk adjacency lists in stored order, which otherwise is
+    /// insertion order. Once sorted, cycle enumeration, SCCs, traversals and
+    /// layouts give the same output for the same edge set however it was
+    /// built. Edges added afterwards are appended, so sort again after edits.
+    #[wasm_bindgen(js_name = sortAdjacency)]
+    pub fn sort_adjacency(&mut self) {
+        for u in 0..self.adj.len() {
+            self.rev_adj[u].sort_unstable();
+            if self.adj[u].is_sorted() {
+                continue;
+            }
+
+            // Permute weights and kinds along with their successors
+            let mut order: Vec<usize> = (0..self.adj[u].len()).collect();
+            order.sort_unstable_by_key(|&i| self.adj[u][i]);
+            let succs: Vec<u32> = order.iter().map(|&i| self.adj[u][i]).collect();
+            self.adj[u] = succs;
+            if let Some(weights) = &mut self.weights {
+                weights[u] = order.iter().map(|&i| weights[u][i]).collect();
+            }
+            if let Some(kinds) = &mut self.kinds {
+                kinds[u] = order.iter().map(|&i| kinds[u][i]).collect();
+            }
+        }
+    }
+
+    /// Add a directed edge from -> to with a dependency kind (0..=31).
+    /// If the edge already exists, its kind is updated. Fails like `addEdge`,
+    /// and also for kinds above 31.
+    #[wasm_bindgen(js_name = addEdgeKind)]
+    pub fn add_edge_kind(&mut self, from: usize, to: usize, kind: u8) -> Result<(), GraphError> {
+        if kind > EdgeKind::MAX {
+            return Err(GraphError::InvalidKind { kind });
+        }
+        self.add_edge(from, to)?;
+        let pos = self.edge_position(from, to);
+
+        let adj = &self.adj;
+        let kinds = self.kinds.get_or_insert_with(|| {
+            adj.iter()
+                .map(|succs| vec![EdgeKind::default(); succs.len()])
+                .collect()
+        });
+        kinds[from][pos] = EdgeKind(kind);
+        Ok(())
+    }
+
+    /// Kind of edge from -> to (0 for plain edges), or undefined if absent.
+    #[wasm_bindgen(js_name = edgeKind)]
+    pub fn edge_kind_code(&self, from: usize, to: usize) -> Option<u8> {
+        self.edge_kind(from, to).map(|k| k.0)
+    }
+
+    /// Remove the edge from -> to. Returns false if it doesn't exist.
+    /// The relative order of the remaining successors is preserved.
+    #[wasm_bindgen(js_name = removeEdge)]
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> bool {
+        let pos = match self.adj.get(from).and_then(|s| s.iter().position(|&w| w as usize == to)) {
+            Some(pos) => pos,
+            None => return false,
+        };
+
+        self.adj[from].remove(pos);
+        if let Some(weights) = &mut self.weights {
+            weights[from].remove(pos);
+        }
+        if let Some(kinds) = &mut self.kinds {
+            kinds[from].remove(pos);
+        }
+        if let Some(rpos) = self.rev_adj[to].iter().position(|&u| u as usize == from) {
+            self.rev_adj[to].remove(rpos);
+        }
+        self.edge_count -= 1;
+        true
+    }
+
+    /// Remove a node, leaving a tombstone so other indices stay valid.
+    ///
+    /// All incident edges and attributes are dropped and the ID is freed
+    /// (adding it again creates a new index). Returns false if the node
+    /// doesn't exist or was already removed.
+    #[wasm_bindgen(js_name = removeNode)]
+    pub fn remove_node(&mut self, node: usize) -> bool {
+        if node >= self.nodes.len() || self.removed[node] {
+            return false;
+        }
+
+        for to in self.adj[node].clone() {
+            self.remove_edge(node, to as usize);
+        }
+        for from in self.rev_adj[node].clone() {
+            self.remove_edge(from as usize, node);
+        }
+
+        let id = std::mem::take(&mut self.nodes[node]);
+        self.node_index.remove(&id);
+        self.attrs.remove(&node);
+        self.members.remove(&node);
+        self.removed[node] = true;
+        self.removed_count += 1;
+        true
+    }
+
+    /// Check whether a node has been removed.
+    #[wasm_bindgen(js_name = isRemoved)]
+    pub fn is_removed(&self, node: usize) -> bool {
+        self.removed.get(node).copied().unwrap_or(false)
+    }
+
+    /// Number of live (non-removed) nodes.
+    /// nodeCount() still counts tombstones, since it bounds valid indices.
+    #[wasm_bindgen(js_name = activeNodeCount)]
+    pub fn active_len(&self) -> usize {
+        self.nodes.len() - self.removed_count
+    }
+
+    /// Check whether the edge from -> to exists.
+    #[wasm_bindgen(js_name = hasEdge)]
+    pub fn has_edge(&self, from: usize, to: usize) -> bool {
+        u32::try_from(to).is_ok_and(|to| self.successors_slice(from).contains(&to))
+    }
+
+    /// Check, without mutating, whether adding from -> to would close a cycle,
+    /// i.e. whether `to` already reaches `from`. Cheaper than re-running
+    /// Tarjan after every interactive edit. A self-loop always counts;
+    /// invalid or removed endpoints never do.
+    #[wasm_bindgen(js_name = wouldCreateCycle)]
+    pub fn would_create_cycle(&self, from: usize, to: usize) -> bool {
+        if from >= self.nodes.len() || to >= self.nodes.len() {
+            return false;
+        }
+        if self.removed[from] || self.removed[to] {
+            return false;
+        }
+        crate::algorithms::subgraph::can_reach(self, to, from)
+    }
+
+    /// Add a weighted directed edge from -> to.
+    /// If the edge already exists, its weight is updated. Fails like `addEdge`.
+    #[wasm_bindgen(js_name = addWeightedEdge)]
+    pub fn add_weighted_edge(
+        &mut self,
+        from: usize,
+        to: usize,
+        weight: f64,
+    ) -> Result<(), GraphError> {
+        self.add_edge(from, to)?;
+        let pos = self.edge_position(from, to);
+
+        // First weighted edge: materialize default weights for existing edges
+        let adj = &self.adj;
+        let weights = self
+            .weights
+            .get_or_insert_with(|| adj.iter().map(|succs| vec![1.0; succs.len()]).collect());
+        weights[from][pos] = weight;
+        Ok(())
+    }
+
+    /// Weight of edge from -> to (1.0 for unweighted edges), or undefined if absent.
+    #[wasm_bindgen(js_name = edgeWeight)]
+    pub fn edge_weight(&self, from: usize, to: usize) -> Option<f64> {
+        let pos = self.adj.get(from)?.iter().position(|&w| w as usize == to)?;
+        Some(self.weights.as_ref().map_or(1.0, |weights| weights[from][pos]))
+    }
+
+    /// Merge another graph into this one.
+    ///
+    /// Nodes are keyed by ID, so a node of `other` whose ID already exists
+    /// here is unified with the existing node; the rest are appended. Edges,
+    /// edge weights/kinds (when `other` carries them) and node attributes
+    /// are copied across. On conflicts the existing value wins: edges already
+    /// present keep their weight and kind, and existing attribute keys are
+    /// not overwritten. Returns the new index of each node of `other`
+    /// (`usize::MAX` for nodes removed from `other`).
+    pub fn merge(&mut self, other: &DiGraph) -> Vec<usize> {
+        let mapping: Vec<usize> = (0..other.len())
+            .map(|v| match other.label(v) {
+                Some(id) => self.add_node(id),
+                None => usize::MAX,
+            })
+            .collect();
+
+        for (from, to) in other.edges() {
+            let (u, v) = (mapping[from], mapping[to]);
+            if self.has_edge(u, v) {
+                continue;
+            }
+            let added = match other.edge_weight(from, to).filter(|_| other.weights.is_some()) {
+                Some(weight) => self.add_weighted_edge(u, v, weight),
+                None => self.add_edge(u, v),
+            };
+            added.expect("merged endpoints are live");
+            if other.kinds.is_some() {
+                if let Some(kind) = other.edge_kind(from, to) {
+                    self.add_edge_kind(u, v, kind.0).expect("kind came from a stored edge");
+                }
+            }
+        }
+
+        for (&node, attrs) in &other.attrs {
+            for (key, value) in attrs {
+                if self.node_attr(mapping[node], key).is_none() {
+                    self.set_node_attr(mapping[node], key, value.clone());
+                }
+            }
+        }
+
+        mapping
+    }
+
+    /// Diff this graph against an older snapshot, matching nodes by label.
+    /// Returns JSON: { added_nodes, removed_nodes, added_edges: [[from, to], ...],
+    ///                 removed_edges, changed_nodes } (all by label)
+    #[wasm_bindgen(js_name = diffFrom)]
+    pub fn diff_from(&self, old: &DiGraph) -> JsValue {
+        let diff = crate::diff::graph_diff(old, self);
+        serde_wasm_bindgen::to_value(&diff).unwrap_or(JsValue::NULL)
+    }
+
+    /// Replace a group of nodes by a single supernode labeled `new_label`.
+    ///
+    /// Edges between the group and the rest of the graph are redirected to the
+    /// supernode (deduplicated, keeping the first weight/kind seen); edges
+    /// inside the group are dropped. Members are removed like `removeNode`,
+    /// including their attributes, and recorded for `membersOf`. Contracting
+    /// a single node is a rename that keeps its index. If `new_label` already
+    /// names a node outside the group, that node absorbs the group.
+    ///
+    /// Invalid and removed indices are ignored; returns the supernode index,
+    /// or `usize::MAX` if no valid node was given.
+    pub fn contract(&mut self, nodes: &[usize], new_label: &str) -> usize {
+        let mut group: Vec<usize> = nodes
+            .iter()
+            .copied()
+            .filter(|&v| v < self.nodes.len() && !self.removed[v])
+            .collect();
+        group.sort_unstable();
+        group.dedup();
+        if group.is_empty() {
+            return usize::MAX;
+        }
+
+        if let [v] = group[..] {
+            match self.node_index.get(new_label) {
+                Some(&w) if w == v => return v,
+                Some(_) => {}
+                None => {
+                    let old = std::mem::replace(&mut self.nodes[v], new_label.to_string());
+                    self.node_index.remove(&old);
+                    self.node_index.insert(new_label.to_string(), v);
+                    return v;
+                }
+            }
+        }
+
+        // External edges, with usize::MAX standing in for the supernode
+        let in_group = |v: usize| group.binary_search(&v).is_ok();
+        let mut redirected = Vec::new();
+        for &u in &group {
+            for v in self.out_neighbors(u).filter(|&v| !in_group(v)) {
+                redirected.push((usize::MAX, v, self.edge_weight(u, v), self.edge_kind(u, v)));
+            }
+            for w in self.in_neighbors(u).filter(|&w| !in_group(w)) {
+                redirected.push((w, usize::MAX, self.edge_weight(w, u), self.edge_kind(w, u)));
+            }
+        }
+
+        let mut members = Vec::with_capacity(group.len());
+        for &u in &group {
+            // Nested contraction: record the original nodes, not the old supernode
+            match self.members.remove(&u) {
+                Some(inner) => members.extend(inner),
+                None => members.push(u),
+            }
+            self.remove_node(u);
+        }
+
+        let supernode = self.add_node(new_label);
+        let resolve = |v: usize| if v == usize::MAX { supernode } else { v };
+        for (from, to, weight, kind) in redirected {
+            let (from, to) = (resolve(from), resolve(to));
+            if from == to || self.has_edge(from, to) {
+                continue;
+            }
+            let added = match weight.filter(|_| self.weights.is_some()) {
+                Some(weight) => self.add_weighted_edge(from, to, weight),
+                None => self.add_edge(from, to),
+            };
+            added.expect("redirected endpoints are live");
+            if self.kinds.is_some() {
+                if let Some(kind) = kind {
+                    self.add_edge_kind(from, to, kind.0).expect("kind came from a stored edge");
+                }
+            }
+        }
+
+        let recorded = self.members.entry(supernode).or_default();
+        recorded.extend(members);
+        recorded.sort_unstable();
+        supernode
+    }
+
+    /// Original nodes replaced by a supernode, as a JSON array of indices
+    /// (empty for nodes that aren't supernodes).
+    #[wasm_bindgen(js_name = membersOf)]
+    pub fn contracted_members(&self, supernode: usize) -> JsValue {
+        serde_wasm_bindgen::to_value(self.members_of(supernode)).unwrap_or(JsValue::NULL)
     }
 
     /// Number of nodes.
@@ -106,7 +669,7 @@ impl DiGraph {
 
     /// Graph density: edges / (nodes * (nodes - 1)).
     pub fn density(&self) -> f64 {
-        let n = self.node_count() as f64;
+        let n = self.active_len() as f64;
         let e = self.edge_count() as f64;
         if n <= 1.0 {
             0.0
@@ -118,7 +681,7 @@ impl DiGraph {
     /// Get node ID by index.
     #[wasm_bindgen(js_name = nodeId)]
     pub fn node_id(&self, idx: usize) -> Option<String> {
-        self.nodes.get(idx).cloned()
+        self.label(idx).map(str::to_string)
     }
 
     /// Get node index by ID.
@@ -127,6 +690,20 @@ impl DiGraph {
         self.node_index.get(id).copied()
     }
 
+    /// Look up a node index by label, like `nodeIdx`; undefined if absent.
+    #[wasm_bindgen(js_name = nodeByLabel)]
+    pub fn node_by_label(&self, label: &str) -> Option<usize> {
+        self.node_index(label)
+    }
+
+    /// Index of the node labeled `label`, adding the node first if missing.
+    /// Labels are unique, so repeated calls return the first index.
+    /// Fails (throws in JS) under the same conditions as `addNode`.
+    #[wasm_bindgen(js_name = getOrAddNode)]
+    pub fn get_or_add_node(&mut self, label: &str) -> Result<usize, GraphError> {
+        self.try_add_node(label)
+    }
+
     /// Get all node IDs as JSON array.
     #[wasm_bindgen(js_name = nodeIds)]
     pub fn node_ids(&self) -> JsValue {
@@ -159,30 +736,289 @@ impl DiGraph {
         serde_wasm_bindgen::to_value(&degrees).unwrap_or(JsValue::NULL)
     }
 
+    /// Source nodes (in-degree 0) as JSON array. See `sources`.
+    #[wasm_bindgen(js_name = sources)]
+    pub fn source_nodes(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.sources()).unwrap_or(JsValue::NULL)
+    }
+
+    /// Sink nodes (out-degree 0) as JSON array. See `sinks`.
+    #[wasm_bindgen(js_name = sinks)]
+    pub fn sink_nodes(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.sinks()).unwrap_or(JsValue::NULL)
+    }
+
+    /// Degree summary: min/max/avg in- and out-degree, sources, sinks and histograms.
+    /// Returns JSON: { node_count, min_in_degree, max_in_degree, avg_in_degree, ...,
+    ///                 in_histogram: [[degree, count], ...], out_histogram: [...] }
+    #[wasm_bindgen(js_name = degreeStats)]
+    pub fn degree_stats(&self) -> JsValue {
+        use crate::algorithms::stats::degree_stats;
+        let result = degree_stats(self);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
+    /// Degrees and role of every node in one call, in node index order.
+    /// Returns JSON: [{ in_deg, out_deg, is_source, is_sink, is_isolated,
+    /// has_self_loop }, ...]; a self-loop counts in both degrees only.
+    #[wasm_bindgen(js_name = nodeRoles)]
+    pub fn node_roles(&self) -> JsValue {
+        use crate::algorithms::stats::node_roles;
+        serde_wasm_bindgen::to_value(&node_roles(self)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Shape of the graph: "Empty", "Forest", "Tree", "Dag" or "Cyclic".
+    #[wasm_bindgen(js_name = classify)]
+    pub fn classify(&self) -> JsValue {
+        use crate::algorithms::stats::classify;
+        serde_wasm_bindgen::to_value(&classify(self)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Local clustering coefficient per node, edge direction ignored.
+    /// Returns array of values in [0, 1] in node index order.
+    #[wasm_bindgen(js_name = clusteringCoefficients)]
+    pub fn clustering_coefficients(&self) -> JsValue {
+        use crate::algorithms::stats::clustering_coefficients;
+        serde_wasm_bindgen::to_value(&clustering_coefficients(self)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Mean local clustering coefficient over live nodes.
+    #[wasm_bindgen(js_name = averageClustering)]
+    pub fn average_clustering(&self) -> f64 {
+        crate::algorithms::stats::average_clustering(self)
+    }
+
+    /// Weakly connected components (edge direction ignored).
+    /// Returns JSON: { components: number[][], component_of: number[], count }
+    #[wasm_bindgen(js_name = weaklyConnectedComponents)]
+    pub fn weakly_connected_components(&self) -> JsValue {
+        use crate::algorithms::components::weakly_connected_components;
+        let result = weakly_connected_components(self);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
+    /// Community id per node from seeded label propagation (see `label_propagation`).
+    /// Returns JSON array; removed nodes get usize::MAX (2^32 - 1 in wasm).
+    #[wasm_bindgen(js_name = labelPropagation)]
+    pub fn label_propagation(&self, max_iters: usize, seed: u64) -> JsValue {
+        use crate::algorithms::community::label_propagation;
+        serde_wasm_bindgen::to_value(&label_propagation(self, max_iters, seed).community_of)
+            .unwrap_or(JsValue::NULL)
+    }
+
+    /// `labelPropagation` with community sizes and whether the labels settled.
+    /// Returns JSON: { community_of, count, sizes, iterations, converged }
+    #[wasm_bindgen(js_name = labelPropagationWithInfo)]
+    pub fn label_propagation_with_info(&self, max_iters: usize, seed: u64) -> JsValue {
+        use crate::algorithms::community::label_propagation;
+        serde_wasm_bindgen::to_value(&label_propagation(self, max_iters, seed))
+            .unwrap_or(JsValue::NULL)
+    }
+
+    /// Seeded Louvain communities over the undirected weighted view.
+    /// Returns JSON: { community_of, count, sizes, iterations, converged,
+    /// modularity, levels: [{ community_of, modularity }, ...] }
+    #[wasm_bindgen(js_name = louvain)]
+    pub fn louvain(&self, resolution: f64, seed: u64) -> JsValue {
+        use crate::algorithms::community::louvain;
+        serde_wasm_bindgen::to_value(&louvain(self, resolution, seed)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Eccentricity of each node over the undirected view (null for removed nodes).
+    /// Returns JSON array of numbers.
+    #[wasm_bindgen(js_name = eccentricities)]
+    pub fn eccentricities(&self) -> JsValue {
+        use crate::algorithms::distance::eccentricities;
+        let ecc = eccentricities(self);
+        serde_wasm_bindgen::to_value(&ecc).unwrap_or(JsValue::NULL)
+    }
+
+    /// Diameter of the largest weakly connected component, or undefined if empty.
+    #[wasm_bindgen(js_name = diameter)]
+    pub fn diameter(&self) -> Option<usize> {
+        crate::algorithms::distance::diameter(self)
+    }
+
+    /// Radius of the largest weakly connected component, or undefined if empty.
+    #[wasm_bindgen(js_name = radius)]
+    pub fn radius(&self) -> Option<usize> {
+        crate::algorithms::distance::radius(self)
+    }
+
     /// Export graph as JSON snapshot.
     #[wasm_bindgen(js_name = toJson)]
     pub fn to_json(&self) -> String {
         let snapshot = GraphSnapshot {
             nodes: self.nodes.clone(),
             edges: self.edges_vec(),
+            weights: self.weights.as_ref().map(|_| self.edge_weights()),
+            kinds: self.kinds_vec(),
+            attrs: self.attrs_vec(),
+            removed: self.removed_vec(),
+            annotations: None,
         };
         serde_json::to_string(&snapshot).unwrap_or_default()
     }
 
+    /// Export graph as JSON snapshot with per-node SCC annotations.
+    /// Same schema as toJson plus `annotations: { component: number, in_cycle: bool }[]`.
+    #[wasm_bindgen(js_name = toJsonAnnotated)]
+    pub fn to_json_with_scc(&self) -> String {
+        use crate::algorithms::cycles::tarjan_scc;
+        self.to_json_annotated(&tarjan_scc(self))
+    }
+
+    /// Export graph as a Mermaid `flowchart TD` diagram.
+    /// Nodes get safe ids (n0, n1, ...) with the node ID as label.
+    #[wasm_bindgen(js_name = toMermaid)]
+    pub fn to_mermaid(&self) -> String {
+        crate::io::mermaid::to_mermaid(self, None)
+    }
+
+    /// Export graph as Mermaid with cycle nodes styled via a `cycle` class.
+    #[wasm_bindgen(js_name = toMermaidWithCycles)]
+    pub fn to_mermaid_with_cycles(&self) -> String {
+        use crate::algorithms::cycles::tarjan_scc;
+        crate::io::mermaid::to_mermaid(self, Some(&tarjan_scc(self)))
+    }
+
+    /// Export graph as directed GraphML (yEd, Gephi), node IDs as `label` data.
+    #[wasm_bindgen(js_name = toGraphml)]
+    pub fn to_graphml(&self) -> String {
+        crate::io::graphml::to_graphml(self, None)
+    }
+
+    /// Export GraphML with each node's SCC index as a `component` attribute.
+    #[wasm_bindgen(js_name = toGraphmlWithScc)]
+    pub fn to_graphml_with_scc(&self) -> String {
+        use crate::algorithms::cycles::tarjan_scc;
+        crate::io::graphml::to_graphml(self, Some(&tarjan_scc(self)))
+    }
+
+    /// Bulk-set node attributes from a JSON object keyed by node ID:
+    /// `{ "bv-1": { "status": "open", "priority": 2 } }`.
+    /// Returns the number of nodes updated.
+    #[wasm_bindgen(js_name = importNodeAttrs)]
+    pub fn import_node_attrs(&mut self, json: &str) -> Result<usize, JsError> {
+        crate::io::json::import_node_attrs(self, json).map_err(|e| JsError::new(&e))
+    }
+
+    /// Get a node attribute as a JS value (undefined if missing).
+    #[wasm_bindgen(js_name = nodeAttr)]
+    pub fn get_node_attr(&self, node: usize, key: &str) -> JsValue {
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        self.node_attr(node, key)
+            .and_then(|v| v.serialize(&serializer).ok())
+            .unwrap_or(JsValue::UNDEFINED)
+    }
+
     /// Import graph from JSON snapshot.
+    /// Edge endpoints may be node indices or node IDs; invalid edges are rejected.
     #[wasm_bindgen(js_name = fromJson)]
     pub fn from_json(json: &str) -> Result<DiGraph, JsError> {
-        let snapshot: GraphSnapshot =
-            serde_json::from_str(json).map_err(|e| JsError::new(&e.to_string()))?;
+        crate::io::json::from_json(json).map_err(|e| JsError::new(&e))
+    }
+
+    /// Import graph from DOT source (`digraph { a -> b; }` subset).
+    /// Node and edge attributes are ignored; errors name the offending line.
+    #[wasm_bindgen(js_name = fromDot)]
+    pub fn from_dot(dot: &str) -> Result<DiGraph, JsError> {
+        crate::io::dot::from_dot(dot).map_err(|e| JsError::new(&e))
+    }
+
+    /// Import graph from `source,target` CSV rows, creating nodes by label.
+    /// A `source,target` header row is optional; errors name the offending line.
+    #[wasm_bindgen(js_name = fromEdgeCsv)]
+    pub fn from_edge_csv(input: &str) -> Result<DiGraph, JsError> {
+        crate::io::csv::from_edge_csv(input).map_err(|e| JsError::new(&e))
+    }
+
+    /// Export edges as `source,target` CSV rows under a header.
+    /// Nodes without edges are not included.
+    #[wasm_bindgen(js_name = toEdgeCsv)]
+    pub fn to_edge_csv(&self) -> String {
+        crate::io::csv::to_edge_csv(self)
+    }
+
+    /// Encode the graph in the compact binary format (see `io::binary`),
+    /// e.g. to cache it in IndexedDB. Node indices are preserved.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        crate::io::binary::to_bytes(self)
+    }
+
+    /// Decode a graph produced by `toBytes`. Throws on corrupt data or an unknown version.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<DiGraph, JsError> {
+        crate::io::binary::from_bytes(bytes).map_err(|e| JsError::new(&e))
+    }
+
+    /// Tarjan SCCs in the compact binary format, for caching next to `toBytes`.
+    #[wasm_bindgen(js_name = sccToBytes)]
+    pub fn scc_to_bytes(&self) -> Vec<u8> {
+        crate::algorithms::cycles::tarjan_scc(self).to_bytes()
+    }
+
+    /// Decode `sccToBytes` output into `tarjanScc` JSON; throws on corrupt data.
+    #[wasm_bindgen(js_name = sccFromBytes)]
+    pub fn scc_from_bytes(bytes: &[u8]) -> Result<JsValue, JsError> {
+        let scc = SCCResult::from_bytes(bytes).map_err(|e| JsError::new(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&scc).unwrap_or(JsValue::NULL))
+    }
 
-        let mut graph = DiGraph::with_capacity(snapshot.nodes.len(), snapshot.edges.len());
-        for id in snapshot.nodes {
-            graph.add_node(&id);
+    /// `enumerateCycles` in the compact binary format.
+    #[wasm_bindgen(js_name = cyclesToBytes)]
+    pub fn cycles_to_bytes(&self, max_cycles: usize) -> Vec<u8> {
+        use crate::algorithms::cycles::enumerate_cycles_with_info;
+        enumerate_cycles_with_info(self, max_cycles).to_bytes()
+    }
+
+    /// Decode `cyclesToBytes` output into `enumerateCycles` JSON; throws on corrupt data.
+    #[wasm_bindgen(js_name = cyclesFromBytes)]
+    pub fn cycles_from_bytes(bytes: &[u8]) -> Result<JsValue, JsError> {
+        use crate::algorithms::cycles::CycleEnumerationResult;
+        let result = CycleEnumerationResult::from_bytes(bytes).map_err(|e| JsError::new(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL))
+    }
+
+    /// Return a new graph with every edge flipped, in O(V + E).
+    ///
+    /// Node IDs, indices, tombstones, node attributes, and edge weights and
+    /// kinds are preserved. For plain predecessor traversal there's no need
+    /// to copy: `predecessors` already reads the maintained reverse adjacency.
+    #[doc(alias = "transpose")]
+    pub fn reverse(&self) -> DiGraph {
+        let n = self.len();
+        let mut adj: Vec<Vec<u32>> = vec![Vec::new(); n];
+        let mut weights = self.weights.as_ref().map(|_| vec![Vec::new(); n]);
+        let mut kinds = self.kinds.as_ref().map(|_| vec![Vec::new(); n]);
+
+        for u in 0..n {
+            for (i, &v) in self.adj[u].iter().enumerate() {
+                let v = v as usize;
+                adj[v].push(u as u32);
+                if let (Some(new), Some(old)) = (&mut weights, &self.weights) {
+                    new[v].push(old[u][i]);
+                }
+                if let (Some(new), Some(old)) = (&mut kinds, &self.kinds) {
+                    new[v].push(old[u][i]);
+                }
+            }
         }
-        for (from, to) in snapshot.edges {
-            graph.add_edge(from, to);
+
+        DiGraph {
+            nodes: self.nodes.clone(),
+            node_index: self.node_index.clone(),
+            adj,
+            rev_adj: self.adj.clone(),
+            edge_count: self.edge_count,
+            weights,
+            kinds,
+            attrs: self.attrs.clone(),
+            removed: self.removed.clone(),
+            removed_count: self.removed_count,
+            members: self.members.clone(),
         }
-        Ok(graph)
     }
 
     /// Get successors of a node as JSON array of indices.
@@ -203,11 +1039,46 @@ impl DiGraph {
     pub fn topological_sort(&self) -> JsValue {
         use crate::algorithms::topo;
         match topo::topological_sort(self) {
-            Some(order) => serde_wasm_bindgen::to_value(&order).unwrap_or(JsValue::NULL),
-            None => JsValue::NULL,
+            Ok(order) => serde_wasm_bindgen::to_value(&order).unwrap_or(JsValue::NULL),
+            Err(_) => JsValue::NULL,
+        }
+    }
+
+    /// Layer of each node: longest path length from any source.
+    /// Returns JSON array of levels, or null if the graph has cycles.
+    #[wasm_bindgen(js_name = topologicalLevels)]
+    pub fn topological_levels(&self) -> JsValue {
+        use crate::algorithms::topo;
+        match topo::topological_levels(self) {
+            Ok(levels) => serde_wasm_bindgen::to_value(&levels).unwrap_or(JsValue::NULL),
+            Err(_) => JsValue::NULL,
         }
     }
 
+    /// Phase number of each node: SCC layers that must finish before it can start.
+    /// Unlike topologicalLevels this never fails; beads on a cycle share a phase.
+    #[wasm_bindgen(js_name = blockingDepth)]
+    pub fn blocking_depth(&self) -> JsValue {
+        use crate::algorithms::topo::blocking_depth;
+        serde_wasm_bindgen::to_value(&blocking_depth(self)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Dependency layers as `{ layers, layer_of }`; layer 0 has no prerequisites.
+    /// With `lenient`, cycle members share a layer; otherwise null on cycles.
+    #[wasm_bindgen(js_name = topologicalGenerations)]
+    pub fn topological_generations(&self, lenient: bool) -> JsValue {
+        use crate::algorithms::topo;
+        let generations = if lenient {
+            topo::topological_generations_lenient(self)
+        } else {
+            match topo::topological_generations(self) {
+                Ok(generations) => generations,
+                Err(_) => return JsValue::NULL,
+            }
+        };
+        serde_wasm_bindgen::to_value(&generations).unwrap_or(JsValue::NULL)
+    }
+
     /// Check if graph is a DAG (directed acyclic graph).
     #[wasm_bindgen(js_name = isDag)]
     pub fn is_dag(&self) -> bool {
@@ -239,6 +1110,79 @@ impl DiGraph {
         critical_path_length(self)
     }
 
+    /// Most expensive dependency chain; `costs[v]` is node effort (default 1 each).
+    /// Returns JSON: { nodes, total_cost, earliest_start }, or null if cyclic.
+    #[wasm_bindgen(js_name = criticalPath)]
+    pub fn critical_path(&self, costs: Option<Vec<f64>>) -> JsValue {
+        use crate::algorithms::paths::critical_path;
+        match critical_path(self, costs.as_deref()) {
+            Ok(path) => serde_wasm_bindgen::to_value(&path).unwrap_or(JsValue::NULL),
+            Err(_) => JsValue::NULL,
+        }
+    }
+
+    /// Longest non-repeating chain found with paths capped at `cutoff` nodes.
+    /// Bounded heuristic that also works on cyclic graphs. Returns JSON array.
+    #[wasm_bindgen(js_name = longestSimplePath)]
+    pub fn longest_simple_path(&self, cutoff: usize) -> JsValue {
+        use crate::algorithms::paths::longest_simple_path;
+        serde_wasm_bindgen::to_value(&longest_simple_path(self, cutoff)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Fewest-edge path from `from` to `to`, endpoints included.
+    /// Returns JSON array, or null if there is no path or an index is invalid.
+    #[wasm_bindgen(js_name = shortestPath)]
+    pub fn shortest_path(&self, from: usize, to: usize) -> JsValue {
+        use crate::algorithms::paths::shortest_path;
+        match shortest_path(self, from, to) {
+            Some(path) => serde_wasm_bindgen::to_value(&path).unwrap_or(JsValue::NULL),
+            None => JsValue::NULL,
+        }
+    }
+
+    /// Hop distance from `from` to each node; null entries are unreachable.
+    #[wasm_bindgen(js_name = shortestPathLengths)]
+    pub fn shortest_path_lengths(&self, from: usize) -> JsValue {
+        use crate::algorithms::paths::shortest_path_lengths;
+        serde_wasm_bindgen::to_value(&shortest_path_lengths(self, from)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Every simple path from `from` to `to`, up to `max_paths` paths of at most `max_len` edges.
+    /// Returns JSON: { paths: number[][], truncated, count }
+    #[wasm_bindgen(js_name = allSimplePaths)]
+    pub fn all_simple_paths(
+        &self,
+        from: usize,
+        to: usize,
+        max_paths: usize,
+        max_len: usize,
+    ) -> JsValue {
+        use crate::algorithms::paths::all_simple_paths;
+        let result = all_simple_paths(self, from, to, max_paths, max_len);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
+    /// Cheapest paths from `source` by edge weight; stops early once `target` is settled.
+    /// Returns JSON: { dist: (number|null)[], pred: (number|null)[] }.
+    /// Throws on a negative or NaN weight, or an invalid source.
+    #[wasm_bindgen(js_name = dijkstra)]
+    pub fn dijkstra(&self, source: usize, target: Option<usize>) -> Result<JsValue, JsError> {
+        use crate::algorithms::paths::dijkstra;
+        let result = dijkstra(self, source, target).map_err(|e| JsError::new(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL))
+    }
+
+    /// Shortest distances from `source` by edge weight, negative weights allowed.
+    /// Returns JSON: (number|null)[]. Throws, listing the nodes, if a negative
+    /// cycle is reachable from `source`.
+    #[wasm_bindgen(js_name = bellmanFord)]
+    pub fn bellman_ford(&self, source: usize) -> Result<JsValue, JsError> {
+        use crate::algorithms::negative_cycle::bellman_ford;
+        let dist = bellman_ford(self, source)
+            .map_err(|cycle| JsError::new(&format!("negative cycle through nodes {:?}", cycle)))?;
+        Ok(serde_wasm_bindgen::to_value(&dist).unwrap_or(JsValue::NULL))
+    }
+
     /// Compute PageRank scores for all nodes.
     /// Returns array of scores in node index order.
     #[wasm_bindgen(js_name = pagerank)]
@@ -253,6 +1197,20 @@ impl DiGraph {
         serde_wasm_bindgen::to_value(&scores).unwrap_or(JsValue::NULL)
     }
 
+    /// PageRank with convergence details.
+    /// Returns JSON: { scores: number[], iterations: number, converged: boolean }
+    #[wasm_bindgen(js_name = pagerankWithInfo)]
+    pub fn pagerank_with_info(&self, damping: f64, max_iterations: u32) -> JsValue {
+        use crate::algorithms::pagerank::{pagerank_with_info, PageRankConfig};
+        let config = PageRankConfig {
+            damping,
+            max_iterations,
+            tolerance: 1e-6,
+        };
+        let result = pagerank_with_info(self, &config);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
     /// Compute PageRank with default parameters (damping=0.85, max_iterations=100).
     #[wasm_bindgen(js_name = pagerankDefault)]
     pub fn pagerank_default(&self) -> JsValue {
@@ -292,6 +1250,48 @@ impl DiGraph {
         serde_wasm_bindgen::to_value(&scores).unwrap_or(JsValue::NULL)
     }
 
+    /// Exact betweenness, optionally divided by (n-1)(n-2) to fall in [0, 1].
+    /// Returns array of scores in node index order.
+    #[wasm_bindgen(js_name = betweennessCentrality)]
+    pub fn betweenness_centrality(&self, normalized: bool) -> JsValue {
+        use crate::algorithms::betweenness::betweenness_centrality;
+        let scores = betweenness_centrality(self, normalized);
+        serde_wasm_bindgen::to_value(&scores).unwrap_or(JsValue::NULL)
+    }
+
+    /// The k nodes with the highest exact betweenness, for the sidebar.
+    /// Returns JSON: [[node, score], ...], highest first.
+    #[wasm_bindgen(js_name = topBetweenness)]
+    pub fn top_betweenness(&self, k: usize) -> JsValue {
+        use crate::algorithms::betweenness::top_betweenness;
+        serde_wasm_bindgen::to_value(&top_betweenness(self, k)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Jaccard similarity of neighbor sets for a flat [a, b, a, b, ...] array.
+    /// Returns one score per pair; throws if the array has odd length.
+    #[wasm_bindgen(js_name = jaccardSimilarity)]
+    pub fn jaccard_similarity(&self, pairs: &[usize]) -> Result<Vec<f64>, JsError> {
+        use crate::algorithms::similarity::jaccard_similarity;
+        let pairs = flat_pairs(pairs).map_err(|e| JsError::new(&e))?;
+        Ok(jaccard_similarity(self, &pairs))
+    }
+
+    /// Link suggestions: the k nodes whose neighbors overlap most with `node`'s.
+    /// Returns JSON: [[node, score], ...], highest first.
+    #[wasm_bindgen(js_name = topSimilar)]
+    pub fn top_similar(&self, node: usize, k: usize) -> JsValue {
+        use crate::algorithms::similarity::top_similar;
+        serde_wasm_bindgen::to_value(&top_similar(self, node, k)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Closeness centrality over outgoing edges (Wasserman–Faust scaling).
+    /// Returns array of scores in node index order.
+    #[wasm_bindgen(js_name = closeness)]
+    pub fn closeness(&self) -> JsValue {
+        use crate::algorithms::closeness::closeness_centrality;
+        serde_wasm_bindgen::to_value(&closeness_centrality(self)).unwrap_or(JsValue::NULL)
+    }
+
     /// Compute approximate betweenness centrality using sampling.
     /// Returns array of scores in node index order.
     /// Error: O(1/sqrt(k)) - with k=100, ~10% error in ranking.
@@ -303,7 +1303,8 @@ impl DiGraph {
     }
 
     /// Compute HITS hub and authority scores.
-    /// Returns JSON object: { hubs: number[], authorities: number[], iterations: number }
+    /// Returns JSON object:
+    /// { hubs: number[], authorities: number[], iterations: number, converged: boolean }
     #[wasm_bindgen(js_name = hits)]
     pub fn hits(&self, tolerance: f64, max_iterations: u32) -> JsValue {
         use crate::algorithms::hits::{hits, HITSConfig};
@@ -340,6 +1341,36 @@ impl DiGraph {
         degeneracy(self)
     }
 
+    /// 2-color the undirected view of the graph.
+    /// Returns array of booleans (side per node, null for removed nodes), or
+    /// null if not bipartite.
+    #[wasm_bindgen(js_name = isBipartite)]
+    pub fn is_bipartite(&self) -> JsValue {
+        use crate::algorithms::coloring::is_bipartite;
+        let colors = is_bipartite(self);
+        serde_wasm_bindgen::to_value(&colors).unwrap_or(JsValue::NULL)
+    }
+
+    /// Greedy (Welsh-Powell) coloring of the undirected view; adjacent nodes differ.
+    /// Removed nodes get color `usize::MAX`.
+    /// Returns JSON: { colors: number[], color_count: number }
+    #[wasm_bindgen(js_name = greedyColoring)]
+    pub fn greedy_coloring(&self) -> JsValue {
+        use crate::algorithms::coloring::greedy_coloring_with_info;
+        let result = greedy_coloring_with_info(self);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
+    /// Maximum matching between two node groups, e.g. beads and owners.
+    /// Edges between the groups count in either direction.
+    /// Returns JSON: [[left, right], ...] in `left` order.
+    #[wasm_bindgen(js_name = maxBipartiteMatching)]
+    pub fn max_bipartite_matching(&self, left: &[usize], right: &[usize]) -> JsValue {
+        use crate::algorithms::matching::max_bipartite_matching;
+        let pairs = max_bipartite_matching(self, left, right);
+        serde_wasm_bindgen::to_value(&pairs).unwrap_or(JsValue::NULL)
+    }
+
     /// Find articulation points (cut vertices) in the graph.
     /// These are nodes whose removal disconnects the graph.
     /// Returns array of node indices.
@@ -369,6 +1400,25 @@ impl DiGraph {
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
 
+    /// Indices into tarjanScc().components in dependency order, so phase 0
+    /// depends on nothing. Returns JSON array of component indices.
+    #[wasm_bindgen(js_name = sccTopologicalOrder)]
+    pub fn scc_topological_order(&self) -> JsValue {
+        use crate::algorithms::cycles::tarjan_scc;
+        use crate::algorithms::topo::scc_topological_order;
+        let order = scc_topological_order(self, &tarjan_scc(self));
+        serde_wasm_bindgen::to_value(&order).unwrap_or(JsValue::NULL)
+    }
+
+    /// SCCs over edges whose kind bit is set in `kind_mask`.
+    /// Returns JSON: { components: number[][], has_cycles: bool, cycle_count: number }
+    #[wasm_bindgen(js_name = tarjanSccMasked)]
+    pub fn tarjan_scc_masked(&self, kind_mask: u32) -> JsValue {
+        use crate::algorithms::cycles::tarjan_scc_masked;
+        let result = tarjan_scc_masked(self, kind_mask);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
     /// Check if graph has any cycles.
     #[wasm_bindgen(js_name = hasCycles)]
     pub fn has_cycles(&self) -> bool {
@@ -385,6 +1435,69 @@ impl DiGraph {
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
 
+    /// Enumerate cycles, skipping SCCs with more than `max_nodes_per_scc` nodes.
+    /// Returns JSON: { cycles, truncated, count, skipped_sccs? } where skipped_sccs
+    /// (present only when non-empty) indexes tarjanScc().components.
+    #[wasm_bindgen(js_name = enumerateCyclesBounded)]
+    pub fn enumerate_cycles_bounded(&self, max_cycles: usize, max_nodes_per_scc: usize) -> JsValue {
+        use crate::algorithms::cycles::enumerate_cycles_bounded;
+        let result = enumerate_cycles_bounded(self, max_cycles, max_nodes_per_scc);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
+    /// Enumerate elementary cycles over edges whose kind bit is set in `kind_mask`.
+    /// Returns JSON: { cycles: number[][], truncated: bool, count: number }
+    #[wasm_bindgen(js_name = enumerateCyclesMasked)]
+    pub fn enumerate_cycles_masked(&self, max_cycles: usize, kind_mask: u32) -> JsValue {
+        use crate::algorithms::cycles::{enumerate_cycles_masked, CycleEnumerationResult};
+        let cycles = enumerate_cycles_masked(self, max_cycles, kind_mask);
+        let count = cycles.len();
+        let result = CycleEnumerationResult {
+            cycles,
+            truncated: count >= max_cycles,
+            count,
+            skipped_sccs: Vec::new(),
+        };
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
+    /// Enumerate elementary cycles passing through a specific node.
+    /// Returns JSON array of cycles, each starting with the given node.
+    #[wasm_bindgen(js_name = cyclesThroughNode)]
+    pub fn cycles_through_node(&self, node: usize, max_cycles: usize) -> JsValue {
+        use crate::algorithms::cycles::cycles_through_node;
+        let cycles = cycles_through_node(self, node, max_cycles);
+        serde_wasm_bindgen::to_value(&cycles).unwrap_or(JsValue::NULL)
+    }
+
+    /// Enumerate elementary cycles using node IDs instead of indices.
+    /// Returns JSON: { cycles: string[][], truncated: bool, count: number }
+    #[wasm_bindgen(js_name = enumerateCyclesLabeled)]
+    pub fn enumerate_cycles_labeled(&self, max_cycles: usize) -> JsValue {
+        use crate::algorithms::cycles::enumerate_cycles_labeled;
+        let result = enumerate_cycles_labeled(self, max_cycles);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
+    /// Enumerate elementary cycles as ordered edge lists.
+    /// Returns JSON array of cycles, each an array of [from, to] pairs
+    /// ending with the closing edge back to the first node.
+    #[wasm_bindgen(js_name = enumerateCycleEdges)]
+    pub fn enumerate_cycle_edges(&self, max_cycles: usize) -> JsValue {
+        use crate::algorithms::cycles::enumerate_cycle_edges;
+        let result = enumerate_cycle_edges(self, max_cycles);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
+    /// Estimate the number of elementary cycles without full enumeration.
+    /// Returns JSON: { lower_bound: number, estimate: number, exact: bool }
+    #[wasm_bindgen(js_name = estimateCycleCount)]
+    pub fn estimate_cycle_count(&self, samples: usize, seed: u64) -> JsValue {
+        use crate::algorithms::cycles::estimate_cycle_count;
+        let result = estimate_cycle_count(self, samples, seed);
+        serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    }
+
     /// Suggest edges to remove to break cycles.
     /// Returns JSON: { suggestions: [{from, to, cycles_broken, collateral, from_id, to_id}], total_cycles, truncated }
     /// Suggestions are sorted by cycles_broken desc, then collateral asc.
@@ -395,6 +1508,33 @@ impl DiGraph {
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
 
+    /// Rank edges by how many enumerated cycles they appear in.
+    /// Returns JSON array of { from, to, cycle_count, fraction, partial }.
+    #[wasm_bindgen(js_name = edgeCycleParticipation)]
+    pub fn edge_cycle_participation(&self, max_cycles: usize) -> JsValue {
+        use crate::algorithms::cycles::edge_cycle_participation;
+        let scores = edge_cycle_participation(self, max_cycles);
+        serde_wasm_bindgen::to_value(&scores).unwrap_or(JsValue::NULL)
+    }
+
+    /// Heuristic feedback arc set: edges whose removal makes the graph acyclic.
+    /// Not guaranteed minimal. Returns array of [from, to] pairs.
+    #[wasm_bindgen(js_name = feedbackArcSet)]
+    pub fn feedback_arc_set(&self) -> JsValue {
+        use crate::algorithms::cycles::feedback_arc_set;
+        let arcs = feedback_arc_set(self);
+        serde_wasm_bindgen::to_value(&arcs).unwrap_or(JsValue::NULL)
+    }
+
+    /// Heuristic feedback vertex set: nodes whose removal makes the graph acyclic.
+    /// Not guaranteed minimal. Returns sorted array of node indices.
+    #[wasm_bindgen(js_name = feedbackVertexSet)]
+    pub fn feedback_vertex_set(&self) -> JsValue {
+        use crate::algorithms::cycles::feedback_vertex_set;
+        let nodes = feedback_vertex_set(self);
+        serde_wasm_bindgen::to_value(&nodes).unwrap_or(JsValue::NULL)
+    }
+
     /// Quick cycle break suggestions (faster, less precise).
     /// Only uses SCC membership without full cycle enumeration.
     /// Returns JSON array of { from, to, collateral, from_id, to_id }.
@@ -472,7 +1612,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = parallelCutSuggestions)]
     pub fn parallel_cut_suggestions(&self, closed_set: &[u8], limit: usize) -> JsValue {
         use crate::algorithms::parallel_cut::parallel_cut_suggestions;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let result = parallel_cut_suggestions(self, &closed, limit);
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
@@ -482,7 +1622,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = parallelCutDefault)]
     pub fn parallel_cut_default(&self, closed_set: &[u8]) -> JsValue {
         use crate::algorithms::parallel_cut::parallel_cut_default;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let result = parallel_cut_default(self, &closed);
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
@@ -493,7 +1633,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = unblockRanking)]
     pub fn unblock_ranking(&self, closed_set: &[u8], limit: usize) -> JsValue {
         use crate::algorithms::parallel_cut::unblock_ranking;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let result = unblock_ranking(self, &closed, limit);
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
@@ -501,11 +1641,17 @@ impl DiGraph {
     /// Extract a subgraph containing only the specified node indices.
     /// Returns a new DiGraph with renumbered indices.
     #[wasm_bindgen(js_name = subgraph)]
-    pub fn subgraph(&self, indices: &[usize]) -> DiGraph {
+    pub fn extract_subgraph(&self, indices: &[usize]) -> DiGraph {
         use crate::algorithms::subgraph::extract_subgraph;
         extract_subgraph(self, indices)
     }
 
+    /// Original index of each node of `subgraph(indices)`, in new-index order.
+    #[wasm_bindgen(js_name = subgraphMapping)]
+    pub fn subgraph_mapping(&self, indices: &[usize]) -> Vec<usize> {
+        self.subgraph(indices).1
+    }
+
     /// Get all node indices reachable from a source node (outgoing direction).
     #[wasm_bindgen(js_name = reachableFrom)]
     pub fn reachable_from(&self, source: usize) -> JsValue {
@@ -522,6 +1668,103 @@ impl DiGraph {
         serde_wasm_bindgen::to_value(&nodes).unwrap_or(JsValue::NULL)
     }
 
+    /// Build a reachability index for O(1) `reaches(from, to)` queries.
+    /// Throws if it would need more than `max_bytes` (see closureMemoryEstimate).
+    #[wasm_bindgen(js_name = transitiveClosure)]
+    pub fn transitive_closure(
+        &self,
+        max_bytes: usize,
+    ) -> Result<crate::algorithms::reachability::ReachabilityIndex, JsError> {
+        use crate::algorithms::reachability::transitive_closure_within;
+        transitive_closure_within(self, max_bytes).map_err(|e| JsError::new(&e))
+    }
+
+    /// Edges implied by longer dependency chains, e.g. A->C when A->B->C exists.
+    /// Edges inside a cycle are never reported. Throws if the reachability
+    /// index would need more than `max_bytes` (see closureMemoryEstimate).
+    /// Returns JSON: { redundant: [[from, to], ...], intra_scc_edges }
+    #[wasm_bindgen(js_name = transitiveReduction)]
+    pub fn transitive_reduction(&self, max_bytes: usize) -> Result<JsValue, JsError> {
+        use crate::algorithms::reachability::transitive_reduction_within;
+        let result = transitive_reduction_within(self, max_bytes).map_err(|e| JsError::new(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL))
+    }
+
+    /// Remove every edge reported by transitiveReduction; returns how many were removed.
+    /// Throws, leaving the graph unchanged, if the index exceeds `max_bytes`.
+    #[wasm_bindgen(js_name = removeRedundantEdges)]
+    pub fn remove_redundant_edges(&mut self, max_bytes: usize) -> Result<usize, JsError> {
+        use crate::algorithms::reachability::transitive_reduction_within;
+        let result = transitive_reduction_within(self, max_bytes).map_err(|e| JsError::new(&e))?;
+        Ok(result.apply(self))
+    }
+
+    /// Nearest shared blockers of two nodes: common ancestors with no common
+    /// ancestor below them. Returns sorted array of node indices (may be empty).
+    #[wasm_bindgen(js_name = lca)]
+    pub fn lca(&self, a: usize, b: usize) -> JsValue {
+        use crate::algorithms::reachability::lca;
+        serde_wasm_bindgen::to_value(&lca(self, a, b)).unwrap_or(JsValue::NULL)
+    }
+
+    /// `lca` for a flat [a, b, a, b, ...] array. Returns one array per pair;
+    /// throws if the array has odd length.
+    #[wasm_bindgen(js_name = lcaBatch)]
+    pub fn lca_batch(&self, pairs: &[usize]) -> Result<JsValue, JsError> {
+        use crate::algorithms::reachability::lca_batch;
+        let pairs = flat_pairs(pairs).map_err(|e| JsError::new(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&lca_batch(self, &pairs)).unwrap_or(JsValue::NULL))
+    }
+
+    /// Lint for a DAG: beads where work fans out and converges again.
+    /// Returns JSON: [[apex, sink], ...], empty if the graph has a cycle.
+    #[wasm_bindgen(js_name = diamonds)]
+    pub fn diamonds(&self) -> JsValue {
+        use crate::algorithms::diamonds::diamonds;
+        serde_wasm_bindgen::to_value(&diamonds(self)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Lint for a DAG: edges implied by a longer path, without modifying the graph.
+    /// Throws if the reachability index would need more than `max_bytes`.
+    /// Returns JSON: [[from, to], ...], or null if the graph has a cycle.
+    #[wasm_bindgen(js_name = redundantEdges)]
+    pub fn redundant_edges(&self, max_bytes: usize) -> Result<JsValue, JsError> {
+        use crate::algorithms::reachability::redundant_edges_within;
+        if !self.is_dag() {
+            return Ok(JsValue::NULL);
+        }
+        let edges = redundant_edges_within(self, max_bytes).map_err(|e| JsError::new(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&edges).unwrap_or(JsValue::NULL))
+    }
+
+    /// Estimated bytes needed by transitiveClosure for this graph.
+    #[wasm_bindgen(js_name = closureMemoryEstimate)]
+    pub fn closure_memory_estimate(&self) -> usize {
+        crate::algorithms::reachability::estimate_closure_bytes(self)
+    }
+
+    /// Data-quality check: live nodes no source reaches, such as cycles with
+    /// no way in. Returns JSON array of node indices, sorted.
+    #[wasm_bindgen(js_name = unreachableFromSources)]
+    pub fn unreachable_from_sources(&self) -> JsValue {
+        use crate::algorithms::reachability::unreachable_from_sources;
+        serde_wasm_bindgen::to_value(&unreachable_from_sources(self)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Everything the node transitively depends on, sorted, excluding the node.
+    #[wasm_bindgen(js_name = ancestors)]
+    pub fn ancestors(&self, node: usize) -> JsValue {
+        use crate::algorithms::reachability::ancestors;
+        serde_wasm_bindgen::to_value(&ancestors(self, node)).unwrap_or(JsValue::NULL)
+    }
+
+    /// Everything that transitively depends on the node, sorted, excluding the node.
+    #[wasm_bindgen(js_name = descendants)]
+    pub fn descendants(&self, node: usize) -> JsValue {
+        use crate::algorithms::reachability::descendants;
+        serde_wasm_bindgen::to_value(&descendants(self, node)).unwrap_or(JsValue::NULL)
+    }
+
     /// Get all nodes in the dependency cone (ancestors + node + descendants).
     #[wasm_bindgen(js_name = dependencyCone)]
     pub fn dependency_cone(&self, node: usize) -> JsValue {
@@ -530,6 +1773,64 @@ impl DiGraph {
         serde_wasm_bindgen::to_value(&nodes).unwrap_or(JsValue::NULL)
     }
 
+    /// Induced subgraph of everything reachable from `roots` in one call.
+    /// Node IDs are kept, so use `nodeIds()` to map results back.
+    #[wasm_bindgen(js_name = reachableSubgraph)]
+    pub fn reachable_subgraph(
+        &self,
+        roots: &[usize],
+        direction: crate::algorithms::subgraph::Direction,
+    ) -> DiGraph {
+        use crate::algorithms::subgraph::reachable_subgraph;
+        reachable_subgraph(self, roots, direction).0
+    }
+
+    /// Hop distance from the nearest of `sources` to every node, following edges
+    /// in `direction` (`Both` ignores direction). Returns an Int32Array with -1
+    /// for unreachable nodes, to skip JSON for large heat maps.
+    #[wasm_bindgen(js_name = bfsDistances)]
+    pub fn bfs_distances(
+        &self,
+        sources: &[usize],
+        direction: crate::algorithms::subgraph::Direction,
+    ) -> Vec<i32> {
+        use crate::algorithms::paths::bfs_distances;
+        bfs_distances(self, sources, direction)
+            .into_iter()
+            .map(|d| d.map_or(-1, |d| d as i32))
+            .collect()
+    }
+
+    /// Count distinct simple paths between two nodes.
+    /// Errors if a cycle lies on a connecting path (the count would be infinite).
+    /// Counts above 2^53 lose precision in JS.
+    #[wasm_bindgen(js_name = countPaths)]
+    pub fn count_paths(&self, from: usize, to: usize) -> Result<f64, JsError> {
+        use crate::algorithms::paths::count_paths;
+        count_paths(self, from, to)
+            .map(|count| count as f64)
+            .map_err(JsError::new)
+    }
+
+    /// Compute immediate dominators of every node reachable from root.
+    /// Returns array where entry i is the immediate dominator of node i
+    /// (the root maps to itself, unreachable nodes are null).
+    #[wasm_bindgen(js_name = dominators)]
+    pub fn dominators(&self, root: usize) -> JsValue {
+        use crate::algorithms::dominators::dominators;
+        let idom = dominators(self, root).idom;
+        serde_wasm_bindgen::to_value(&idom).unwrap_or(JsValue::NULL)
+    }
+
+    /// Nodes that can only be reached from root through `node`, sorted.
+    /// Returns array of node indices (empty if `node` is unreachable from root).
+    #[wasm_bindgen(js_name = dominatedBy)]
+    pub fn dominated_by(&self, root: usize, node: usize) -> JsValue {
+        use crate::algorithms::dominators::dominators;
+        let nodes = dominators(self, root).dominated_by(node);
+        serde_wasm_bindgen::to_value(&nodes).unwrap_or(JsValue::NULL)
+    }
+
     // ========================================================================
     // Actionable queries (work with closed_set to determine workable items)
     // ========================================================================
@@ -557,7 +1858,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = actionableNodes)]
     pub fn actionable_nodes(&self, closed_set: &[u8]) -> JsValue {
         use crate::reachability::actionable_nodes;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let nodes = actionable_nodes(self, &closed);
         serde_wasm_bindgen::to_value(&nodes).unwrap_or(JsValue::NULL)
     }
@@ -567,7 +1868,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = openBlockers)]
     pub fn open_blockers(&self, node: usize, closed_set: &[u8]) -> JsValue {
         use crate::reachability::open_blockers;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let nodes = open_blockers(self, node, &closed);
         serde_wasm_bindgen::to_value(&nodes).unwrap_or(JsValue::NULL)
     }
@@ -577,7 +1878,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = openBlockerCount)]
     pub fn open_blocker_count(&self, node: usize, closed_set: &[u8]) -> usize {
         use crate::reachability::open_blocker_count;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         open_blocker_count(self, node, &closed)
     }
 
@@ -591,7 +1892,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = whatIfClose)]
     pub fn what_if_close(&self, node: usize, closed_set: &[u8]) -> JsValue {
         use crate::whatif::what_if_close;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let result = what_if_close(self, node, &closed);
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
@@ -601,7 +1902,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = whatIfCloseBatch)]
     pub fn what_if_close_batch(&self, nodes: &[usize], closed_set: &[u8]) -> JsValue {
         use crate::whatif::what_if_close_batch;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let result = what_if_close_batch(self, nodes, &closed);
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
@@ -612,7 +1913,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = topWhatIf)]
     pub fn top_what_if(&self, closed_set: &[u8], limit: usize) -> JsValue {
         use crate::whatif::top_what_if;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let results = top_what_if(self, &closed, limit);
         serde_wasm_bindgen::to_value(&results).unwrap_or(JsValue::NULL)
     }
@@ -623,11 +1924,23 @@ impl DiGraph {
     #[wasm_bindgen(js_name = allWhatIf)]
     pub fn all_what_if(&self, closed_set: &[u8], limit: usize) -> JsValue {
         use crate::whatif::all_what_if;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let results = all_what_if(self, &closed, limit);
         serde_wasm_bindgen::to_value(&results).unwrap_or(JsValue::NULL)
     }
 
+    /// What-if analysis: which cycles disappear if the given edges are removed.
+    /// removals is a flat array of [from, to, from, to, ...] pairs; throws if
+    /// it has odd length.
+    /// Returns JSON: { cycle_count, has_cycles, freed_nodes, ignored_removals }
+    #[wasm_bindgen(js_name = simulateEdgeRemoval)]
+    pub fn simulate_edge_removal(&self, removals: &[usize]) -> Result<JsValue, JsError> {
+        use crate::whatif::simulate_edge_removal;
+        let pairs = flat_pairs(removals).map_err(|e| JsError::new(&e))?;
+        let result = simulate_edge_removal(self, &pairs);
+        Ok(serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL))
+    }
+
     // ========================================================================
     // TopK Set (greedy submodular selection for maximum unlock)
     // ========================================================================
@@ -639,7 +1952,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = topkSet)]
     pub fn topk_set(&self, closed_set: &[u8], k: usize) -> JsValue {
         use crate::algorithms::topk_set::topk_set;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let result = topk_set(self, &closed, k);
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
@@ -649,7 +1962,7 @@ impl DiGraph {
     #[wasm_bindgen(js_name = topkSetDefault)]
     pub fn topk_set_default(&self, closed_set: &[u8]) -> JsValue {
         use crate::algorithms::topk_set::topk_set_default;
-        let closed: Vec<bool> = closed_set.iter().map(|&b| b != 0).collect();
+        let closed = self.closed_mask(closed_set);
         let result = topk_set_default(self, &closed);
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
@@ -657,22 +1970,484 @@ impl DiGraph {
 
 // Internal methods (not exposed to WASM)
 impl DiGraph {
-    /// Get successors slice (internal use).
-    pub(crate) fn successors_slice(&self, node: usize) -> &[usize] {
+    /// Validate both endpoints of an edge about to be added, returning
+    /// them in storage form.
+    fn check_endpoints(&self, from: usize, to: usize) -> Result<(NodeId, NodeId), GraphError> {
+        for index in [from, to] {
+            if index >= self.nodes.len() {
+                let node_count = self.nodes.len();
+                return Err(GraphError::NodeOutOfRange { index, node_count });
+            }
+            if self.removed[index] {
+                return Err(GraphError::NodeRemoved { index });
+            }
+        }
+        Ok((NodeId::try_from(from)?, NodeId::try_from(to)?))
+    }
+
+    /// Position of `to` in `from`'s successor list; the edge must exist.
+    fn edge_position(&self, from: usize, to: usize) -> usize {
+        self.adj[from]
+            .iter()
+            .position(|&w| w as usize == to)
+            .expect("edge was just added")
+    }
+
+    /// Add a node like `try_add_node`, returning its index.
+    ///
+    /// # Panics
+    /// Once the graph holds 2^32 nodes; use `try_add_node` to handle that.
+    pub fn add_node(&mut self, id: &str) -> usize {
+        self.try_add_node(id).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// The edge-reversed graph as a fresh owned copy; same as `reverse`.
+    pub fn reversed(&self) -> DiGraph {
+        self.reverse()
+    }
+
+    /// Snapshot the adjacency in CSR layout for read-only analysis.
+    ///
+    /// The snapshot does not follow later mutations; freeze again after editing.
+    pub fn freeze(&self) -> crate::csr::CsrGraph {
+        crate::csr::CsrGraph::from(self)
+    }
+
+    /// Nodes that `node` points to, in insertion order (empty if out of range).
+    ///
+    /// Borrows the adjacency storage, which holds indices as u32 to halve its
+    /// memory; widen with `as usize`, or use `out_neighbors` for `usize` items.
+    pub fn successors_slice(&self, node: usize) -> &[u32] {
         self.adj.get(node).map_or(&[], |v| v.as_slice())
     }
 
-    /// Get predecessors slice (internal use).
-    pub(crate) fn predecessors_slice(&self, node: usize) -> &[usize] {
+    /// Nodes pointing to `node` (empty if out of range).
+    ///
+    /// Backed by the reverse adjacency, which `add_edge`/`remove_edge` keep in
+    /// step with the forward lists, so this is a borrow rather than a scan.
+    /// Indices are u32 as in `successors_slice`; `in_neighbors` yields `usize`.
+    pub fn predecessors_slice(&self, node: usize) -> &[u32] {
         self.rev_adj.get(node).map_or(&[], |v| v.as_slice())
     }
 
-    /// Iterate over all edges (internal use).
-    pub(crate) fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+    /// Successors of `node` as `usize` indices, in `successors_slice` order.
+    pub fn out_neighbors(&self, node: usize) -> impl Iterator<Item = usize> + Clone + '_ {
+        self.successors_slice(node).iter().map(|&w| w as usize)
+    }
+
+    /// Predecessors of `node` as `usize` indices, in `predecessors_slice` order.
+    pub fn in_neighbors(&self, node: usize) -> impl Iterator<Item = usize> + Clone + '_ {
+        self.predecessors_slice(node).iter().map(|&u| u as usize)
+    }
+
+    /// Neighbors of `node` in the undirected view: successors and
+    /// predecessors together, sorted and deduplicated, without `node` itself.
+    /// Empty for removed or out-of-range nodes.
+    pub fn undirected_neighbors(&self, node: usize) -> Vec<usize> {
+        let mut ns: Vec<usize> = self
+            .out_neighbors(node)
+            .chain(self.in_neighbors(node))
+            .filter(|&w| w != node)
+            .collect();
+        ns.sort_unstable();
+        ns.dedup();
+        ns
+    }
+
+    /// Iterate over (node, label) pairs of live nodes, ascending by index.
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|&(v, _)| !self.is_removed(v))
+            .map(|(v, id)| (v, id.as_str()))
+    }
+
+    /// Iterate over all edges as (from, to) pairs.
+    ///
+    /// Ordered by source index, then by `successors_slice` order, which is
+    /// also the order `toJson` and `edge_weights` use.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
         self.adj
             .iter()
             .enumerate()
-            .flat_map(|(from, tos)| tos.iter().map(move |&to| (from, to)))
+            .flat_map(|(from, tos)| tos.iter().map(move |&to| (from, to as usize)))
+    }
+
+    /// Iterate over (successor, weight) pairs of a node, in `successors_slice` order.
+    /// Unweighted edges have weight 1.0.
+    pub fn weighted_successors(&self, node: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let weights = self.weights.as_ref().and_then(|w| w.get(node));
+        self.successors_slice(node)
+            .iter()
+            .enumerate()
+            .map(move |(i, &to)| (to as usize, weights.map_or(1.0, |w| w[i])))
+    }
+
+    /// Edge weights in `edges()` order (by source index, then successor order).
+    pub fn edge_weights(&self) -> Vec<f64> {
+        (0..self.len())
+            .flat_map(|u| self.weighted_successors(u).map(|(_, w)| w))
+            .collect()
+    }
+
+    /// Export graph as JSON snapshot, tagging each node with its component
+    /// id and whether it lies on a cycle.
+    ///
+    /// The output is still accepted by `from_json` (annotations are ignored on import).
+    /// Nodes missing from `scc` (e.g. a result computed on a smaller graph)
+    /// get component `usize::MAX` and `in_cycle: false`.
+    pub fn to_json_annotated(&self, scc: &SCCResult) -> String {
+        let mut annotations = vec![
+            NodeAnnotation {
+                component: usize::MAX,
+                in_cycle: false,
+            };
+            self.len()
+        ];
+        for (id, component) in scc.components.iter().enumerate() {
+            let cyclic =
+                component.len() > 1 || component.first().is_some_and(|&v| self.has_edge(v, v));
+            for &v in component {
+                if let Some(a) = annotations.get_mut(v) {
+                    *a = NodeAnnotation {
+                        component: id,
+                        in_cycle: cyclic,
+                    };
+                }
+            }
+        }
+
+        let snapshot = GraphSnapshot {
+            nodes: self.nodes.clone(),
+            edges: self.edges_vec(),
+            weights: self.weights.as_ref().map(|_| self.edge_weights()),
+            kinds: self.kinds_vec(),
+            attrs: self.attrs_vec(),
+            removed: self.removed_vec(),
+            annotations: Some(annotations),
+        };
+        serde_json::to_string(&snapshot).unwrap_or_default()
+    }
+
+    /// Kind of edge from -> to (`EdgeKind::BLOCKS` for plain edges), or None if absent.
+    pub fn edge_kind(&self, from: usize, to: usize) -> Option<EdgeKind> {
+        let pos = self.adj.get(from)?.iter().position(|&w| w as usize == to)?;
+        Some(self.kinds.as_ref().map_or(EdgeKind::default(), |kinds| kinds[from][pos]))
+    }
+
+    /// Successors reached via edges whose kind is selected by `kind_mask`.
+    pub fn successors_of_kind(
+        &self,
+        node: usize,
+        kind_mask: u32,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let kinds = self.kinds.as_ref().and_then(|k| k.get(node));
+        self.successors_slice(node)
+            .iter()
+            .enumerate()
+            .filter(move |&(i, _)| {
+                let kind = kinds.map_or(EdgeKind::default(), |k| k[i]);
+                kind.mask() & kind_mask != 0
+            })
+            .map(|(_, &to)| to as usize)
+    }
+
+    /// Look up a node index by label.
+    ///
+    /// Labels are unique: `add_node` with an existing label returns the
+    /// index of the first insertion instead of creating a duplicate, so
+    /// this always resolves to that first index.
+    pub fn node_index(&self, label: &str) -> Option<usize> {
+        self.node_index.get(label).copied()
+    }
+
+    /// Original nodes replaced by `supernode` via `contract`, in index order
+    /// (empty if it isn't a supernode).
+    ///
+    /// Members are tombstones, so their labels are gone; callers that need
+    /// them should keep the labels from before the contraction.
+    pub fn members_of(&self, supernode: usize) -> &[usize] {
+        self.members.get(&supernode).map_or(&[], |v| v.as_slice())
+    }
+
+    /// Borrow the label of a node (see `node_id` for the owned wasm variant).
+    pub fn label(&self, node: usize) -> Option<&str> {
+        if self.is_removed(node) {
+            return None;
+        }
+        self.nodes.get(node).map(String::as_str)
+    }
+
+    /// Nodes with in-degree 0, ascending.
+    ///
+    /// An isolated node is both a source and a sink. Removed nodes are skipped.
+    pub fn sources(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&v| self.rev_adj[v].is_empty() && !self.removed[v])
+            .collect()
+    }
+
+    /// Nodes with out-degree 0, ascending.
+    ///
+    /// An isolated node is both a source and a sink. Removed nodes are skipped.
+    pub fn sinks(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&v| self.adj[v].is_empty() && !self.removed[v])
+            .collect()
+    }
+
+    /// `(in_degree, out_degree)` of every node, in index order.
+    /// Removed nodes keep their slot, with `(0, 0)`.
+    pub fn degree_sequence(&self) -> Vec<(usize, usize)> {
+        (0..self.len())
+            .map(|v| {
+                if self.removed[v] {
+                    (0, 0)
+                } else {
+                    (self.rev_adj[v].len(), self.adj[v].len())
+                }
+            })
+            .collect()
+    }
+
+    /// Induced subgraph over `nodes` plus a new -> original index mapping.
+    /// See `algorithms::subgraph::induced_subgraph`.
+    #[doc(alias = "induced_subgraph")]
+    pub fn subgraph(&self, nodes: &[usize]) -> (DiGraph, Vec<usize>) {
+        crate::algorithms::subgraph::induced_subgraph(self, nodes)
+    }
+
+    /// Add a batch of edges, sizing each adjacency list once up front.
+    ///
+    /// All endpoints are validated before anything is inserted, so an error
+    /// leaves the graph unchanged. Existing and repeated edges are skipped
+    /// as with `add_edge`, but found with a marker array rather than a scan
+    /// of the source's list, so the batch costs O(V + E) overall.
+    ///
+    /// # Errors
+    /// Names the first pair with an endpoint that is out of range or removed.
+    pub fn add_edges(&mut self, edges: &[(usize, usize)]) -> Result<(), String> {
+        let n = self.nodes.len();
+        if let Some((i, &(from, to))) = edges.iter().enumerate().find(|&(_, &(from, to))| {
+            from >= n || to >= n || self.removed[from] || self.removed[to]
+        }) {
+            return Err(format!(
+                "edge {} ({}, {}) references a node outside 0..{} or a removed node",
+                i, from, to, n
+            ));
+        }
+
+        // Bucket edge indices by source (stable), so duplicates are found
+        // with one marker array instead of a linear scan per edge
+        let mut start = vec![0usize; n + 1];
+        for &(from, _) in edges {
+            start[from + 1] += 1;
+        }
+        for v in 0..n {
+            start[v + 1] += start[v];
+        }
+        let mut by_source = vec![0usize; edges.len()];
+        let mut next = start.clone();
+        for (i, &(from, _)) in edges.iter().enumerate() {
+            by_source[next[from]] = i;
+            next[from] += 1;
+        }
+
+        let mut keep = vec![false; edges.len()];
+        let mut mark = vec![usize::MAX; n];
+        let mut out_extra = vec![0usize; n];
+        let mut in_extra = vec![0usize; n];
+        for from in 0..n {
+            let bucket = &by_source[start[from]..start[from + 1]];
+            if bucket.is_empty() {
+                continue;
+            }
+            for &w in &self.adj[from] {
+                mark[w as usize] = from;
+            }
+            for &i in bucket {
+                let to = edges[i].1;
+                if mark[to] != from {
+                    mark[to] = from;
+                    keep[i] = true;
+                    out_extra[from] += 1;
+                    in_extra[to] += 1;
+                }
+            }
+        }
+        // Reserve so each list reallocates at most once
+        for v in 0..n {
+            self.adj[v].reserve(out_extra[v]);
+            self.rev_adj[v].reserve(in_extra[v]);
+        }
+
+        for (&(from, to), _) in edges.iter().zip(&keep).filter(|&(_, &k)| k) {
+            self.adj[from].push(to as u32);
+            self.rev_adj[to].push(from as u32);
+            if let Some(weights) = &mut self.weights {
+                weights[from].push(1.0);
+            }
+            if let Some(kinds) = &mut self.kinds {
+                kinds[from].push(EdgeKind::default());
+            }
+            self.edge_count += 1;
+        }
+        Ok(())
+    }
+
+    /// Size the per-node edge lists for `edges` ahead of inserting them, so
+    /// each list (and its weights or kinds, if tracked) reallocates at most
+    /// once. Endpoints must be in range; duplicates only over-reserve.
+    pub(crate) fn reserve_edge_lists(&mut self, edges: &[(usize, usize)]) {
+        let n = self.nodes.len();
+        let mut out_extra = vec![0usize; n];
+        let mut in_extra = vec![0usize; n];
+        for &(from, to) in edges {
+            out_extra[from] += 1;
+            in_extra[to] += 1;
+        }
+        for v in 0..n {
+            self.adj[v].reserve(out_extra[v]);
+            self.rev_adj[v].reserve(in_extra[v]);
+            if let Some(weights) = &mut self.weights {
+                weights[v].reserve(out_extra[v]);
+            }
+            if let Some(kinds) = &mut self.kinds {
+                kinds[v].reserve(out_extra[v]);
+            }
+        }
+    }
+
+    /// Build a graph from node IDs and `(from, to)` index pairs in one pass.
+    /// With `sorted`, adjacency lists are sorted afterwards so algorithm
+    /// output doesn't depend on edge order (see `sort_adjacency`).
+    ///
+    /// # Errors
+    /// Fails on duplicate IDs (they would shift later indices) and names the
+    /// first edge with an out-of-range endpoint.
+    pub fn from_edge_list(
+        labels: Vec<String>,
+        edges: &[(u32, u32)],
+        sorted: bool,
+    ) -> Result<DiGraph, String> {
+        let mut graph = DiGraph::with_capacity(labels.len(), edges.len());
+        for (i, id) in labels.iter().enumerate() {
+            if graph.try_add_node(id).map_err(|e| e.to_string())? != i {
+                return Err(format!("duplicate node label {:?} at index {}", id, i));
+            }
+        }
+        let edges: Vec<(usize, usize)> =
+            edges.iter().map(|&(u, v)| (u as usize, v as usize)).collect();
+        graph.add_edges(&edges)?;
+        if sorted {
+            graph.sort_adjacency();
+        }
+        Ok(graph)
+    }
+
+    /// Dense n×n 0/1 adjacency matrix in node-index order (small graphs only).
+    pub fn to_adjacency_matrix(&self) -> Vec<Vec<u8>> {
+        crate::io::matrix::to_adjacency_matrix(self)
+    }
+
+    /// Build a graph from a square 0/1 matrix, labeling nodes `n0`, `n1`, ...
+    pub fn from_adjacency_matrix(matrix: &[Vec<u8>]) -> Result<DiGraph, String> {
+        crate::io::matrix::from_adjacency_matrix(matrix)
+    }
+
+    /// True once any edge carries an explicit weight.
+    pub fn is_weighted(&self) -> bool {
+        self.weights.is_some()
+    }
+
+    /// True once any edge carries a non-default kind.
+    pub(crate) fn has_edge_kinds(&self) -> bool {
+        self.kinds.is_some()
+    }
+
+    /// All attributes of a node, if it has any.
+    pub fn node_attrs(&self, node: usize) -> Option<&Map<String, Value>> {
+        self.attrs.get(&node)
+    }
+
+    /// Set a node attribute, replacing any previous value. Ignores invalid
+    /// and removed nodes, so a tombstone never carries attributes.
+    pub fn set_node_attr(&mut self, node: usize, key: &str, value: Value) {
+        if node < self.nodes.len() && !self.removed[node] {
+            self.attrs.entry(node).or_default().insert(key.to_string(), value);
+        }
+    }
+
+    /// Get a node attribute.
+    pub fn node_attr(&self, node: usize, key: &str) -> Option<&Value> {
+        self.attrs.get(&node)?.get(key)
+    }
+
+    /// Get a string attribute (None if missing or not a string).
+    pub fn attr_str(&self, node: usize, key: &str) -> Option<&str> {
+        self.node_attr(node, key)?.as_str()
+    }
+
+    /// Get a numeric attribute (None if missing or not a number).
+    pub fn attr_f64(&self, node: usize, key: &str) -> Option<f64> {
+        self.node_attr(node, key)?.as_f64()
+    }
+
+    /// Get a boolean attribute (None if missing or not a bool).
+    pub fn attr_bool(&self, node: usize, key: &str) -> Option<bool> {
+        self.node_attr(node, key)?.as_bool()
+    }
+
+    /// Convert a JS closed set to a mask covering every node, with
+    /// removed nodes treated as closed so they never show up as open work.
+    fn closed_mask(&self, closed_set: &[u8]) -> Vec<bool> {
+        (0..self.len().max(closed_set.len()))
+            .map(|i| closed_set.get(i).is_some_and(|&b| b != 0) || self.is_removed(i))
+            .collect()
+    }
+
+    /// Push a tombstone directly (used when importing snapshots).
+    pub(crate) fn add_tombstone(&mut self) -> usize {
+        let idx = self.nodes.len();
+        self.nodes.push(String::new());
+        self.adj.push(Vec::new());
+        self.rev_adj.push(Vec::new());
+        self.removed.push(true);
+        self.removed_count += 1;
+        if let Some(weights) = &mut self.weights {
+            weights.push(Vec::new());
+        }
+        if let Some(kinds) = &mut self.kinds {
+            kinds.push(Vec::new());
+        }
+        idx
+    }
+
+    /// Indices of removed nodes, or None if there are none.
+    fn removed_vec(&self) -> Option<Vec<usize>> {
+        if self.removed_count == 0 {
+            return None;
+        }
+        Some((0..self.len()).filter(|&v| self.removed[v]).collect())
+    }
+
+    /// Node attributes in node order, or None if no node has any.
+    pub(crate) fn attrs_vec(&self) -> Option<Vec<Map<String, Value>>> {
+        if self.attrs.is_empty() {
+            return None;
+        }
+        Some(
+            (0..self.len())
+                .map(|v| self.attrs.get(&v).cloned().unwrap_or_default())
+                .collect(),
+        )
+    }
+
+    /// Edge kinds in `edges()` order, or None if every edge is kind 0.
+    pub(crate) fn kinds_vec(&self) -> Option<Vec<u8>> {
+        let kinds = self.kinds.as_ref()?;
+        Some(kinds.iter().flatten().map(|k| k.0).collect())
     }
 
     /// Collect edges as vec (for serialization).
@@ -680,14 +2455,13 @@ impl DiGraph {
         self.edges().collect()
     }
 
-    /// Get node count (internal, non-WASM).
-    pub(crate) fn len(&self) -> usize {
+    /// Number of node indices, including removed nodes (see `active_len`).
+    pub fn len(&self) -> usize {
         self.nodes.len()
     }
 
-    /// Check if graph is empty.
-    #[allow(dead_code)]
-    pub(crate) fn is_empty(&self) -> bool {
+    /// True if no node has ever been added.
+    pub fn is_empty(&self) -> bool {
         self.nodes.is_empty()
     }
 }
@@ -701,6 +2475,12 @@ impl Default for DiGraph {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::Cell;
+
+    thread_local! {
+        /// Overrides `max_node_index` for the current test thread
+        pub(super) static NODE_INDEX_LIMIT: Cell<Option<usize>> = const { Cell::new(None) };
+    }
 
     #[test]
     fn test_new_graph() {
@@ -723,20 +2503,258 @@ mod tests {
         let mut g = DiGraph::new();
         let a = g.add_node("a");
         let b = g.add_node("b");
-        g.add_edge(a, b);
-        g.add_edge(a, b); // Should be idempotent
+        g.add_edge(a, b).unwrap();
+        g.add_edge(a, b).unwrap(); // Should be idempotent
         assert_eq!(g.edge_count(), 1);
     }
 
+    #[test]
+    fn test_no_parallel_edges_from_any_insert_path() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_edge(a, b).unwrap();
+        g.add_weighted_edge(a, b, 2.0).unwrap();
+        g.add_edge_kind(a, b, EdgeKind::RELATED.0).unwrap();
+        g.add_edge(a, b).unwrap();
+
+        assert_eq!(g.edge_count(), 1);
+        assert_eq!(g.out_neighbors(a).collect::<Vec<_>>(), [b]);
+        assert_eq!(g.in_neighbors(b).collect::<Vec<_>>(), [a]);
+        assert_eq!(g.out_degree(a), 1);
+
+        // Duplicate rows in an import collapse too
+        let json = r#"{ "nodes": ["a", "b"], "edges": [[0, 1], ["a", "b"], [0, 1]] }"#;
+        let imported = DiGraph::from_json(json).unwrap();
+        assert_eq!(imported.edge_count(), 1);
+        assert_eq!(imported.in_degree(b), 1);
+
+        // Merging a graph with the same edge doesn't double it
+        g.merge(&imported);
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_predecessors_consistent() {
+        // Interleave node and edge insertion, then remove some edges
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_edge(a, b).unwrap();
+        let c = g.add_node("c");
+        g.add_edge(c, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        let d = g.add_node("d");
+        g.add_edge(d, a).unwrap();
+        g.add_edge(a, d).unwrap();
+        g.add_edge(d, d).unwrap();
+
+        let check = |g: &DiGraph| {
+            for v in 0..g.node_count() {
+                for w in g.out_neighbors(v) {
+                    assert!(g.in_neighbors(w).any(|x| x == v));
+                }
+                for u in g.in_neighbors(v) {
+                    assert!(g.has_edge(u, v));
+                }
+                assert_eq!(g.in_degree(v), g.predecessors_slice(v).len());
+                assert_eq!(g.out_degree(v), g.successors_slice(v).len());
+            }
+            let ins: usize = (0..g.node_count()).map(|v| g.in_degree(v)).sum();
+            let outs: usize = (0..g.node_count()).map(|v| g.out_degree(v)).sum();
+            assert_eq!(ins, g.edge_count());
+            assert_eq!(outs, g.edge_count());
+        };
+
+        check(&g);
+        assert_eq!(g.in_neighbors(b).collect::<Vec<_>>(), [a, c]);
+
+        g.remove_edge(a, b);
+        g.remove_edge(d, d);
+        check(&g);
+        assert_eq!(g.in_neighbors(b).collect::<Vec<_>>(), [c]);
+
+        g.remove_node(a);
+        check(&g);
+        assert!(g.predecessors_slice(d).is_empty());
+        assert!(g.predecessors_slice(99).is_empty());
+    }
+
+    #[test]
+    fn test_bulk_construction_matches_incremental() {
+        let n = 20_000;
+        let labels: Vec<String> = (0..n).map(|i| format!("bv-{}", i)).collect();
+
+        // Pseudo-random edge list with some repeats
+        let mut state: u64 = 7;
+        let edges: Vec<(u32, u32)> = (0..120_000)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                let u = (state >> 33) as usize % n;
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                let v = (state >> 33) as usize % n;
+                (u as u32, v as u32)
+            })
+            .collect();
+
+        let bulk = DiGraph::from_edge_list(labels.clone(), &edges, false).unwrap();
+
+        let mut incremental = DiGraph::new();
+        for label in &labels {
+            incremental.add_node(label);
+        }
+        for &(u, v) in &edges {
+            incremental.add_edge(u as usize, v as usize).unwrap();
+        }
+
+        assert_eq!(bulk.edge_count(), incremental.edge_count());
+        for v in 0..n {
+            assert_eq!(bulk.successors_slice(v), incremental.successors_slice(v));
+            assert_eq!(bulk.predecessors_slice(v), incremental.predecessors_slice(v));
+        }
+    }
+
+    #[test]
+    fn test_sort_adjacency_deterministic() {
+        use crate::algorithms::cycles::{enumerate_cycles, tarjan_scc};
+        let labels: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+        let edges = [(0, 1), (0, 2), (1, 2), (2, 0), (2, 3), (3, 0), (1, 3)];
+        let mut shuffled = edges;
+        shuffled.reverse();
+        shuffled.swap(1, 4);
+
+        let mut g1 = DiGraph::from_edge_list(labels.clone(), &edges, true).unwrap();
+        let mut g2 = DiGraph::from_edge_list(labels, &shuffled, false).unwrap();
+        assert_ne!(g1.successors_slice(0), g2.successors_slice(0));
+        g2.sort_adjacency();
+
+        for v in 0..4 {
+            assert!(g2.successors_slice(v).is_sorted());
+            assert!(g2.predecessors_slice(v).is_sorted());
+        }
+        assert_eq!(enumerate_cycles(&g1, 100), enumerate_cycles(&g2, 100));
+        assert_eq!(tarjan_scc(&g1).components, tarjan_scc(&g2).components);
+
+        // Weights and kinds follow their edges
+        g1.add_weighted_edge(3, 1, 5.0).unwrap();
+        g1.add_edge_kind(3, 1, EdgeKind::RELATED.0).unwrap();
+        g1.sort_adjacency();
+        assert_eq!(g1.out_neighbors(3).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(g1.edge_weight(3, 1), Some(5.0));
+        assert_eq!(g1.edge_weight(3, 0), Some(1.0));
+        assert_eq!(g1.edge_kind(3, 1), Some(EdgeKind::RELATED));
+    }
+
+    #[test]
+    fn test_bulk_construction_errors() {
+        let labels = vec!["a".to_string(), "b".to_string()];
+        let err = DiGraph::from_edge_list(labels.clone(), &[(0, 1), (1, 5)], false).unwrap_err();
+        assert!(err.contains("edge 1 (1, 5)"), "{}", err);
+
+        let dup = vec!["a".to_string(), "a".to_string()];
+        assert!(DiGraph::from_edge_list(dup, &[], false).is_err());
+
+        // A failed batch leaves the graph untouched
+        let mut g = DiGraph::from_edge_list(labels, &[(0, 1)], false).unwrap();
+        assert!(g.add_edges(&[(1, 0), (0, 2)]).is_err());
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_add_edges_skips_duplicates_in_order() {
+        let labels = ["a", "b", "c"].map(String::from).to_vec();
+        let mut g = DiGraph::from_edge_list(labels, &[(0, 1)], false).unwrap();
+        g.add_weighted_edge(1, 2, 3.0).unwrap();
+        g.add_edges(&[(2, 0), (0, 1), (0, 2), (2, 0), (1, 0), (0, 2), (1, 2)]).unwrap();
+        assert_eq!(g.edge_count(), 5);
+        assert_eq!(g.out_neighbors(0).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(g.in_neighbors(0).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(g.edge_weight(1, 2), Some(3.0));
+        assert_eq!(g.edge_weight(1, 0), Some(1.0));
+    }
+
+    #[test]
+    fn test_flat_pairs_rejects_odd_length() {
+        assert_eq!(flat_pairs(&[1, 2, 3, 4]), Ok(vec![(1, 2), (3, 4)]));
+        assert_eq!(flat_pairs::<u32>(&[]), Ok(vec![]));
+        let err = flat_pairs(&[1, 2, 3]).unwrap_err();
+        assert!(err.contains("3 elements"), "{}", err);
+    }
+
+    #[test]
+    fn test_with_capacity_large() {
+        let n = 1_000_000;
+        let mut g = DiGraph::with_capacity(n, n);
+        for i in 0..n {
+            assert_eq!(g.add_node(&i.to_string()), i);
+        }
+        g.reserve_edges(0, 2);
+        for i in 1..n {
+            g.add_edge(i - 1, i).unwrap();
+        }
+        assert_eq!(g.node_count(), n);
+        assert_eq!(g.edge_count(), n - 1);
+        assert_eq!(g.node_index("999999"), Some(n - 1));
+        assert_eq!(g.out_neighbors(n - 2).collect::<Vec<_>>(), [n - 1]);
+
+        g.reserve_nodes(10);
+        assert_eq!(g.add_node("extra"), n);
+    }
+
+    #[test]
+    fn test_importers_size_edge_lists_once() {
+        // A hub with many out- and in-edges would regrow its lists repeatedly
+        let mut csv = String::from("source,target\n");
+        let mut edges = Vec::new();
+        for i in 1..=40 {
+            csv.push_str(&format!("hub,n{}\nm{},hub\n", i, i));
+            edges.push(format!("[\"hub\", \"n{}\"]", i));
+        }
+        let json = format!(
+            "{{\"nodes\": [\"hub\", {}], \"edges\": [{}]}}",
+            (1..=40).map(|i| format!("\"n{}\"", i)).collect::<Vec<_>>().join(", "),
+            edges.join(", ")
+        );
+
+        for g in [
+            crate::io::csv::from_edge_csv(&csv).unwrap(),
+            crate::io::json::from_json(&json).unwrap(),
+        ] {
+            let hub = g.node_index("hub").unwrap();
+            assert_eq!(g.out_degree(hub), 40);
+            assert_eq!(g.adj[hub].capacity(), 40);
+            assert_eq!(g.rev_adj[hub].capacity(), g.in_degree(hub));
+        }
+    }
+
+    #[test]
+    fn test_with_zero_capacity_like_new() {
+        let mut g = DiGraph::with_capacity(0, 0);
+        assert_eq!(g.node_count(), 0);
+        assert_eq!(g.edge_count(), 0);
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_edge(a, b).unwrap();
+        g.reserve_edges(42, 1);
+        assert_eq!(g.out_neighbors(a).collect::<Vec<_>>(), [b]);
+        assert_eq!(g.to_json(), {
+            let mut h = DiGraph::new();
+            h.add_node("a");
+            h.add_node("b");
+            h.add_edge(0, 1).unwrap();
+            h.to_json()
+        });
+    }
+
     #[test]
     fn test_degrees() {
         let mut g = DiGraph::new();
         let a = g.add_node("a");
         let b = g.add_node("b");
         let c = g.add_node("c");
-        g.add_edge(a, b);
-        g.add_edge(a, c);
-        g.add_edge(b, c);
+        g.add_edge(a, b).unwrap();
+        g.add_edge(a, c).unwrap();
+        g.add_edge(b, c).unwrap();
 
         assert_eq!(g.out_degree(a), 2);
         assert_eq!(g.out_degree(b), 1);
@@ -747,6 +2765,31 @@ mod tests {
         assert_eq!(g.in_degree(c), 2);
     }
 
+    #[test]
+    fn test_degree_sequence_star() {
+        // Every leaf depends on the center
+        let mut g = DiGraph::new();
+        let center = g.add_node("center");
+        for i in 0..5 {
+            let leaf = g.add_node(&format!("leaf{}", i));
+            g.add_edge(leaf, center).unwrap();
+        }
+
+        let seq = g.degree_sequence();
+        assert_eq!(seq.len(), 6);
+        assert_eq!(seq[center], (5, 0));
+        assert!(seq[1..].iter().all(|&d| d == (0, 1)));
+        assert_eq!(g.in_degree(center), 5);
+        assert_eq!(g.out_degree(1), 1);
+
+        // A removed leaf keeps its slot with zero degrees
+        g.remove_node(2);
+        let seq = g.degree_sequence();
+        assert_eq!(seq.len(), 6);
+        assert_eq!(seq[center], (4, 0));
+        assert_eq!(seq[2], (0, 0));
+    }
+
     #[test]
     fn test_density() {
         let mut g = DiGraph::new();
@@ -757,17 +2800,47 @@ mod tests {
 
         let a = 0;
         let b = g.add_node("b");
-        g.add_edge(a, b);
+        g.add_edge(a, b).unwrap();
         // 2 nodes, 1 edge: 1 / (2 * 1) = 0.5
         assert!((g.density() - 0.5).abs() < 0.001);
     }
 
+    #[test]
+    fn test_counts_complete_triangle() {
+        let mut g = DiGraph::new();
+        assert!(g.is_empty());
+        assert_eq!(g.edge_count(), 0);
+
+        for id in ["a", "b", "c"] {
+            g.add_node(id);
+        }
+        for u in 0..3 {
+            for v in 0..3 {
+                if u != v {
+                    g.add_edge(u, v).unwrap();
+                }
+            }
+        }
+        assert!(!g.is_empty());
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.edge_count(), 6);
+        assert!((g.density() - 1.0).abs() < 1e-12);
+
+        // The counter tracks removals too
+        g.remove_edge(0, 1);
+        assert_eq!(g.edge_count(), 5);
+        g.remove_node(2);
+        assert_eq!(g.edge_count(), 1);
+        assert_eq!(g.len(), 3);
+        assert!((g.density() - 0.5).abs() < 1e-12);
+    }
+
     #[test]
     fn test_json_roundtrip() {
         let mut g = DiGraph::new();
         let a = g.add_node("a");
         let b = g.add_node("b");
-        g.add_edge(a, b);
+        g.add_edge(a, b).unwrap();
 
         let json = g.to_json();
         let g2 = DiGraph::from_json(&json).unwrap();
@@ -777,4 +2850,736 @@ mod tests {
         assert_eq!(g2.node_id(0), Some("a".to_string()));
         assert_eq!(g2.node_id(1), Some("b".to_string()));
     }
+
+    #[test]
+    fn test_json_roundtrip_preserves_edges() {
+        let mut g = DiGraph::new();
+        for id in ["a", "b", "c", "d"] {
+            g.add_node(id);
+        }
+        g.add_edge(2, 0).unwrap();
+        g.add_edge(0, 1).unwrap();
+        g.add_edge(0, 3).unwrap();
+        g.add_edge(3, 3).unwrap();
+
+        let g2 = DiGraph::from_json(&g.to_json()).unwrap();
+        assert_eq!(g2.edges_vec(), g.edges_vec());
+        assert_eq!(g2.nodes, g.nodes);
+    }
+
+    #[test]
+    fn test_edge_kinds() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, b).unwrap();
+        g.add_edge_kind(a, c, EdgeKind::RELATED.0).unwrap();
+        g.add_edge(b, c).unwrap();
+
+        assert_eq!(g.edge_kind(a, b), Some(EdgeKind::BLOCKS));
+        assert_eq!(g.edge_kind(a, c), Some(EdgeKind::RELATED));
+        assert_eq!(g.edge_kind(b, c), Some(EdgeKind::BLOCKS));
+        assert_eq!(g.edge_kind(c, a), None);
+
+        let blocks: Vec<usize> = g.successors_of_kind(a, EdgeKind::BLOCKS.mask()).collect();
+        assert_eq!(blocks, vec![b]);
+        let all: Vec<usize> = g.successors_of_kind(a, EdgeKind::ALL).collect();
+        assert_eq!(all, vec![b, c]);
+
+        // Out-of-range kinds are rejected rather than stored unmaskable
+        let kind = EdgeKind::MAX + 1;
+        assert_eq!(g.add_edge_kind(c, a, kind), Err(GraphError::InvalidKind { kind }));
+        assert_eq!(g.edge_kind(c, a), None);
+
+        let g2 = DiGraph::from_json(&g.to_json()).unwrap();
+        assert_eq!(g2.edge_kind(a, c), Some(EdgeKind::RELATED));
+        assert_eq!(g2.edge_kind(a, b), Some(EdgeKind::BLOCKS));
+    }
+
+    #[test]
+    fn test_remove_edge_breaks_cycle() {
+        use crate::algorithms::cycles::has_cycles;
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_weighted_edge(a, b, 2.0).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge_kind(c, a, EdgeKind::RELATED.0).unwrap();
+        g.add_edge(a, c).unwrap();
+        assert!(has_cycles(&g));
+
+        assert!(g.remove_edge(c, a));
+        assert!(!has_cycles(&g));
+        assert!(!g.remove_edge(c, a));
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.in_degree(a), 0);
+
+        // Parallel weight/kind storage stays aligned after removal
+        assert!(g.remove_edge(a, b));
+        assert_eq!(g.edge_weight(a, c), Some(1.0));
+        assert_eq!(g.edge_kind(a, c), Some(EdgeKind::BLOCKS));
+    }
+
+    #[test]
+    fn test_remove_node_tombstone() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(b, d).unwrap();
+        g.add_edge(c, d).unwrap();
+        g.add_edge(b, b).unwrap();
+        g.set_node_attr(b, "status", serde_json::json!("open"));
+
+        assert!(g.remove_node(b));
+        assert!(!g.remove_node(b));
+        assert!(g.is_removed(b));
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.active_len(), 3);
+        assert_eq!(g.edge_count(), 1);
+
+        // Former successors keep their other edges and indices
+        assert_eq!(g.out_neighbors(c).collect::<Vec<_>>(), [d]);
+        assert!(g.predecessors_slice(c).is_empty());
+        assert_eq!(g.in_neighbors(d).collect::<Vec<_>>(), [c]);
+        assert!(g.successors_slice(a).is_empty());
+
+        // The tombstone has no label, attributes, or new edges
+        assert_eq!(g.node_id(b), None);
+        assert_eq!(g.node_index("b"), None);
+        assert_eq!(g.attr_str(b, "status"), None);
+        assert_eq!(g.add_edge(a, b), Err(GraphError::NodeRemoved { index: b }));
+        assert_eq!(g.edge_count(), 1);
+
+        // Re-adding the ID allocates a fresh index
+        assert_eq!(g.add_node("b"), 4);
+
+        let g2 = DiGraph::from_json(&g.to_json()).unwrap();
+        assert!(g2.is_removed(b));
+        assert_eq!(g2.node_index("b"), Some(4));
+        assert_eq!(g2.edges_vec(), g.edges_vec());
+    }
+
+    #[test]
+    fn test_reverse() {
+        use crate::algorithms::cycles::tarjan_scc;
+        let mut g = DiGraph::new();
+        for id in ["a", "b", "c", "d", "e"] {
+            g.add_node(id);
+        }
+        g.add_weighted_edge(0, 1, 2.0).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(2, 0).unwrap();
+        g.add_edge_kind(2, 3, EdgeKind::RELATED.0).unwrap();
+        g.add_edge(4, 3).unwrap();
+
+        let r = g.reverse();
+        assert_eq!(r.edge_count(), g.edge_count());
+        for (u, v) in g.edges() {
+            assert!(r.has_edge(v, u));
+            assert!(r.in_neighbors(u).any(|x| x == v));
+            assert_eq!(r.edge_weight(v, u), g.edge_weight(u, v));
+            assert_eq!(r.edge_kind(v, u), g.edge_kind(u, v));
+        }
+        assert_eq!(r.node_index("c"), Some(2));
+
+        // Reversing twice restores the edge set
+        let mut twice = r.reverse().edges_vec();
+        let mut original = g.edges_vec();
+        twice.sort_unstable();
+        original.sort_unstable();
+        assert_eq!(twice, original);
+
+        // Same strongly connected components
+        let normalize = |g: &DiGraph| {
+            let mut comps: Vec<Vec<usize>> = tarjan_scc(g)
+                .components
+                .into_iter()
+                .map(|mut c| {
+                    c.sort_unstable();
+                    c
+                })
+                .collect();
+            comps.sort();
+            comps
+        };
+        assert_eq!(normalize(&g), normalize(&r));
+
+        let empty = DiGraph::new().reverse();
+        assert_eq!(empty.node_count(), 0);
+        assert_eq!(empty.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_reverse_chain() {
+        // a -> b -> c reverses to c -> b -> a
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+
+        let r = g.reversed();
+        assert_eq!(r.out_neighbors(c).collect::<Vec<_>>(), [b]);
+        assert_eq!(r.out_neighbors(b).collect::<Vec<_>>(), [a]);
+        assert!(r.successors_slice(a).is_empty());
+        assert_eq!(r.node_id(a), Some("a".to_string()));
+
+        let rr = r.reversed();
+        for v in [a, b, c] {
+            assert_eq!(rr.successors_slice(v), g.successors_slice(v));
+            assert_eq!(rr.predecessors_slice(v), g.predecessors_slice(v));
+        }
+    }
+
+    #[test]
+    fn test_merge_triangles() {
+        let triangle = |ids: [&str; 3]| {
+            let mut g = DiGraph::new();
+            for id in ids {
+                g.add_node(id);
+            }
+            g.add_edge(0, 1).unwrap();
+            g.add_edge(1, 2).unwrap();
+            g.add_edge(2, 0).unwrap();
+            g
+        };
+
+        // Disjoint triangles: nodes appended with offset indices
+        let mut g = triangle(["a", "b", "c"]);
+        let mapping = g.merge(&triangle(["x", "y", "z"]));
+        assert_eq!(mapping, vec![3, 4, 5]);
+        assert_eq!(g.node_count(), 6);
+        assert_eq!(g.edge_count(), 6);
+        assert!(g.has_edge(5, 3));
+
+        // Shared IDs unify: the reversed triangle adds three new edges
+        let mut other = DiGraph::new();
+        for id in ["c", "b", "a"] {
+            other.add_node(id);
+        }
+        other.add_weighted_edge(0, 1, 4.0).unwrap();
+        other.add_edge(1, 2).unwrap();
+        other.add_edge(2, 0).unwrap();
+        other.set_node_attr(0, "status", serde_json::json!("open"));
+
+        let mapping = g.merge(&other);
+        assert_eq!(mapping, vec![2, 1, 0]);
+        assert_eq!(g.node_count(), 6);
+        assert_eq!(g.edge_count(), 9);
+        assert_eq!(g.edge_weight(2, 1), Some(4.0));
+        assert_eq!(g.edge_weight(0, 1), Some(1.0));
+        assert_eq!(g.attr_str(2, "status"), Some("open"));
+    }
+
+    #[test]
+    fn test_merge_shared_node_unifies() {
+        use serde_json::json;
+        // Repo A: a -> shared; repo B: shared -> b (plus a conflicting edge/attr)
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let shared = g.add_node("bv-42");
+        g.add_weighted_edge(a, shared, 2.0).unwrap();
+        g.set_node_attr(shared, "status", json!("open"));
+
+        let mut other = DiGraph::new();
+        let s2 = other.add_node("bv-42");
+        let b = other.add_node("b");
+        let a2 = other.add_node("a");
+        other.add_edge_kind(s2, b, EdgeKind::PARENT_CHILD.0).unwrap();
+        other.add_weighted_edge(a2, s2, 9.0).unwrap();
+        other.set_node_attr(s2, "status", json!("closed"));
+        other.set_node_attr(s2, "priority", json!(1));
+
+        let mapping = g.merge(&other);
+        assert_eq!(mapping, vec![shared, 2, a]);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 2);
+
+        // Union of the shared node's edges, with carried-over kind
+        assert_eq!(g.in_neighbors(shared).collect::<Vec<_>>(), [a]);
+        assert_eq!(g.out_neighbors(shared).collect::<Vec<_>>(), [2]);
+        assert_eq!(g.edge_kind(shared, 2), Some(EdgeKind::PARENT_CHILD));
+
+        // Existing values win on conflict; new keys are added
+        assert_eq!(g.edge_weight(a, shared), Some(2.0));
+        assert_eq!(g.attr_str(shared, "status"), Some("open"));
+        assert_eq!(g.attr_f64(shared, "priority"), Some(1.0));
+    }
+
+    #[test]
+    fn test_sources_and_sinks() {
+        // a -> b -> c
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        assert_eq!(g.sources(), vec![a]);
+        assert_eq!(g.sinks(), vec![c]);
+
+        // An isolated node is both; a removed one is neither
+        let d = g.add_node("d");
+        let e = g.add_node("e");
+        g.remove_node(e);
+        assert_eq!(g.sources(), vec![a, d]);
+        assert_eq!(g.sinks(), vec![c, d]);
+    }
+
+    #[test]
+    fn test_would_create_cycle() {
+        // a -> b -> c, plus d -> e
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        let e = g.add_node("e");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(d, e).unwrap();
+
+        // Closing edge of the near-cycle
+        assert!(g.would_create_cycle(c, a));
+        assert!(g.would_create_cycle(b, a));
+        assert!(g.would_create_cycle(c, c));
+
+        // Independent or forward edges
+        assert!(!g.would_create_cycle(a, c));
+        assert!(!g.would_create_cycle(c, d));
+        assert!(!g.would_create_cycle(e, a));
+        assert!(!g.would_create_cycle(a, 99));
+
+        // Nothing was added
+        assert_eq!(g.edge_count(), 3);
+        assert!(!g.has_cycles());
+    }
+
+    #[test]
+    fn test_contract_redirects_and_dedups() {
+        use crate::algorithms::cycles::tarjan_scc;
+        // x -> a -> b -> c -> a, with b -> y and c -> y
+        let mut g = DiGraph::new();
+        let x = g.add_node("x");
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let y = g.add_node("y");
+        g.add_edge(x, a).unwrap();
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(c, a).unwrap();
+        g.add_edge(b, y).unwrap();
+        g.add_edge(c, y).unwrap();
+        assert!(tarjan_scc(&g).has_cycles);
+
+        let s = g.contract(&[a, b, c], "abc");
+        assert_eq!(s, 5);
+        assert_eq!(g.node_idx("abc"), Some(s));
+        assert_eq!(g.members_of(s), &[a, b, c]);
+        assert!(g.is_removed(a) && g.is_removed(b) && g.is_removed(c));
+
+        // Redirected, the two edges into y collapsed, internal edges gone
+        assert_eq!(g.out_neighbors(x).collect::<Vec<_>>(), [s]);
+        assert_eq!(g.out_neighbors(s).collect::<Vec<_>>(), [y]);
+        assert_eq!(g.edge_count(), 2);
+
+        let scc = tarjan_scc(&g);
+        assert!(!scc.has_cycles);
+        assert!(scc.components.iter().all(|c| c.len() == 1));
+
+        // Nested contraction records the original nodes
+        let t = g.contract(&[s, y], "all");
+        assert_eq!(g.members_of(t), &[a, b, c, y]);
+        assert!(g.members_of(s).is_empty());
+    }
+
+    #[test]
+    fn test_contract_two_cycle() {
+        // p -> a <-> b -> q: the 2-cycle must not turn into a self-loop
+        let mut g = DiGraph::new();
+        let p = g.add_node("p");
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let q = g.add_node("q");
+        g.add_edge(p, a).unwrap();
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, a).unwrap();
+        g.add_edge(b, q).unwrap();
+
+        let s = g.contract(&[a, b], "ab");
+        assert!(!g.has_edge(s, s));
+        assert!(g.has_edge(p, s) && g.has_edge(s, q));
+        assert_eq!(g.edge_count(), 2);
+        assert!(!g.has_cycles());
+    }
+
+    #[test]
+    fn test_contract_single_node_and_cross_group() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, b).unwrap();
+        g.add_weighted_edge(b, c, 3.0).unwrap();
+
+        // A single node is renamed in place
+        assert_eq!(g.contract(&[b], "B"), b);
+        assert_eq!(g.node_idx("B"), Some(b));
+        assert_eq!(g.node_idx("b"), None);
+        assert!(g.members_of(b).is_empty());
+        assert_eq!(g.edge_weight(b, c), Some(3.0));
+
+        // Contracting a and c creates a new 2-cycle with b
+        let s = g.contract(&[a, c, 99], "ac");
+        assert_eq!(g.out_neighbors(s).collect::<Vec<_>>(), [b]);
+        assert_eq!(g.out_neighbors(b).collect::<Vec<_>>(), [s]);
+        assert_eq!(g.edge_weight(b, s), Some(3.0));
+        assert!(g.has_cycles());
+
+        assert_eq!(g.contract(&[a, 99], "none"), usize::MAX);
+    }
+
+    #[test]
+    fn test_label_lookup() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("bv-1");
+        let b = g.add_node("Fix login");
+
+        assert_eq!(g.node_index("bv-1"), Some(a));
+        assert_eq!(g.node_index("Fix login"), Some(b));
+        assert_eq!(g.label(b), Some("Fix login"));
+
+        assert_eq!(g.node_index("bv-404"), None);
+        assert_eq!(g.node_by_label("Fix login"), Some(b));
+        assert_eq!(g.node_by_label("bv-404"), None);
+        assert_eq!(g.label(99), None);
+
+        // Duplicate label resolves to the first insertion
+        assert_eq!(g.add_node("bv-1"), a);
+        assert_eq!(g.node_index("bv-1"), Some(a));
+        assert_eq!(g.node_count(), 2);
+    }
+
+    #[test]
+    fn test_build_from_label_pairs() {
+        // Importer pattern: get-or-add each endpoint, then link them
+        let pairs = [("bv-1", "bv-2"), ("bv-3", "bv-1"), ("bv-2", "bv-3"), ("bv-4", "bv-2")];
+        let mut g = DiGraph::new();
+        for (from, to) in pairs {
+            let u = g.get_or_add_node(from).unwrap();
+            let v = g.get_or_add_node(to).unwrap();
+            g.add_edge(u, v).unwrap();
+            // Lookups stay in sync after every interleaved insertion
+            assert_eq!(g.node_by_label(from), Some(u));
+            assert_eq!(g.node_by_label(to), Some(v));
+        }
+        assert_eq!(g.get_or_add_node("bv-3"), Ok(2));
+
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.node_index("bv-4"), Some(3));
+        assert_eq!(g.node_index("bv-5"), None);
+        assert_eq!(g.successors_slice(g.node_index("bv-2").unwrap()), &[2]);
+    }
+
+    #[test]
+    fn test_node_attrs() {
+        use serde_json::json;
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.set_node_attr(a, "status", json!("open"));
+        g.set_node_attr(a, "priority", json!(2));
+        g.set_node_attr(a, "blocked", json!(true));
+        g.set_node_attr(7, "status", json!("ghost"));
+
+        assert_eq!(g.attr_str(a, "status"), Some("open"));
+        assert_eq!(g.attr_f64(a, "priority"), Some(2.0));
+        assert_eq!(g.attr_bool(a, "blocked"), Some(true));
+        // Missing keys, wrong types, and nodes without attrs
+        assert_eq!(g.node_attr(a, "owner"), None);
+        assert_eq!(g.attr_f64(a, "status"), None);
+        assert_eq!(g.attr_str(b, "status"), None);
+        assert_eq!(g.node_attr(7, "status"), None);
+
+        g.set_node_attr(a, "status", json!("closed"));
+        assert_eq!(g.attr_str(a, "status"), Some("closed"));
+
+        // Tombstones drop their attrs and don't take new ones
+        g.remove_node(a);
+        g.set_node_attr(a, "status", json!("reopened"));
+        assert_eq!(g.node_attrs(a), None);
+        assert_eq!(g.node_attr(a, "status"), None);
+    }
+
+    #[test]
+    fn test_node_attrs_serialization() {
+        use serde_json::json;
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_edge(a, b).unwrap();
+        assert!(!g.to_json().contains("attrs"));
+
+        g.set_node_attr(b, "status", json!("open"));
+        let json = g.to_json();
+        assert!(json.contains(r#""attrs":[{},{"status":"open"}]"#), "{}", json);
+
+        let g2 = DiGraph::from_json(&json).unwrap();
+        assert_eq!(g2.attr_str(b, "status"), Some("open"));
+        assert_eq!(g2.node_attr(a, "status"), None);
+    }
+
+    #[test]
+    fn test_json_annotated() {
+        use crate::algorithms::cycles::tarjan_scc;
+        // a <-> b cycle, c -> a, d self-loop
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, a).unwrap();
+        g.add_edge(c, a).unwrap();
+        g.add_edge(d, d).unwrap();
+
+        let scc = tarjan_scc(&g);
+        let json = g.to_json_annotated(&scc);
+        let snapshot: GraphSnapshot = serde_json::from_str(&json).unwrap();
+        let ann = snapshot.annotations.unwrap();
+
+        assert_eq!(ann[a].component, ann[b].component);
+        assert_ne!(ann[a].component, ann[c].component);
+        assert!(scc.components[ann[c].component].contains(&c));
+        assert!(ann[a].in_cycle && ann[b].in_cycle && ann[d].in_cycle);
+        assert!(!ann[c].in_cycle);
+
+        // Annotated output is still importable
+        let g2 = DiGraph::from_json(&json).unwrap();
+        assert_eq!(g2.edges_vec(), g.edges_vec());
+    }
+
+    #[test]
+    fn test_edge_weights_mixed() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, b).unwrap();
+        g.add_weighted_edge(b, c, 2.5).unwrap();
+        g.add_edge(c, a).unwrap();
+
+        assert_eq!(g.edge_weight(a, b), Some(1.0));
+        assert_eq!(g.edge_weight(b, c), Some(2.5));
+        assert_eq!(g.edge_weight(c, a), Some(1.0));
+        assert_eq!(g.edge_weight(a, c), None);
+        assert_eq!(g.edge_weight(9, a), None);
+        assert_eq!(g.edge_count(), 3);
+
+        // Re-adding updates the weight without duplicating the edge
+        g.add_weighted_edge(a, b, -1.0).unwrap();
+        assert_eq!(g.edge_weight(a, b), Some(-1.0));
+        assert_eq!(g.edge_count(), 3);
+
+        let pairs: Vec<(usize, f64)> = g.weighted_successors(b).collect();
+        assert_eq!(pairs, vec![(c, 2.5)]);
+    }
+
+    #[test]
+    fn test_edge_weights_feed_algorithms() {
+        // Negative weight on the closing edge makes the triangle a negative cycle
+        use crate::algorithms::negative_cycle::negative_cycle;
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+        g.add_edge(c, a).unwrap();
+        assert_eq!(negative_cycle(&g, &g.edge_weights()), Ok(None));
+
+        g.add_weighted_edge(c, a, -3.0).unwrap();
+        assert!(negative_cycle(&g, &g.edge_weights()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_bfs_distances_typed_array() {
+        use crate::algorithms::subgraph::Direction;
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_node("island");
+        g.add_edge(a, b).unwrap();
+
+        assert_eq!(g.bfs_distances(&[a], Direction::Forward), vec![0, 1, -1]);
+        assert_eq!(g.bfs_distances(&[a], Direction::Backward), vec![0, -1, -1]);
+        assert_eq!(g.bfs_distances(&[], Direction::Both), vec![-1; 3]);
+    }
+
+    #[test]
+    fn test_edges_and_nodes_iterators() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.add_edge(c, a).unwrap();
+        g.add_edge(a, b).unwrap();
+        g.add_edge(b, c).unwrap();
+
+        let edges: Vec<(usize, usize)> = g.edges().collect();
+        assert_eq!(edges, vec![(a, b), (b, c), (c, a)]);
+
+        g.remove_node(b);
+        let nodes: Vec<(usize, &str)> = g.nodes().collect();
+        assert_eq!(nodes, vec![(a, "a"), (c, "c")]);
+        assert_eq!(g.edges().collect::<Vec<_>>(), vec![(c, a)]);
+    }
+
+    #[test]
+    fn test_add_edge_out_of_range() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+
+        let err = g.add_edge(a, 5).unwrap_err();
+        assert_eq!(err, GraphError::NodeOutOfRange { index: 5, node_count: 2 });
+        assert_eq!(err.to_string(), "node index 5 is out of range for a graph with 2 nodes");
+        assert_eq!(
+            g.add_weighted_edge(7, b, 2.0),
+            Err(GraphError::NodeOutOfRange { index: 7, node_count: 2 })
+        );
+        assert_eq!(
+            g.add_edge_kind(9, 9, EdgeKind::RELATED.0),
+            Err(GraphError::NodeOutOfRange { index: 9, node_count: 2 })
+        );
+        assert_eq!(g.edge_count(), 0);
+        assert!(!g.is_weighted());
+
+        assert_eq!(g.add_edge(a, b), Ok(()));
+        assert_eq!(g.add_edge(a, b), Ok(()));
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_json_roundtrip_weights() {
+        let mut g = DiGraph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_weighted_edge(a, b, 0.25).unwrap();
+
+        let json = g.to_json();
+        assert!(json.contains("weights"));
+        let g2 = DiGraph::from_json(&json).unwrap();
+        assert_eq!(g2.edge_weight(a, b), Some(0.25));
+
+        // Unweighted graphs don't carry a weights field
+        let mut plain = DiGraph::new();
+        plain.add_node("x");
+        assert!(!plain.to_json().contains("weights"));
+    }
+
+    #[test]
+    fn test_subgraph_keeps_internal_edges() {
+        // 0 -> 1 -> 2 -> 3 -> 4 plus 4 -> 1; keep {3, 1, 2}
+        let mut g = DiGraph::new();
+        for i in 0..5 {
+            g.add_node(&format!("n{}", i));
+        }
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 1)] {
+            g.add_edge(u, v).unwrap();
+        }
+        let (sub, mapping) = g.subgraph(&[3, 1, 2]);
+        assert_eq!(mapping, vec![3, 1, 2]);
+        assert_eq!(sub.node_count(), 3);
+        // Only 1 -> 2 and 2 -> 3 survive, renumbered as 1 -> 2 and 2 -> 0
+        assert_eq!(sub.edges().collect::<Vec<_>>(), [(1, 2), (2, 0)]);
+        assert_eq!(sub.label(0), Some("n3"));
+        assert_eq!(g.subgraph_mapping(&[3, 1, 2, 1, 99]), mapping);
+    }
+
+    #[test]
+    fn test_undirected_neighbors() {
+        // 0 <-> 1, 2 -> 0, 0 -> 0
+        let mut g = DiGraph::new();
+        for i in 0..4 {
+            g.add_node(&format!("n{}", i));
+        }
+        for (u, v) in [(0, 1), (1, 0), (2, 0), (0, 0)] {
+            g.add_edge(u, v).unwrap();
+        }
+        assert_eq!(g.undirected_neighbors(0), [1, 2]);
+        assert_eq!(g.undirected_neighbors(2), [0]);
+        assert!(g.undirected_neighbors(3).is_empty());
+        assert!(g.undirected_neighbors(99).is_empty());
+        g.remove_node(2);
+        assert_eq!(g.undirected_neighbors(0), [1]);
+        assert!(g.undirected_neighbors(2).is_empty());
+    }
+
+    #[test]
+    fn test_node_id_conversions() {
+        let id = NodeId::try_from(u32::MAX as usize).unwrap();
+        assert_eq!(usize::from(id), u32::MAX as usize);
+        assert_eq!(NodeId::from(7), NodeId(7));
+        // Serialized as a bare integer, not a wrapper object
+        assert_eq!(serde_json::to_string(&NodeId(7)).unwrap(), "7");
+
+        #[cfg(target_pointer_width = "64")]
+        {
+            let index = u32::MAX as usize + 1;
+            let err = NodeId::try_from(index).unwrap_err();
+            assert_eq!(err, GraphError::TooManyNodes { index });
+            assert!(err.to_string().contains("u32"));
+        }
+    }
+
+    #[test]
+    fn test_too_many_nodes_is_an_error() {
+        // Pretend the u32 range ends at index 2, so the 4th node overflows
+        NODE_INDEX_LIMIT.with(|limit| limit.set(Some(2)));
+        let mut g = DiGraph::new();
+        for id in ["a", "b", "c"] {
+            g.try_add_node(id).unwrap();
+        }
+        g.add_edge(0, 2).unwrap();
+
+        assert_eq!(g.try_add_node("d"), Err(GraphError::TooManyNodes { index: 3 }));
+        assert_eq!(g.get_or_add_node("d"), Err(GraphError::TooManyNodes { index: 3 }));
+        // Nothing was half-added, and existing labels still resolve
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.node_by_label("d"), None);
+        assert_eq!(g.get_or_add_node("c"), Ok(2));
+        assert_eq!(g.edge_count(), 1);
+
+        let labels = ["w", "x", "y", "z"].map(String::from).to_vec();
+        let err = DiGraph::from_edge_list(labels, &[], false).unwrap_err();
+        assert!(err.contains("u32"), "{}", err);
+        NODE_INDEX_LIMIT.with(|limit| limit.set(None));
+    }
+
+    #[test]
+    fn test_indices_beyond_u16_range() {
+        // Enough nodes that truncating indices to 16 bits would alias them
+        let mut g = DiGraph::new();
+        for i in 0..70_000 {
+            g.add_node(&format!("n{}", i));
+        }
+        g.add_edge(3, 69_999).unwrap();
+        g.add_edge(69_999, 65_539).unwrap();
+
+        assert!(g.has_edge(3, 69_999));
+        assert!(!g.has_edge(3, 3 + 65_536));
+        assert_eq!(g.out_neighbors(69_999).collect::<Vec<_>>(), [65_539]);
+        assert_eq!(g.in_neighbors(69_999).collect::<Vec<_>>(), [3]);
+        assert_eq!(g.successors_slice(3), &[69_999u32]);
+    }
 }
@@ -0,0 +1,539 @@
+//! Compact binary persistence for DiGraph.
+//!
+//! Lets the viewer cache a built graph (e.g. in IndexedDB) instead of
+//! rebuilding it from the beads export on every load. Node indices,
+//! tombstones and successor order survive a round trip, so cached analysis
+//! results stay valid.
+//!
+//! Layout (integers little-endian):
+//! - `u8` format version (`FORMAT_VERSION`)
+//! - `u32` node count, then per node a `u8` removed flag and, for live
+//!   nodes, a `u32` byte length plus the UTF-8 label
+//! - per node: `u32` out-degree followed by that many `u32` successors
+//! - `u8` section flags: 1 = weights, 2 = kinds, 4 = attributes
+//! - weights: one `f64` per edge in `edges()` order
+//! - kinds: one `u8` per edge in `edges()` order
+//! - attributes: `u32` byte length plus a JSON array of per-node objects
+//!
+//! Cached analysis results start with a kind byte instead, then the version:
+//! - `SCCResult` (`b'S'`): `u32` node count, per node its `u32` component
+//!   (`u32::MAX` for removed nodes), `u32` finish order entry per
+//!   component, then `u32` cycle count
+//! - `CycleEnumerationResult` (`b'C'`): `u8` truncated flag, `u32` cycle
+//!   count, per cycle a `u32` length and its nodes, then `u32` skipped
+//!   component count and the component ids
+
+use crate::algorithms::cycles::{CycleEnumerationResult, SCCResult};
+use crate::graph::{DiGraph, EdgeKind};
+use serde_json::{Map, Value};
+
+/// Current format version; bump it when the layout changes.
+pub const FORMAT_VERSION: u8 = 1;
+
+const HAS_WEIGHTS: u8 = 1;
+const HAS_KINDS: u8 = 2;
+const HAS_ATTRS: u8 = 4;
+
+const KIND_SCC: u8 = b'S';
+const KIND_CYCLES: u8 = b'C';
+
+/// Encode a graph in the binary format.
+///
+/// Supernode membership from `contract` is not stored.
+pub fn to_bytes(graph: &DiGraph) -> Vec<u8> {
+    let n = graph.len();
+    let mut out = Vec::with_capacity(1 + n * 16 + graph.edge_count() * 4);
+    out.push(FORMAT_VERSION);
+
+    out.extend((n as u32).to_le_bytes());
+    for v in 0..n {
+        match graph.label(v) {
+            Some(label) => {
+                out.push(0);
+                out.extend((label.len() as u32).to_le_bytes());
+                out.extend(label.as_bytes());
+            }
+            None => out.push(1),
+        }
+    }
+
+    for v in 0..n {
+        let succs = graph.successors_slice(v);
+        out.extend((succs.len() as u32).to_le_bytes());
+        for &w in succs {
+            out.extend(w.to_le_bytes());
+        }
+    }
+
+    let kinds = graph.kinds_vec();
+    let attrs = graph.attrs_vec();
+    let mut flags = 0;
+    if graph.is_weighted() {
+        flags |= HAS_WEIGHTS;
+    }
+    if kinds.is_some() {
+        flags |= HAS_KINDS;
+    }
+    if attrs.is_some() {
+        flags |= HAS_ATTRS;
+    }
+    out.push(flags);
+
+    if graph.is_weighted() {
+        for w in graph.edge_weights() {
+            out.extend(w.to_le_bytes());
+        }
+    }
+    if let Some(kinds) = kinds {
+        out.extend(kinds);
+    }
+    if let Some(attrs) = attrs {
+        let json = serde_json::to_vec(&attrs).unwrap_or_default();
+        out.extend((json.len() as u32).to_le_bytes());
+        out.extend(json);
+    }
+
+    out
+}
+
+/// Decode a graph produced by `to_bytes`.
+///
+/// # Errors
+/// Returns a message for an unknown version, a truncated buffer, trailing
+/// bytes, invalid UTF-8 or duplicate labels, edges that are out of range,
+/// repeated or touch a removed node, kinds above `EdgeKind::MAX`, or
+/// malformed attribute JSON. Never panics on bad input.
+pub fn from_bytes(bytes: &[u8]) -> Result<DiGraph, String> {
+    let mut r = Reader { bytes, pos: 0 };
+
+    let version = r.u8()?;
+    if version != FORMAT_VERSION {
+        return Err(format!(
+            "unsupported graph format version {} (expected {})",
+            version, FORMAT_VERSION
+        ));
+    }
+
+    // Each node takes at least one byte, which bounds allocations on bad input
+    let n = r.u32()? as usize;
+    if n > r.remaining() {
+        return Err(format!("node count {} exceeds buffer size", n));
+    }
+    let mut graph = DiGraph::with_capacity(n, 0);
+    for i in 0..n {
+        if r.u8()? != 0 {
+            graph.add_tombstone();
+            continue;
+        }
+        let len = r.u32()? as usize;
+        let label = std::str::from_utf8(r.take(len)?)
+            .map_err(|_| format!("label of node {} is not valid UTF-8", i))?;
+        if graph.add_node(label) != i {
+            return Err(format!("duplicate node label {:?} at index {}", label, i));
+        }
+    }
+
+    let mut edges = Vec::new();
+    for from in 0..n {
+        let degree = r.u32()? as usize;
+        for _ in 0..degree {
+            let to = r.u32()? as usize;
+            if to >= n || graph.is_removed(from) || graph.is_removed(to) {
+                return Err(format!(
+                    "edge {} -> {} references a node outside 0..{} or a removed node",
+                    from, to, n
+                ));
+            }
+            edges.push((from, to));
+        }
+    }
+
+    let flags = r.u8()?;
+    let weights = if flags & HAS_WEIGHTS != 0 {
+        let mut weights = Vec::with_capacity(edges.len());
+        for _ in 0..edges.len() {
+            weights.push(r.f64()?);
+        }
+        Some(weights)
+    } else {
+        None
+    };
+    let kinds = if flags & HAS_KINDS != 0 {
+        let kinds = r.take(edges.len())?;
+        if let Some(i) = kinds.iter().position(|&k| k > EdgeKind::MAX) {
+            return Err(format!("edge {} has kind {} (max {})", i, kinds[i], EdgeKind::MAX));
+        }
+        Some(kinds)
+    } else {
+        None
+    };
+    let attrs: Option<Vec<Map<String, Value>>> = if flags & HAS_ATTRS != 0 {
+        let len = r.u32()? as usize;
+        let attrs: Vec<Map<String, Value>> = serde_json::from_slice(r.take(len)?)
+            .map_err(|e| format!("invalid attribute JSON: {}", e))?;
+        if attrs.len() != n {
+            return Err(format!("expected {} node attribute maps, got {}", n, attrs.len()));
+        }
+        Some(attrs)
+    } else {
+        None
+    };
+    if r.remaining() > 0 {
+        return Err(format!("{} trailing bytes after graph data", r.remaining()));
+    }
+
+    for (i, &(from, to)) in edges.iter().enumerate() {
+        if graph.has_edge(from, to) {
+            return Err(format!("edge {} -> {} appears twice", from, to));
+        }
+        let added = match &weights {
+            Some(weights) => graph.add_weighted_edge(from, to, weights[i]),
+            None => graph.add_edge(from, to),
+        };
+        added.map_err(|e| format!("edge {}: {}", i, e))?;
+        if let Some(kinds) = kinds {
+            if kinds[i] != EdgeKind::default().0 {
+                graph
+                    .add_edge_kind(from, to, kinds[i])
+                    .map_err(|e| format!("edge {}: {}", i, e))?;
+            }
+        }
+    }
+    for (v, map) in attrs.into_iter().flatten().enumerate() {
+        for (key, value) in map {
+            graph.set_node_attr(v, &key, value);
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Encode an SCC result, including the finish order behind
+/// `reverse_topological_order`.
+pub fn scc_to_bytes(scc: &SCCResult) -> Vec<u8> {
+    let n = scc.node_count();
+    let k = scc.components.len();
+    let mut out = Vec::with_capacity(2 + 4 * (n + k + 2));
+    out.push(KIND_SCC);
+    out.push(FORMAT_VERSION);
+    out.extend((n as u32).to_le_bytes());
+    for v in 0..n {
+        let id = scc.component_of(v);
+        out.extend((if id == usize::MAX { u32::MAX } else { id as u32 }).to_le_bytes());
+    }
+    for &id in scc.reverse_topological_order() {
+        out.extend((id as u32).to_le_bytes());
+    }
+    out.extend((scc.cycle_count as u32).to_le_bytes());
+    out
+}
+
+/// Decode an SCC result produced by `scc_to_bytes`.
+///
+/// # Errors
+/// Returns a message for the wrong kind or version, a truncated buffer,
+/// trailing bytes, components that are empty or out of order, a finish
+/// order that isn't a permutation of the components, or more cyclic
+/// components than components. Never panics on bad input.
+pub fn scc_from_bytes(bytes: &[u8]) -> Result<SCCResult, String> {
+    let mut r = Reader { bytes, pos: 0 };
+    r.header(KIND_SCC, "SCC result")?;
+
+    let n = r.u32()? as usize;
+    if n > r.remaining() / 4 {
+        return Err(format!("node count {} exceeds buffer size", n));
+    }
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    for v in 0..n {
+        let id = r.u32()?;
+        if id == u32::MAX {
+            continue;
+        }
+        let id = id as usize;
+        if id > components.len() {
+            return Err(format!(
+                "node {} is in component {} before component {} has a member",
+                v,
+                id,
+                components.len()
+            ));
+        }
+        if id == components.len() {
+            components.push(Vec::new());
+        }
+        components[id].push(v);
+    }
+
+    let k = components.len();
+    let mut finish_order = Vec::with_capacity(k);
+    let mut seen = vec![false; k];
+    for _ in 0..k {
+        let id = r.u32()? as usize;
+        if id >= k || std::mem::replace(&mut seen[id], true) {
+            return Err(format!("finish order entry {} is invalid or repeated", id));
+        }
+        finish_order.push(id);
+    }
+    let cycle_count = r.u32()? as usize;
+    if cycle_count > k {
+        return Err(format!("cycle count {} exceeds {} components", cycle_count, k));
+    }
+    if r.remaining() > 0 {
+        return Err(format!("{} trailing bytes after SCC data", r.remaining()));
+    }
+    Ok(SCCResult::from_parts(components, cycle_count, n, finish_order))
+}
+
+/// Encode a cycle enumeration result.
+pub fn cycles_to_bytes(result: &CycleEnumerationResult) -> Vec<u8> {
+    let nodes: usize = result.cycles.iter().map(Vec::len).sum();
+    let mut out = Vec::with_capacity(11 + 4 * (nodes + result.cycles.len()));
+    out.push(KIND_CYCLES);
+    out.push(FORMAT_VERSION);
+    out.push(u8::from(result.truncated));
+    out.extend((result.cycles.len() as u32).to_le_bytes());
+    for cycle in &result.cycles {
+        out.extend((cycle.len() as u32).to_le_bytes());
+        for &v in cycle {
+            out.extend((v as u32).to_le_bytes());
+        }
+    }
+    out.extend((result.skipped_sccs.len() as u32).to_le_bytes());
+    for &id in &result.skipped_sccs {
+        out.extend((id as u32).to_le_bytes());
+    }
+    out
+}
+
+/// Decode a cycle enumeration result produced by `cycles_to_bytes`.
+///
+/// # Errors
+/// Returns a message for the wrong kind or version, a truncated buffer, a
+/// truncated flag other than 0 or 1, or trailing bytes. Never panics on bad
+/// input.
+pub fn cycles_from_bytes(bytes: &[u8]) -> Result<CycleEnumerationResult, String> {
+    let mut r = Reader { bytes, pos: 0 };
+    r.header(KIND_CYCLES, "cycle result")?;
+
+    let truncated = match r.u8()? {
+        0 => false,
+        1 => true,
+        flag => return Err(format!("truncated flag is {}, expected 0 or 1", flag)),
+    };
+    let count = r.u32()? as usize;
+    let mut cycles = Vec::new();
+    for _ in 0..count {
+        cycles.push(r.u32_list()?);
+    }
+    let skipped_sccs = r.u32_list()?;
+    if r.remaining() > 0 {
+        return Err(format!("{} trailing bytes after cycle data", r.remaining()));
+    }
+    Ok(CycleEnumerationResult {
+        count: cycles.len(),
+        cycles,
+        truncated,
+        skipped_sccs,
+    })
+}
+
+/// Bounds-checked cursor over the input buffer.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if len > self.remaining() {
+            return Err(format!("unexpected end of graph data at byte {}", self.pos));
+        }
+        let slice = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(f64::from_le_bytes(buf))
+    }
+
+    /// A `u32` length followed by that many `u32` values.
+    fn u32_list(&mut self) -> Result<Vec<usize>, String> {
+        let len = self.u32()? as usize;
+        let mut values = Vec::new();
+        for _ in 0..len {
+            values.push(self.u32()? as usize);
+        }
+        Ok(values)
+    }
+
+    /// Check the kind byte and version of a cached result.
+    fn header(&mut self, kind: u8, what: &str) -> Result<(), String> {
+        if self.u8()? != kind {
+            return Err(format!("not an encoded {}", what));
+        }
+        let version = self.u8()?;
+        if version != FORMAT_VERSION {
+            return Err(format!(
+                "unsupported {} format version {} (expected {})",
+                what, version, FORMAT_VERSION
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> DiGraph {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("bd-α");
+        let b = graph.add_node("依存 🚧");
+        let gone = graph.add_node("gone");
+        let c = graph.add_node("c");
+        graph.add_weighted_edge(a, b, 2.5).unwrap();
+        graph.add_edge(b, b).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge_kind(c, a, EdgeKind::PARENT_CHILD.0).unwrap();
+        graph.remove_node(gone);
+        graph.set_node_attr(b, "status", json!("open"));
+        graph
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let graph = sample();
+        let decoded = from_bytes(&to_bytes(&graph)).unwrap();
+
+        assert_eq!(decoded.len(), graph.len());
+        assert_eq!(decoded.to_json(), graph.to_json());
+        assert_eq!(decoded.node_idx("依存 🚧"), Some(1));
+        assert!(decoded.is_removed(2));
+        assert_eq!(decoded.successors_slice(1), &[1, 3]);
+        assert_eq!(decoded.edge_weight(0, 1), Some(2.5));
+        assert_eq!(decoded.edge_kind(3, 0), Some(EdgeKind::PARENT_CHILD));
+        assert_eq!(decoded.attr_str(1, "status"), Some("open"));
+
+        let empty = from_bytes(&to_bytes(&DiGraph::new())).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_bad_input_errors() {
+        let bytes = to_bytes(&sample());
+
+        // Every truncation fails cleanly
+        for len in 0..bytes.len() {
+            assert!(from_bytes(&bytes[..len]).is_err(), "truncated at {}", len);
+        }
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = FORMAT_VERSION + 1;
+        let err = from_bytes(&wrong_version).unwrap_err();
+        assert!(err.contains("version"), "{}", err);
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(from_bytes(&trailing).is_err());
+
+        // Huge node count in a tiny buffer
+        assert!(from_bytes(&[FORMAT_VERSION, 0xff, 0xff, 0xff, 0xff]).is_err());
+
+        // One node with an edge to node 7
+        let mut bad_edge = vec![FORMAT_VERSION, 1, 0, 0, 0, 0, 1, 0, 0, 0, b'a'];
+        bad_edge.extend(1u32.to_le_bytes());
+        bad_edge.extend(7u32.to_le_bytes());
+        bad_edge.push(0);
+        let err = from_bytes(&bad_edge).unwrap_err();
+        assert!(err.contains("0 -> 7"), "{}", err);
+    }
+
+    #[test]
+    fn test_scc_result_round_trip() {
+        use crate::algorithms::cycles::tarjan_scc;
+
+        let scc = tarjan_scc(&sample());
+        let bytes = scc.to_bytes();
+        let decoded = SCCResult::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, scc);
+        assert_eq!(decoded.components, vec![vec![0, 1, 3]]);
+        assert!(decoded.has_cycles);
+        assert_eq!(decoded.cycle_count, 1);
+        assert_eq!(decoded.component_of(2), usize::MAX);
+        assert_eq!(decoded.reverse_topological_order(), scc.reverse_topological_order());
+        assert!(bytes.len() < serde_json::to_vec(&scc).unwrap().len());
+
+        let mut chain = DiGraph::new();
+        for id in ["a", "b", "c"] {
+            chain.add_node(id);
+        }
+        chain.add_edge(2, 1).unwrap();
+        chain.add_edge(1, 0).unwrap();
+        let scc = tarjan_scc(&chain);
+        assert_eq!(SCCResult::from_bytes(&scc.to_bytes()).unwrap(), scc);
+
+        // JSON still works, minus the finish order
+        let json = serde_json::to_string(&scc).unwrap();
+        let from_json: SCCResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json.components, scc.components);
+        assert!(from_json.same_component(1, 1) && !from_json.same_component(0, 1));
+
+        for len in 0..bytes.len() {
+            assert!(SCCResult::from_bytes(&bytes[..len]).is_err(), "truncated at {}", len);
+        }
+        let err = SCCResult::from_bytes(&to_bytes(&chain)).unwrap_err();
+        assert!(err.contains("not an encoded SCC result"), "{}", err);
+    }
+
+    #[test]
+    fn test_cycle_result_round_trip() {
+        use crate::algorithms::cycles::enumerate_cycles_with_info;
+
+        let mut graph = sample();
+        graph.add_edge(0, 3).unwrap();
+        let result = enumerate_cycles_with_info(&graph, 100);
+        assert!(result.count >= 2);
+        let bytes = result.to_bytes();
+        assert_eq!(CycleEnumerationResult::from_bytes(&bytes).unwrap(), result);
+
+        let bounded = CycleEnumerationResult {
+            cycles: vec![vec![4, 5]],
+            truncated: true,
+            count: 1,
+            skipped_sccs: vec![0, 2],
+        };
+        let decoded = CycleEnumerationResult::from_bytes(&bounded.to_bytes()).unwrap();
+        assert_eq!(decoded, bounded);
+
+        let json = serde_json::to_string(&result).unwrap();
+        let from_json: CycleEnumerationResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json, result);
+
+        for len in 0..bytes.len() {
+            assert!(CycleEnumerationResult::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut bad_flag = bytes.clone();
+        bad_flag[2] = 2;
+        assert!(CycleEnumerationResult::from_bytes(&bad_flag).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! Edge-list CSV import and export.
+//!
+//! One `source,target` row per edge, with nodes named by label, which is
+//! what a dependency sheet exported from a spreadsheet looks like. Fields
+//! follow RFC 4180 quoting: a field wrapped in double quotes may contain
+//! commas and line breaks, and `""` inside it stands for one quote.
+
+use crate::graph::DiGraph;
+
+/// Build a graph from `source,target` rows.
+///
+/// Nodes are created on first mention, so indices follow the order labels
+/// appear in. A first row reading `source,target` or `from,to` (any case)
+/// is taken as a header and skipped. Blank lines are skipped, unquoted
+/// fields are trimmed, and repeated edges are stored once. A leading byte
+/// order mark, as spreadsheet exports often add, is ignored.
+///
+/// # Errors
+/// Returns a message with a `line N:` prefix, N being the line the row
+/// starts on, for a row without exactly two fields, an empty label, an
+/// unterminated quote, or text after a closing quote.
+pub fn from_edge_csv(input: &str) -> Result<DiGraph, String> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let rows = split_rows(input);
+    // Every row is one edge; labels are usually shared between rows. Edges
+    // are inserted in one batch at the end, which sizes each list once.
+    let mut graph = DiGraph::with_capacity(rows.len(), rows.len());
+    let mut edges = Vec::with_capacity(rows.len());
+    let mut first = true;
+    for (line, row) in rows {
+        if row.trim().is_empty() {
+            continue;
+        }
+        let fields = split_row(row).map_err(|e| format!("line {}: {}", line, e))?;
+        if fields.len() != 2 {
+            return Err(format!(
+                "line {}: expected 2 fields (source,target), found {}",
+                line,
+                fields.len()
+            ));
+        }
+        if std::mem::take(&mut first) && is_header(&fields) {
+            continue;
+        }
+        if fields.iter().any(String::is_empty) {
+            return Err(format!("line {}: empty node label", line));
+        }
+        let from = graph.add_node(&fields[0]);
+        let to = graph.add_node(&fields[1]);
+        edges.push((from, to));
+    }
+    graph.add_edges(&edges)?;
+    Ok(graph)
+}
+
+/// Export every edge as a `source,target` row under a `source,target` header.
+///
+/// Rows follow `DiGraph::edges` order. Labels are quoted when they contain
+/// a comma, quote or line break, or start or end with whitespace. Nodes
+/// without edges don't appear, so they are lost on a round trip.
+pub fn to_edge_csv(graph: &DiGraph) -> String {
+    let mut out = String::from("source,target\n");
+    for (from, to) in graph.edges() {
+        let (Some(a), Some(b)) = (graph.label(from), graph.label(to)) else {
+            continue;
+        };
+        push_field(&mut out, a);
+        out.push(',');
+        push_field(&mut out, b);
+        out.push('\n');
+    }
+    out
+}
+
+fn is_header(fields: &[String]) -> bool {
+    let (a, b) = (&fields[0], &fields[1]);
+    (a.eq_ignore_ascii_case("source") && b.eq_ignore_ascii_case("target"))
+        || (a.eq_ignore_ascii_case("from") && b.eq_ignore_ascii_case("to"))
+}
+
+/// Split the input into rows at line breaks outside quoted fields, each
+/// with the 1-based line it starts on. Line terminators (`\n` or `\r\n`)
+/// are dropped; an unterminated quote runs to the end of the input.
+fn split_rows(input: &str) -> Vec<(usize, &str)> {
+    let bytes = input.as_bytes();
+    let mut rows = Vec::new();
+    let (mut start, mut start_line, mut line) = (0, 1, 1);
+    let (mut quoted, mut field_start) = (false, true);
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' if quoted => {
+                if bytes.get(i + 1) == Some(&b'"') {
+                    i += 1;
+                } else {
+                    quoted = false;
+                }
+            }
+            b'"' if field_start => {
+                quoted = true;
+                field_start = false;
+            }
+            b'\n' => {
+                line += 1;
+                if !quoted {
+                    let row = &input[start..i];
+                    rows.push((start_line, row.strip_suffix('\r').unwrap_or(row)));
+                    (start, start_line, field_start) = (i + 1, line, true);
+                }
+            }
+            b',' if !quoted => field_start = true,
+            b' ' | b'\t' => {}
+            _ => field_start = false,
+        }
+        i += 1;
+    }
+    if start < input.len() {
+        rows.push((start_line, &input[start..]));
+    }
+    rows
+}
+
+/// Split one row into fields, unquoting as it goes.
+fn split_row(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+        let mut field = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err("unterminated quoted field".to_string()),
+                }
+            }
+            while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+            if !matches!(chars.peek(), None | Some(',')) {
+                return Err(format!("unexpected text after quoted field {:?}", field));
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                field.push(c);
+            }
+            field.truncate(field.trim_end().len());
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+fn push_field(out: &mut String, label: &str) {
+    let needs_quotes = label.contains([',', '"', '\n', '\r'])
+        || label.starts_with(char::is_whitespace)
+        || label.ends_with(char::is_whitespace);
+    if needs_quotes {
+        out.push('"');
+        out.push_str(&label.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(label);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_with_header() {
+        let input = "Source,Target\nbv-1,bv-2\n\nbv-2, bv-3\r\nbv-1,bv-2\n";
+        let graph = from_edge_csv(input).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.node_idx("bv-3"), Some(2));
+        assert_eq!(to_edge_csv(&graph), "source,target\nbv-1,bv-2\nbv-2,bv-3\n");
+
+        // Without a header the first row is an edge
+        let graph = from_edge_csv("a,b\nb,c").unwrap();
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_csv_quoted_labels_roundtrip() {
+        let input = "\"Fix login, then deploy\",\"say \"\"hi\"\"\"\n";
+        let graph = from_edge_csv(input).unwrap();
+        assert_eq!(graph.node_id(0), Some("Fix login, then deploy".to_string()));
+        assert_eq!(graph.node_id(1), Some("say \"hi\"".to_string()));
+
+        let csv = to_edge_csv(&graph);
+        assert_eq!(csv, format!("source,target\n{}", input));
+        let again = from_edge_csv(&csv).unwrap();
+        assert_eq!(again.edges().collect::<Vec<_>>(), [(0, 1)]);
+        assert_eq!(again.node_id(0), graph.node_id(0));
+    }
+
+    #[test]
+    fn test_csv_malformed_rows() {
+        let err = from_edge_csv("source,target\na,b\na,b,c\n").unwrap_err();
+        assert!(err.starts_with("line 3:"), "{}", err);
+
+        let err = from_edge_csv("a\n").unwrap_err();
+        assert!(err.contains("found 1"), "{}", err);
+
+        let err = from_edge_csv("a,b\n\"c,d\n").unwrap_err();
+        assert!(err.contains("line 2: unterminated"), "{}", err);
+
+        let err = from_edge_csv("\"a\"x,b\n").unwrap_err();
+        assert!(err.contains("after quoted field"), "{}", err);
+
+        let err = from_edge_csv("a, \n").unwrap_err();
+        assert!(err.contains("empty node label"), "{}", err);
+
+        // Errors name the line a multi-line row starts on
+        let err = from_edge_csv("a,b\n\"c\nd\",e,f\n").unwrap_err();
+        assert!(err.starts_with("line 2: expected 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_csv_multiline_labels_and_bom() {
+        let input = "\u{feff}source,target\r\n\"Step 1\r\nthen 2\",b\r\nb,\"x\ny\"\r\n";
+        let graph = from_edge_csv(input).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.node_id(0), Some("Step 1\r\nthen 2".to_string()));
+        assert_eq!(graph.node_id(2), Some("x\ny".to_string()));
+
+        let csv = to_edge_csv(&graph);
+        let again = from_edge_csv(&csv).unwrap();
+        assert_eq!(
+            again.edges().collect::<Vec<_>>(),
+            graph.edges().collect::<Vec<_>>()
+        );
+        for v in 0..3 {
+            assert_eq!(again.node_id(v), graph.node_id(v));
+        }
+
+        // A quote inside an unquoted field doesn't start a quoted section
+        let graph = from_edge_csv("5\" pipe,b\nb,c\n").unwrap();
+        assert_eq!(graph.node_id(0), Some("5\" pipe".to_string()));
+        assert_eq!(graph.edge_count(), 2);
+    }
+}
@@ -0,0 +1,346 @@
+//! DOT (Graphviz) import for DiGraph.
+//!
+//! Supports the subset needed to round-trip dependency graphs:
+//!
+//! ```text
+//! digraph deps {
+//!     "bd-1" [label="Fix login"];   // node statement, attributes ignored
+//!     a -> b -> c;                  # edge chains
+//!     /* block comments */
+//! }
+//! ```
+//!
+//! Node IDs may be bare (letters, digits, `_`, `.`, and `-` after the first
+//! character, as in `bd-12`) or double-quoted with `\"` escapes. Graph-level
+//! `key = value` statements and `graph`/`node`/`edge` defaults are accepted
+//! and ignored. Undirected graphs, `--` edges and subgraphs are rejected.
+
+use crate::graph::DiGraph;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    Arrow,
+    UndirectedEdge,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Semi,
+    Comma,
+    Equals,
+}
+
+/// Split `input` into tokens, each tagged with its 1-based line number.
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let at = |i: usize| chars.get(i).copied();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while let Some(c) = at(i) {
+        let next = at(i + 1);
+        i += 1;
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            // `#` lines are C preprocessor output, which DOT ignores
+            '#' if starts_line(&chars, i - 1) => i = skip_line(&chars, i),
+            '/' if next == Some('/') => i = skip_line(&chars, i),
+            '/' if next == Some('*') => {
+                let start = line;
+                i += 1;
+                loop {
+                    match at(i) {
+                        Some('*') if at(i + 1) == Some('/') => break,
+                        Some(c) => line += usize::from(c == '\n'),
+                        None => return Err(format!("line {}: unterminated comment", start)),
+                    }
+                    i += 1;
+                }
+                i += 2;
+            }
+            '"' => {
+                let start = line;
+                let mut id = String::new();
+                loop {
+                    match at(i) {
+                        Some('"') => break,
+                        Some('\\') if at(i + 1) == Some('"') => {
+                            id.push('"');
+                            i += 1;
+                        }
+                        Some(c) => {
+                            line += usize::from(c == '\n');
+                            id.push(c);
+                        }
+                        None => return Err(format!("line {}: unterminated string", start)),
+                    }
+                    i += 1;
+                }
+                i += 1;
+                tokens.push((Token::Id(id), start));
+            }
+            '-' if next == Some('>') => {
+                i += 1;
+                tokens.push((Token::Arrow, line));
+            }
+            '-' if next == Some('-') => {
+                i += 1;
+                tokens.push((Token::UndirectedEdge, line));
+            }
+            '{' => tokens.push((Token::LBrace, line)),
+            '}' => tokens.push((Token::RBrace, line)),
+            '[' => tokens.push((Token::LBracket, line)),
+            ']' => tokens.push((Token::RBracket, line)),
+            ';' => tokens.push((Token::Semi, line)),
+            ',' => tokens.push((Token::Comma, line)),
+            '=' => tokens.push((Token::Equals, line)),
+            c if is_id_char(c) => {
+                let mut id = String::from(c);
+                // `-` belongs to the ID (as in `bd-12`) unless it starts an edge operator
+                while let Some(c) = at(i) {
+                    let operator = c == '-' && matches!(at(i + 1), Some('>' | '-'));
+                    if !(is_id_char(c) || c == '-') || operator {
+                        break;
+                    }
+                    id.push(c);
+                    i += 1;
+                }
+                tokens.push((Token::Id(id), line));
+            }
+            c => return Err(format!("line {}: unexpected character {:?}", line, c)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// True if only whitespace precedes `i` on its line.
+fn starts_line(chars: &[char], i: usize) -> bool {
+    chars[..i]
+        .iter()
+        .rev()
+        .take_while(|&&c| c != '\n')
+        .all(|c| c.is_whitespace())
+}
+
+/// Index of the newline ending the line that contains `i`.
+fn skip_line(chars: &[char], i: usize) -> usize {
+    chars[i..]
+        .iter()
+        .position(|&c| c == '\n')
+        .map_or(chars.len(), |p| i + p)
+}
+
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Token cursor that reports errors with the current line.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn line(&self) -> usize {
+        let last = self.tokens.last().map_or(1, |&(_, line)| line);
+        self.tokens.get(self.pos).map_or(last, |&(_, line)| line)
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("line {}: {}", self.line(), message)
+    }
+
+    fn expect_id(&mut self, what: &str) -> Result<String, String> {
+        if let Some(Token::Id(id)) = self.peek().cloned() {
+            self.pos += 1;
+            return Ok(id);
+        }
+        Err(self.error(&format!("expected {}", what)))
+    }
+
+    /// Skip an optional `[ ... ]` attribute list.
+    fn skip_attrs(&mut self) -> Result<(), String> {
+        if self.peek() != Some(&Token::LBracket) {
+            return Ok(());
+        }
+        self.next();
+        loop {
+            match self.next() {
+                Some(Token::RBracket) => return Ok(()),
+                Some(Token::Id(_) | Token::Equals | Token::Comma | Token::Semi) => {}
+                Some(_) => {
+                    self.pos -= 1;
+                    return Err(self.error("malformed attribute list"));
+                }
+                None => return Err(self.error("unterminated attribute list")),
+            }
+        }
+    }
+}
+
+/// Build a DiGraph from DOT source.
+///
+/// Nodes get indices in order of first mention, whether in a node
+/// statement or as an edge endpoint. Repeated edges are stored once.
+///
+/// # Errors
+/// Returns a message with a `line N:` prefix for malformed input, an
+/// undirected `graph`, `--` edges or subgraphs.
+pub fn from_dot(input: &str) -> Result<DiGraph, String> {
+    let mut p = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+
+    if matches!(p.peek(), Some(Token::Id(k)) if k.eq_ignore_ascii_case("strict")) {
+        p.next();
+    }
+    match p.expect_id("`digraph`")?.to_ascii_lowercase().as_str() {
+        "digraph" => {}
+        "graph" => return Err(p.error("undirected graphs are not supported, use `digraph`")),
+        _ => {
+            p.pos -= 1;
+            return Err(p.error("expected `digraph`"));
+        }
+    }
+    if matches!(p.peek(), Some(Token::Id(_))) {
+        p.next();
+    }
+    if p.next() != Some(Token::LBrace) {
+        p.pos -= 1;
+        return Err(p.error("expected `{`"));
+    }
+
+    let mut graph = DiGraph::new();
+    loop {
+        let id = match p.next() {
+            Some(Token::RBrace) => break,
+            Some(Token::Semi) => continue,
+            Some(Token::Id(id)) => id,
+            Some(Token::LBrace) => {
+                p.pos -= 1;
+                return Err(p.error("subgraphs are not supported"));
+            }
+            Some(_) => {
+                p.pos -= 1;
+                return Err(p.error("expected a node ID or `}`"));
+            }
+            None => return Err(p.error("missing closing `}`")),
+        };
+
+        let is_default = ["graph", "node", "edge"]
+            .iter()
+            .any(|k| id.eq_ignore_ascii_case(k));
+        if is_default && p.peek() == Some(&Token::LBracket) {
+            p.skip_attrs()?;
+            continue;
+        }
+        if p.peek() == Some(&Token::Equals) {
+            p.next();
+            p.expect_id("a value after `=`")?;
+            continue;
+        }
+
+        let mut from = graph.add_node(&id);
+        loop {
+            match p.peek() {
+                Some(Token::Arrow) => {
+                    p.next();
+                    let to = graph.add_node(&p.expect_id("a node ID after `->`")?);
+                    graph
+                        .add_edge(from, to)
+                        .expect("both endpoints were just added");
+                    from = to;
+                }
+                Some(Token::UndirectedEdge) => {
+                    return Err(p.error("`--` edges are not supported in a digraph"))
+                }
+                _ => break,
+            }
+        }
+        p.skip_attrs()?;
+    }
+
+    if p.peek().is_some() {
+        return Err(p.error("unexpected input after closing `}`"));
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_dot_simple() {
+        let graph = from_dot(
+            "digraph deps {
+                a -> b; b -> c
+                // comment -> ignored
+                c -> a -> b [color=red];
+                d;
+            }",
+        )
+        .unwrap();
+        let labels: Vec<&str> = (0..graph.len()).filter_map(|v| graph.label(v)).collect();
+        assert_eq!(labels, ["a", "b", "c", "d"]);
+        assert_eq!(graph.edge_count(), 3);
+        assert!(graph.has_edge(0, 1) && graph.has_edge(1, 2) && graph.has_edge(2, 0));
+    }
+
+    #[test]
+    fn test_from_dot_quoted_ids_and_attrs() {
+        let graph = from_dot(
+            r#"strict digraph {
+                # preprocessor line
+                rankdir = LR;
+                node [shape=box, style="rounded"];
+                "fix login bug" [label="Fix \"login\""];
+                "fix login bug" -> "bd-2" /* why */ -> bd.3
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(graph.node_index("fix login bug"), Some(0));
+        assert_eq!(graph.label(1), Some("bd-2"));
+        assert_eq!(graph.label(2), Some("bd.3"));
+        assert_eq!(graph.edge_count(), 2);
+        assert!(graph.has_edge(1, 2));
+    }
+
+    #[test]
+    fn test_from_dot_errors() {
+        let err = from_dot("digraph {\n  a -> b;\n  b -> ;\n}").unwrap_err();
+        assert_eq!(err, "line 3: expected a node ID after `->`");
+
+        assert!(from_dot("digraph { a -> b")
+            .unwrap_err()
+            .contains("missing closing `}`"));
+        assert!(from_dot("graph { a -- b }")
+            .unwrap_err()
+            .contains("undirected"));
+        assert!(from_dot("digraph { a -- b }").unwrap_err().contains("`--`"));
+        assert!(from_dot("digraph { \"open }")
+            .unwrap_err()
+            .contains("unterminated string"));
+        assert!(from_dot("digraph { a [x=1 }")
+            .unwrap_err()
+            .contains("attribute list"));
+        assert!(from_dot("digraph { a } b")
+            .unwrap_err()
+            .contains("after closing"));
+        assert!(from_dot("").unwrap_err().contains("expected `digraph`"));
+    }
+}
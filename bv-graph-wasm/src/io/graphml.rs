@@ -0,0 +1,137 @@
+//! GraphML export for DiGraph, for importing bead graphs into yEd or Gephi.
+//!
+//! Nodes are written with ids `n0`, `n1`, ... (their index) and the bead ID
+//! as a `label` data value; removed nodes are left out.
+
+use crate::algorithms::cycles::SCCResult;
+use crate::graph::DiGraph;
+use std::fmt::Write;
+
+/// Render the graph as a directed GraphML document.
+///
+/// If `scc` is given, each node also gets a `component` attribute holding
+/// its index into `scc.components`; nodes missing from `scc` get none.
+pub fn to_graphml(graph: &DiGraph, scc: Option<&SCCResult>) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+    if scc.is_some() {
+        out.push_str(
+            "  <key id=\"component\" for=\"node\" attr.name=\"component\" attr.type=\"long\"/>\n",
+        );
+    }
+    out.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+
+    for v in 0..graph.len() {
+        let Some(label) = graph.label(v) else {
+            continue;
+        };
+        let _ = writeln!(out, "    <node id=\"n{}\">", v);
+        let _ = writeln!(
+            out,
+            "      <data key=\"label\">{}</data>",
+            escape_xml(label)
+        );
+        if let Some(component) = scc.map(|s| s.component_of(v)).filter(|&c| c != usize::MAX) {
+            let _ = writeln!(out, "      <data key=\"component\">{}</data>", component);
+        }
+        out.push_str("    </node>\n");
+    }
+
+    for (i, (from, to)) in graph.edges().enumerate() {
+        let _ = writeln!(
+            out,
+            "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"/>",
+            i, from, to
+        );
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Escape text for XML content or attribute values.
+/// Control characters that XML 1.0 forbids become spaces.
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() && c < ' ' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::cycles::tarjan_scc;
+
+    /// Check that every tag is closed in order; good enough for our output.
+    fn assert_well_formed(xml: &str) {
+        let mut open: Vec<&str> = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            let end = rest[start..].find('>').expect("unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('?') || tag.ends_with('/') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop(), Some(name), "mismatched </{}>", name);
+            } else {
+                open.push(tag.split_whitespace().next().unwrap());
+            }
+        }
+        assert!(open.is_empty(), "unclosed tags: {:?}", open);
+    }
+
+    #[test]
+    fn test_graphml_structure() {
+        // a <-> b, b -> c, c removed after d -> c
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(d, c).unwrap();
+        graph.remove_node(c);
+
+        let out = to_graphml(&graph, None);
+        assert_well_formed(&out);
+        assert!(out.contains("edgedefault=\"directed\""));
+        assert_eq!(out.matches("<node ").count(), 3);
+        assert_eq!(out.matches("<edge ").count(), 2);
+        assert!(out.contains("<edge id=\"e1\" source=\"n1\" target=\"n0\"/>"));
+        assert!(!out.contains("n2"));
+        assert!(!out.contains("key=\"component\""));
+
+        let out = to_graphml(&graph, Some(&tarjan_scc(&graph)));
+        assert_well_formed(&out);
+        assert_eq!(out.matches("<data key=\"component\">0</data>").count(), 2);
+        assert!(out.contains("<data key=\"component\">1</data>"));
+    }
+
+    #[test]
+    fn test_graphml_escapes_labels() {
+        let mut graph = DiGraph::new();
+        graph.add_node("<b>Tom & \"Jerry's\"</b>\u{1}");
+
+        let out = to_graphml(&graph, None);
+        assert_well_formed(&out);
+        assert!(out.contains(
+            "<data key=\"label\">&lt;b&gt;Tom &amp; &quot;Jerry&apos;s&quot;&lt;/b&gt; </data>"
+        ));
+    }
+}
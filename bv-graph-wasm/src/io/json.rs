@@ -0,0 +1,249 @@
+//! JSON import for DiGraph.
+//!
+//! Accepts the snapshot schema produced by `DiGraph::to_json`:
+//! `{ "nodes": ["a", "b"], "edges": [[0, 1]] }`
+//! Edge endpoints may also be given by node label: `[["a", "b"]]`, and an
+//! optional `weights` / `kinds` array gives one weight / kind per edge.
+//! Tombstoned node indices are listed in an optional `removed` array.
+
+use crate::graph::{DiGraph, EdgeKind};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Edge endpoint: either a node index or a node label.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Endpoint {
+    Index(usize),
+    Label(String),
+}
+
+/// JSON input schema.
+#[derive(Deserialize)]
+struct JsonGraph {
+    nodes: Vec<String>,
+    #[serde(default)]
+    edges: Vec<(Endpoint, Endpoint)>,
+    #[serde(default)]
+    weights: Option<Vec<f64>>,
+    #[serde(default)]
+    kinds: Option<Vec<u8>>,
+    #[serde(default)]
+    attrs: Option<Vec<Map<String, Value>>>,
+    #[serde(default)]
+    removed: Vec<usize>,
+}
+
+/// Build a DiGraph from a JSON snapshot.
+///
+/// Node indices follow the order of the `nodes` array. Every edge endpoint
+/// is validated, and duplicate node labels are rejected since they would
+/// shift the indices of later nodes.
+///
+/// # Errors
+/// Returns a descriptive message for malformed JSON, duplicate labels,
+/// out-of-range edge indices, unknown edge labels, weights or kinds arrays
+/// whose length doesn't match the edges, or kinds above `EdgeKind::MAX`.
+pub fn from_json(input: &str) -> Result<DiGraph, String> {
+    let parsed: JsonGraph =
+        serde_json::from_str(input).map_err(|e| format!("invalid graph JSON: {}", e))?;
+
+    let mut removed = vec![false; parsed.nodes.len()];
+    for &v in &parsed.removed {
+        match removed.get_mut(v) {
+            Some(slot) => *slot = true,
+            None => {
+                return Err(format!(
+                    "removed node index {} but graph has {} nodes",
+                    v,
+                    parsed.nodes.len()
+                ))
+            }
+        }
+    }
+
+    let mut graph = DiGraph::with_capacity(parsed.nodes.len(), parsed.edges.len());
+    for (i, id) in parsed.nodes.iter().enumerate() {
+        if removed[i] {
+            graph.add_tombstone();
+        } else if graph.add_node(id) != i {
+            return Err(format!("duplicate node label {:?} at index {}", id, i));
+        }
+    }
+
+    let resolve = |graph: &DiGraph, endpoint: &Endpoint, edge: usize| -> Result<usize, String> {
+        match endpoint {
+            Endpoint::Index(idx) if *idx < graph.node_count() => Ok(*idx),
+            Endpoint::Index(idx) => Err(format!(
+                "edge {} references node index {} but graph has {} nodes",
+                edge,
+                idx,
+                graph.node_count()
+            )),
+            Endpoint::Label(label) => graph
+                .node_idx(label)
+                .ok_or_else(|| format!("edge {} references unknown node {:?}", edge, label)),
+        }
+    };
+
+    if let Some(attrs) = parsed.attrs {
+        if attrs.len() != parsed.nodes.len() {
+            return Err(format!(
+                "expected {} node attribute maps, got {}",
+                parsed.nodes.len(),
+                attrs.len()
+            ));
+        }
+        for (v, map) in attrs.into_iter().enumerate() {
+            for (key, value) in map {
+                graph.set_node_attr(v, &key, value);
+            }
+        }
+    }
+
+    if let Some(weights) = &parsed.weights {
+        if weights.len() != parsed.edges.len() {
+            return Err(format!(
+                "expected {} edge weights, got {}",
+                parsed.edges.len(),
+                weights.len()
+            ));
+        }
+    }
+    if let Some(kinds) = &parsed.kinds {
+        if kinds.len() != parsed.edges.len() {
+            return Err(format!(
+                "expected {} edge kinds, got {}",
+                parsed.edges.len(),
+                kinds.len()
+            ));
+        }
+        if let Some(i) = kinds.iter().position(|&k| k > EdgeKind::MAX) {
+            return Err(format!(
+                "edge {} has kind {} (max {})",
+                i,
+                kinds[i],
+                EdgeKind::MAX
+            ));
+        }
+    }
+
+    let edges = parsed
+        .edges
+        .iter()
+        .enumerate()
+        .map(|(i, (from, to))| Ok((resolve(&graph, from, i)?, resolve(&graph, to, i)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+    graph.reserve_edge_lists(&edges);
+
+    for (i, &(from, to)) in edges.iter().enumerate() {
+        let added = match &parsed.weights {
+            Some(weights) => graph.add_weighted_edge(from, to, weights[i]),
+            None => graph.add_edge(from, to),
+        };
+        added.map_err(|e| format!("edge {}: {}", i, e))?;
+        if let Some(kinds) = &parsed.kinds {
+            if kinds[i] != EdgeKind::default().0 {
+                graph
+                    .add_edge_kind(from, to, kinds[i])
+                    .map_err(|e| format!("edge {}: {}", i, e))?;
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Set node attributes from a JSON object keyed by node label:
+/// `{ "a": { "status": "open" }, "b": { "priority": 1 } }`.
+///
+/// Existing attributes with other keys are kept. Returns the number of
+/// nodes updated; nothing is applied if any label is unknown.
+pub fn import_node_attrs(graph: &mut DiGraph, input: &str) -> Result<usize, String> {
+    let parsed: Map<String, Value> =
+        serde_json::from_str(input).map_err(|e| format!("invalid attribute JSON: {}", e))?;
+
+    let mut updates = Vec::with_capacity(parsed.len());
+    for (label, attrs) in parsed {
+        let node = graph
+            .node_idx(&label)
+            .ok_or_else(|| format!("unknown node {:?}", label))?;
+        match attrs {
+            Value::Object(map) => updates.push((node, map)),
+            other => {
+                return Err(format!("attributes of {:?} must be an object, got {}", label, other))
+            }
+        }
+    }
+
+    let count = updates.len();
+    for (node, map) in updates {
+        for (key, value) in map {
+            graph.set_node_attr(node, &key, value);
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_json_valid() {
+        let input = r#"{ "nodes": ["a", "b", "c"], "edges": [[0, 1], [1, 2]] }"#;
+        let graph = from_json(input).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.successors_slice(1), &[2]);
+    }
+
+    #[test]
+    fn test_from_json_label_edges() {
+        let input = r#"{ "nodes": ["bd-1", "bd-2"], "edges": [["bd-1", "bd-2"], [1, "bd-1"]] }"#;
+        let graph = from_json(input).unwrap();
+        assert_eq!(graph.successors_slice(0), &[1]);
+        assert_eq!(graph.successors_slice(1), &[0]);
+
+        let err = from_json(r#"{ "nodes": ["bd-1"], "edges": [["bd-1", "bd-9"]] }"#).unwrap_err();
+        assert!(err.contains("bd-9"), "{}", err);
+    }
+
+    #[test]
+    fn test_from_json_out_of_range() {
+        let err = from_json(r#"{ "nodes": ["a", "b"], "edges": [[0, 5]] }"#).unwrap_err();
+        assert!(err.contains("index 5"), "{}", err);
+        assert!(err.contains("2 nodes"), "{}", err);
+    }
+
+    #[test]
+    fn test_from_json_malformed() {
+        let err = from_json(r#"{ "nodes": ["a", "#).unwrap_err();
+        assert!(err.starts_with("invalid graph JSON"), "{}", err);
+
+        let err = from_json(r#"{ "nodes": ["a", "a"] }"#).unwrap_err();
+        assert!(err.contains("duplicate"), "{}", err);
+
+        let err = from_json(r#"{ "nodes": ["a", "b"], "edges": [[0, 1]], "weights": [] }"#)
+            .unwrap_err();
+        assert!(err.contains("weights"), "{}", err);
+    }
+
+    #[test]
+    fn test_import_node_attrs() {
+        let mut graph = from_json(r#"{ "nodes": ["a", "b"] }"#).unwrap();
+        let count =
+            import_node_attrs(&mut graph, r#"{ "a": { "status": "open", "priority": 1 } }"#)
+                .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(graph.attr_str(0, "status"), Some("open"));
+        assert_eq!(graph.attr_f64(0, "priority"), Some(1.0));
+
+        // Unknown labels reject the whole batch
+        let err = import_node_attrs(&mut graph, r#"{ "b": { "x": 1 }, "zz": {} }"#).unwrap_err();
+        assert!(err.contains("zz"), "{}", err);
+        assert_eq!(graph.node_attr(1, "x"), None);
+
+        assert!(import_node_attrs(&mut graph, r#"{ "a": 3 }"#).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Adjacency-matrix import and export.
+//!
+//! Intended for small graphs (teaching, debugging, numpy/pandas dumps):
+//! the matrix is dense, so memory grows with the square of the node count.
+
+use crate::graph::DiGraph;
+
+/// Export the graph as an n×n 0/1 matrix in node-index order.
+///
+/// `matrix[u][v] == 1` iff the edge u -> v exists. Removed nodes keep
+/// their row and column, which are all zeros.
+pub fn to_adjacency_matrix(graph: &DiGraph) -> Vec<Vec<u8>> {
+    let n = graph.len();
+    let mut matrix = vec![vec![0u8; n]; n];
+    for (from, to) in graph.edges() {
+        matrix[from][to] = 1;
+    }
+    matrix
+}
+
+/// Build a graph from a square 0/1 adjacency matrix.
+///
+/// Nodes are labeled `n0`, `n1`, ... in row order.
+///
+/// # Errors
+/// Returns a message naming the first row that isn't length n, or the
+/// first entry that isn't 0 or 1.
+pub fn from_adjacency_matrix(matrix: &[Vec<u8>]) -> Result<DiGraph, String> {
+    let n = matrix.len();
+    for (i, row) in matrix.iter().enumerate() {
+        if row.len() != n {
+            return Err(format!(
+                "matrix is not square: row {} has {} entries, expected {}",
+                i,
+                row.len(),
+                n
+            ));
+        }
+        if let Some(j) = row.iter().position(|&x| x > 1) {
+            return Err(format!("entry ({}, {}) is {}, expected 0 or 1", i, j, row[j]));
+        }
+    }
+
+    let mut graph = DiGraph::with_capacity(n, 0);
+    for i in 0..n {
+        graph.add_node(&format!("n{}", i));
+    }
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &x) in row.iter().enumerate() {
+            if x == 1 {
+                graph.add_edge(i, j).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_roundtrip() {
+        let matrix = vec![vec![0, 1, 1], vec![0, 0, 1], vec![1, 0, 1]];
+        let graph = from_adjacency_matrix(&matrix).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 5);
+        assert_eq!(graph.node_id(2), Some("n2".to_string()));
+        assert_eq!(to_adjacency_matrix(&graph), matrix);
+
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.add_edge(b, a).unwrap();
+        let again = from_adjacency_matrix(&to_adjacency_matrix(&graph)).unwrap();
+        assert_eq!(again.out_neighbors(b).collect::<Vec<_>>(), [a]);
+
+        assert!(to_adjacency_matrix(&DiGraph::new()).is_empty());
+        assert_eq!(from_adjacency_matrix(&[]).unwrap().node_count(), 0);
+    }
+
+    #[test]
+    fn test_matrix_errors() {
+        let ragged = vec![vec![0, 1], vec![0]];
+        let err = from_adjacency_matrix(&ragged).unwrap_err();
+        assert!(err.contains("row 1"), "{}", err);
+
+        let err = from_adjacency_matrix(&[vec![2]]).unwrap_err();
+        assert!(err.contains("(0, 0)"), "{}", err);
+    }
+}
@@ -0,0 +1,110 @@
+//! Mermaid flowchart export for DiGraph.
+//!
+//! Mermaid ids can't contain spaces or most punctuation, so nodes are
+//! emitted as `n0`, `n1`, ... with the real node label in the bracket text.
+
+use crate::algorithms::cycles::SCCResult;
+use crate::graph::DiGraph;
+use std::fmt::Write;
+
+/// Render the graph as a `flowchart TD` diagram.
+///
+/// If `scc` is given, nodes in non-trivial components (or with a self-loop)
+/// are assigned the `cycle` class, which is styled in the output.
+pub fn to_mermaid(graph: &DiGraph, scc: Option<&SCCResult>) -> String {
+    let mut out = String::from("flowchart TD\n");
+
+    for v in 0..graph.len() {
+        if let Some(label) = graph.label(v) {
+            let _ = writeln!(out, "    n{}[\"{}\"]", v, escape_label(label));
+        }
+    }
+
+    for (from, to) in graph.edges() {
+        let _ = writeln!(out, "    n{} --> n{}", from, to);
+    }
+
+    if let Some(scc) = scc {
+        let cyclic: Vec<String> = scc
+            .components
+            .iter()
+            .filter(|c| c.len() > 1 || c.first().is_some_and(|&v| has_self_loop(graph, v)))
+            .flatten()
+            .filter(|&&v| v < graph.len())
+            .map(|v| format!("n{}", v))
+            .collect();
+        if !cyclic.is_empty() {
+            out.push_str("    classDef cycle fill:#fdd,stroke:#c00,stroke-width:2px\n");
+            let _ = writeln!(out, "    class {} cycle", cyclic.join(","));
+        }
+    }
+
+    out
+}
+
+fn has_self_loop(graph: &DiGraph, v: usize) -> bool {
+    graph.has_edge(v, v)
+}
+
+/// Escape a label for use inside `["..."]`.
+/// Quotes and angle brackets become Mermaid entity codes; newlines become spaces.
+fn escape_label(label: &str) -> String {
+    let mut out = String::with_capacity(label.len());
+    for c in label.chars() {
+        match c {
+            '"' => out.push_str("#quot;"),
+            '<' => out.push_str("#lt;"),
+            '>' => out.push_str("#gt;"),
+            '\n' | '\r' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::cycles::tarjan_scc;
+
+    #[test]
+    fn test_mermaid_basic() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("Fix login bug");
+        let b = graph.add_node("bv-2");
+        graph.add_edge(a, b).unwrap();
+
+        let out = to_mermaid(&graph, None);
+        assert!(out.starts_with("flowchart TD"));
+        assert!(out.contains("n0[\"Fix login bug\"]"));
+        assert!(out.contains("n0 --> n1"));
+        assert!(!out.contains("classDef"));
+    }
+
+    #[test]
+    fn test_mermaid_escapes_labels() {
+        let mut graph = DiGraph::new();
+        graph.add_node("say \"hi\" <now>\nplease");
+
+        let out = to_mermaid(&graph, None);
+        assert!(out.contains("n0[\"say #quot;hi#quot; #lt;now#gt; please\"]"));
+    }
+
+    #[test]
+    fn test_mermaid_cycle_class() {
+        // a <-> b, c -> a
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let scc = tarjan_scc(&graph);
+        let out = to_mermaid(&graph, Some(&scc));
+        let class_line = out.lines().find(|l| l.trim_start().starts_with("class ")).unwrap();
+        assert!(class_line.contains("n0") && class_line.contains("n1"));
+        assert!(!class_line.contains("n2"));
+    }
+}
@@ -0,0 +1,12 @@
+//! Graph import and export formats.
+//!
+//! Converts between DiGraph and the text and binary formats used by the viewer and
+//! external tools.
+
+pub mod binary;
+pub mod csv;
+pub mod dot;
+pub mod graphml;
+pub mod json;
+pub mod matrix;
+pub mod mermaid;
@@ -6,13 +6,20 @@
 use wasm_bindgen::prelude::*;
 
 mod graph;
+mod csr;
+mod builder;
 pub mod algorithms;
+pub mod io;
+pub mod diff;
 mod advanced;
 mod whatif;
 mod subgraph;
 mod reachability;
+mod wasm;
 
-pub use graph::DiGraph;
+pub use graph::{DiGraph, EdgeKind, GraphError, NodeId};
+pub use csr::{Adjacency, CsrGraph};
+pub use builder::{BuildError, BuildProblem, GraphBuilder};
 
 // Re-export key algorithm functions for testing
 pub use algorithms::pagerank::{pagerank, pagerank_default, PageRankConfig};
@@ -20,7 +27,7 @@ pub use algorithms::betweenness::{betweenness, betweenness_approx};
 pub use algorithms::eigenvector::{eigenvector, eigenvector_default, EigenvectorConfig};
 pub use algorithms::critical_path::{critical_path_heights, critical_path_nodes, critical_path_length};
 pub use algorithms::cycles::{has_cycles, tarjan_scc};
-pub use algorithms::kcore::{kcore, degeneracy};
+pub use algorithms::kcore::{k_core, kcore, degeneracy};
 pub use algorithms::slack::{slack, total_float};
 pub use algorithms::hits::{hits, hits_default, HITSConfig};
 
@@ -23,7 +23,7 @@ pub fn reachable_from(graph: &DiGraph, source: usize) -> Vec<usize> {
 
     while let Some(v) = queue.pop_front() {
         result.push(v);
-        for &w in graph.successors_slice(v) {
+        for w in graph.out_neighbors(v) {
             if !visited[w] {
                 visited[w] = true;
                 queue.push_back(w);
@@ -51,7 +51,7 @@ pub fn reachable_to(graph: &DiGraph, target: usize) -> Vec<usize> {
 
     while let Some(v) = queue.pop_front() {
         result.push(v);
-        for &u in graph.predecessors_slice(v) {
+        for u in graph.in_neighbors(v) {
             if !visited[u] {
                 visited[u] = true;
                 queue.push_back(u);
@@ -65,22 +65,21 @@ pub fn reachable_to(graph: &DiGraph, target: usize) -> Vec<usize> {
 /// Get direct blockers (predecessors) of a node.
 /// These are issues that must be completed before this node can start.
 pub fn blockers(graph: &DiGraph, node: usize) -> Vec<usize> {
-    graph.predecessors_slice(node).to_vec()
+    graph.in_neighbors(node).collect()
 }
 
 /// Get direct dependents (successors) of a node.
 /// These are issues that depend on this node being completed.
 pub fn dependents(graph: &DiGraph, node: usize) -> Vec<usize> {
-    graph.successors_slice(node).to_vec()
+    graph.out_neighbors(node).collect()
 }
 
 /// Check if all predecessors of node are in the closed set.
 /// A node is actionable if all its blockers are closed.
 pub fn is_actionable(graph: &DiGraph, node: usize, closed_set: &[bool]) -> bool {
     graph
-        .predecessors_slice(node)
-        .iter()
-        .all(|&p| closed_set.get(p).copied().unwrap_or(false))
+        .in_neighbors(node)
+        .all(|p| closed_set.get(p).copied().unwrap_or(false))
 }
 
 /// Get all actionable nodes (no open blockers).
@@ -95,19 +94,16 @@ pub fn actionable_nodes(graph: &DiGraph, closed_set: &[bool]) -> Vec<usize> {
 /// Get open blockers for a node (predecessors not in closed set).
 pub fn open_blockers(graph: &DiGraph, node: usize, closed_set: &[bool]) -> Vec<usize> {
     graph
-        .predecessors_slice(node)
-        .iter()
-        .filter(|&&p| !closed_set.get(p).copied().unwrap_or(false))
-        .copied()
+        .in_neighbors(node)
+        .filter(|&p| !closed_set.get(p).copied().unwrap_or(false))
         .collect()
 }
 
 /// Count of open blockers for a node.
 pub fn open_blocker_count(graph: &DiGraph, node: usize, closed_set: &[bool]) -> usize {
     graph
-        .predecessors_slice(node)
-        .iter()
-        .filter(|&&p| !closed_set.get(p).copied().unwrap_or(false))
+        .in_neighbors(node)
+        .filter(|&p| !closed_set.get(p).copied().unwrap_or(false))
         .count()
 }
 
@@ -137,9 +133,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let from_a = reachable_from(&graph, a);
         assert_eq!(from_a.len(), 4);
@@ -166,10 +162,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let from_a = reachable_from(&graph, a);
         assert_eq!(from_a.len(), 4);
@@ -188,9 +184,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let to_d = reachable_to(&graph, d);
         assert_eq!(to_d.len(), 4);
@@ -217,10 +213,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let to_d = reachable_to(&graph, d);
         assert_eq!(to_d.len(), 4);
@@ -246,8 +242,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, c);
-        graph.add_edge(b, c);
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let c_blockers = blockers(&graph, c);
         assert_eq!(c_blockers.len(), 2);
@@ -265,8 +261,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         // Nothing closed: only a is actionable (no blockers)
         let closed_none = vec![false, false, false];
@@ -296,10 +292,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         // Nothing closed: only a is actionable
         let closed_none = vec![false, false, false, false];
@@ -326,8 +322,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, c);
-        graph.add_edge(b, c);
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         // Nothing closed: c has 2 open blockers
         let closed_none = vec![false, false, false];
@@ -354,8 +350,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, c);
-        graph.add_edge(b, c);
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let closed_none = vec![false, false, false];
         assert_eq!(open_blocker_count(&graph, c, &closed_none), 2);
@@ -374,7 +370,7 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
+        graph.add_edge(a, b).unwrap();
 
         let from_a = reachable_from(&graph, a);
         assert_eq!(from_a.len(), 2);
@@ -391,9 +387,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         // All nodes reachable from any node
         let from_a = reachable_from(&graph, a);
@@ -0,0 +1,103 @@
+//! One-shot WASM entry points for cycle analysis.
+//!
+//! The `DiGraph` methods cover incremental use; these functions take a
+//! whole graph at once (typed arrays or a JSON snapshot) and return the
+//! SCC and cycle results in a single call, so JS needs no glue code.
+
+use crate::algorithms::cycles::{enumerate_cycles_with_info, tarjan_scc};
+use crate::algorithms::cycles::{CycleEnumerationResult, SCCResult};
+use crate::graph::DiGraph;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Combined SCC and cycle enumeration result.
+#[derive(Serialize)]
+pub struct CycleAnalysis {
+    pub scc: SCCResult,
+    pub cycles: CycleEnumerationResult,
+}
+
+/// Run SCC detection and bounded cycle enumeration on a graph.
+pub fn analyze_cycles_in(graph: &DiGraph, max_cycles: usize) -> CycleAnalysis {
+    CycleAnalysis {
+        scc: tarjan_scc(graph),
+        cycles: enumerate_cycles_with_info(graph, max_cycles),
+    }
+}
+
+/// Analyze cycles of a graph given as node IDs and a flat [from, to, ...] edge array.
+/// Returns JSON: { scc: { components, has_cycles, cycle_count },
+///                 cycles: { cycles, truncated, count } }
+#[wasm_bindgen(js_name = analyzeCycles)]
+pub fn analyze_cycles(
+    labels: Vec<String>,
+    edges: &[u32],
+    max_cycles: usize,
+) -> Result<JsValue, JsError> {
+    let graph = DiGraph::from_edge_list_flat(labels, edges, None)?;
+    let result = analyze_cycles_in(&graph, max_cycles);
+    Ok(serde_wasm_bindgen::to_value(&result)?)
+}
+
+/// Analyze cycles of a graph given as a JSON snapshot (the toJson/fromJson schema).
+/// Returns the same shape as analyzeCycles.
+#[wasm_bindgen(js_name = analyzeCyclesJson)]
+pub fn analyze_cycles_json(json: &str, max_cycles: usize) -> Result<JsValue, JsError> {
+    let graph = DiGraph::from_json(json)?;
+    let result = analyze_cycles_in(&graph, max_cycles);
+    Ok(serde_wasm_bindgen::to_value(&result)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_cycles_in() {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let result = analyze_cycles_in(&graph, 10);
+        assert!(result.scc.has_cycles);
+        assert_eq!(result.cycles.count, 1);
+        assert!(!result.cycles.truncated);
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod wasm_tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn get(value: &JsValue, key: &str) -> JsValue {
+        js_sys::Reflect::get(value, &JsValue::from_str(key)).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn three_cycle_has_cycles() {
+        let labels = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let result = analyze_cycles(labels, &[0, 1, 1, 2, 2, 0], 10).unwrap();
+        assert_eq!(get(&get(&result, "scc"), "has_cycles").as_bool(), Some(true));
+        assert_eq!(get(&get(&result, "cycles"), "count").as_f64(), Some(1.0));
+
+        let json = r#"{ "nodes": ["a", "b", "c"], "edges": [[0, 1], [1, 2], [2, 0]] }"#;
+        let result = analyze_cycles_json(json, 10).unwrap();
+        assert_eq!(get(&get(&result, "scc"), "has_cycles").as_bool(), Some(true));
+    }
+
+    #[wasm_bindgen_test]
+    fn add_edge_out_of_range_throws() {
+        let mut graph = DiGraph::new();
+        graph.add_node("a");
+        let err: JsValue = graph.add_edge(0, 3).unwrap_err().into();
+        assert_eq!(
+            get(&err, "message").as_string().as_deref(),
+            Some("node index 3 is out of range for a graph with 1 nodes")
+        );
+    }
+}
@@ -3,10 +3,11 @@
 //! What-If analysis answers "If I close issue X, what happens?"
 //! It computes direct unblocks, transitive cascades, and impact metrics.
 
+use crate::algorithms::cycles::{tarjan_scc, tarjan_scc_filtered, SCCResult};
 use crate::graph::DiGraph;
 use crate::reachability::{actionable_nodes, is_actionable};
 use serde::Serialize;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 /// Result of a what-if simulation for closing a single node.
 #[derive(Debug, Clone, Serialize)]
@@ -61,7 +62,7 @@ pub fn what_if_close(graph: &DiGraph, node: usize, closed_set: &[bool]) -> WhatI
     // These are successors of node that had all other blockers already closed
     let mut direct_unblocks = Vec::new();
 
-    for &successor in graph.successors_slice(node) {
+    for successor in graph.out_neighbors(node) {
         if new_closed[successor] {
             continue;
         }
@@ -125,16 +126,13 @@ fn count_cascade(graph: &DiGraph, roots: &[usize], initial_closed: &[bool]) -> V
         closed[v] = true;
 
         // Check successors
-        for &w in graph.successors_slice(v) {
+        for w in graph.out_neighbors(v) {
             if visited[w] || closed[w] {
                 continue;
             }
 
             // Check if all predecessors of w are now resolved
-            let all_resolved = graph
-                .predecessors_slice(w)
-                .iter()
-                .all(|&p| closed[p] || visited[p]);
+            let all_resolved = graph.in_neighbors(w).all(|p| closed[p] || visited[p]);
 
             if all_resolved {
                 visited[w] = true;
@@ -262,7 +260,7 @@ pub fn what_if_close_batch(
         if node >= n {
             continue;
         }
-        for &successor in graph.successors_slice(node) {
+        for successor in graph.out_neighbors(node) {
             if seen[successor] || new_closed[successor] {
                 continue;
             }
@@ -290,6 +288,74 @@ pub fn what_if_close_batch(
     }
 }
 
+// ============================================================================
+// Edge removal simulation (does deleting a dependency break the cycles?)
+// ============================================================================
+
+/// Result of simulating the removal of a set of edges.
+#[derive(Debug, Clone, Serialize)]
+pub struct EdgeRemovalResult {
+    /// Number of non-trivial SCCs remaining after the removal
+    pub cycle_count: usize,
+    /// Whether any cycles remain
+    pub has_cycles: bool,
+    /// Nodes that were on a cycle before and no longer are
+    pub freed_nodes: Vec<usize>,
+    /// Number of requested removals that don't exist in the graph (ignored)
+    pub ignored_removals: usize,
+}
+
+/// Simulate removing edges and report which cycles disappear.
+///
+/// SCC analysis runs on a masked view of the graph, so neither the graph
+/// nor its adjacency is copied. Removals naming nonexistent edges are
+/// ignored and counted in `ignored_removals`.
+///
+/// # Arguments
+/// * `graph` - The dependency graph
+/// * `removals` - Edges (from, to) to pretend are deleted
+pub fn simulate_edge_removal(graph: &DiGraph, removals: &[(usize, usize)]) -> EdgeRemovalResult {
+    let mut masked: HashSet<(usize, usize)> = HashSet::with_capacity(removals.len());
+    let mut ignored_removals = 0;
+    for &(from, to) in removals {
+        if graph.has_edge(from, to) {
+            masked.insert((from, to));
+        } else {
+            ignored_removals += 1;
+        }
+    }
+
+    let before = cyclic_nodes(graph, &tarjan_scc(graph), |_, _| true);
+    let keep = |from: usize, to: usize| !masked.contains(&(from, to));
+    let after_scc = tarjan_scc_filtered(graph, keep);
+    let after = cyclic_nodes(graph, &after_scc, keep);
+
+    let freed_nodes = (0..graph.len())
+        .filter(|&v| before[v] && !after[v])
+        .collect();
+
+    EdgeRemovalResult {
+        cycle_count: after_scc.cycle_count,
+        has_cycles: after_scc.has_cycles,
+        freed_nodes,
+        ignored_removals,
+    }
+}
+
+/// Mark nodes that lie on a cycle: members of non-trivial SCCs or nodes with a kept self-loop.
+fn cyclic_nodes<F>(graph: &DiGraph, scc: &SCCResult, keep: F) -> Vec<bool>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let mut cyclic = vec![false; graph.len()];
+    for component in &scc.components {
+        for &v in component {
+            cyclic[v] = component.len() > 1 || (graph.has_edge(v, v) && keep(v, v));
+        }
+    }
+    cyclic
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,8 +385,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let closed = vec![false, false, false];
         let result = what_if_close(&graph, a, &closed);
@@ -345,10 +411,10 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let closed = vec![false, false, false, false];
         let result = what_if_close(&graph, a, &closed);
@@ -366,8 +432,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, c);
-        graph.add_edge(b, c);
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         // a is closed
         let closed = vec![true, false, false];
@@ -385,8 +451,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, c);
-        graph.add_edge(b, c);
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let closed = vec![false, false, false];
         let result = what_if_close(&graph, a, &closed);
@@ -416,7 +482,7 @@ mod tests {
         let a = graph.add_node("a");
         for i in 0..5 {
             let b = graph.add_node(&format!("b{}", i));
-            graph.add_edge(a, b);
+            graph.add_edge(a, b).unwrap();
         }
 
         let closed = vec![false; 6];
@@ -434,7 +500,7 @@ mod tests {
         let mut prev = graph.add_node("a");
         for i in 1..6 {
             let node = graph.add_node(&format!("n{}", i));
-            graph.add_edge(prev, node);
+            graph.add_edge(prev, node).unwrap();
             prev = node;
         }
 
@@ -458,10 +524,10 @@ mod tests {
         let d = graph.add_node("d");
         let e = graph.add_node("e");
         let f = graph.add_node("f");
-        graph.add_edge(a, b);
-        graph.add_edge(a, c);
-        graph.add_edge(a, d);
-        graph.add_edge(e, f);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(a, d).unwrap();
+        graph.add_edge(e, f).unwrap();
 
         let closed = vec![false; 6];
         let top = top_what_if(&graph, &closed, 10);
@@ -483,7 +549,7 @@ mod tests {
         for i in 0..10 {
             let a = graph.add_node(&format!("a{}", i));
             let b = graph.add_node(&format!("b{}", i));
-            graph.add_edge(a, b);
+            graph.add_edge(a, b).unwrap();
         }
 
         let closed = vec![false; 20];
@@ -500,8 +566,8 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, c);
-        graph.add_edge(b, c);
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, c).unwrap();
 
         let closed = vec![false, false, false];
         let result = what_if_close_batch(&graph, &[a, b], &closed);
@@ -524,10 +590,10 @@ mod tests {
         let c = graph.add_node("c");
         let d = graph.add_node("d");
         let e = graph.add_node("e");
-        graph.add_edge(a, c);
-        graph.add_edge(b, d);
-        graph.add_edge(c, e);
-        graph.add_edge(d, e);
+        graph.add_edge(a, c).unwrap();
+        graph.add_edge(b, d).unwrap();
+        graph.add_edge(c, e).unwrap();
+        graph.add_edge(d, e).unwrap();
 
         let closed = vec![false; 5];
         let result = what_if_close_batch(&graph, &[a, b], &closed);
@@ -543,7 +609,7 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let _c = graph.add_node("c");
-        graph.add_edge(a, b);
+        graph.add_edge(a, b).unwrap();
 
         let closed = vec![false, false, false];
         let all = all_what_if(&graph, &closed, 10);
@@ -561,9 +627,9 @@ mod tests {
         let a = graph.add_node("a");
         let b = graph.add_node("b");
         let c = graph.add_node("c");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, a);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
 
         let closed = vec![false, false, false];
 
@@ -584,8 +650,8 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let closed = vec![false; 4];
 
@@ -608,9 +674,9 @@ mod tests {
         let b = graph.add_node("b");
         let c = graph.add_node("c");
         let d = graph.add_node("d");
-        graph.add_edge(a, b);
-        graph.add_edge(b, c);
-        graph.add_edge(c, d);
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, d).unwrap();
 
         let closed = vec![false; 4];
         let result = what_if_close(&graph, a, &closed);
@@ -621,4 +687,48 @@ mod tests {
         assert_eq!(result.cascade_ids[1], c);
         assert_eq!(result.cascade_ids[2], d);
     }
+
+    // ========================================================================
+    // Edge removal simulation tests
+    // ========================================================================
+
+    #[test]
+    fn test_simulate_edge_removal_triangle() {
+        // a -> b -> c -> a: removing c -> a breaks the only cycle
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+
+        let result = simulate_edge_removal(&graph, &[(c, a)]);
+        assert!(!result.has_cycles);
+        assert_eq!(result.cycle_count, 0);
+        assert_eq!(result.freed_nodes, vec![a, b, c]);
+        assert_eq!(result.ignored_removals, 0);
+
+        // Graph itself is untouched
+        assert_eq!(graph.edge_count(), 3);
+    }
+
+    #[test]
+    fn test_simulate_edge_removal_still_cyclic() {
+        // a <-> b, b <-> c: 2-edge-connected SCC, one removal leaves cycles
+        let mut graph = DiGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, a).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, b).unwrap();
+
+        let result = simulate_edge_removal(&graph, &[(a, b), (c, a)]);
+        assert!(result.has_cycles);
+        assert_eq!(result.cycle_count, 1);
+        assert_eq!(result.freed_nodes, vec![a]);
+        assert_eq!(result.ignored_removals, 1); // c -> a doesn't exist
+    }
 }
@@ -54,7 +54,7 @@ fn load_test_graph(path: &Path) -> (DiGraph, TestGraphFile) {
         graph.add_node(node_id);
     }
     for (from, to) in &graph_file.edges {
-        graph.add_edge(*from, *to);
+        graph.add_edge(*from, *to).unwrap();
     }
 
     (graph, graph_file)
@@ -498,12 +498,16 @@ fn test_golden_degrees() {
             if let Some(idx) = graph.node_idx(node_id) {
                 let act_out = graph.out_degree(idx) as i32;
                 assert_eq!(act_out, exp_out, "out_degree mismatch for {} in {}", node_id, name);
+                assert_eq!(graph.successors_slice(idx).len() as i32, exp_out);
             }
         }
         for (node_id, &exp_in) in &expected.in_degree {
             if let Some(idx) = graph.node_idx(node_id) {
                 let act_in = graph.in_degree(idx) as i32;
                 assert_eq!(act_in, exp_in, "in_degree mismatch for {} in {}", node_id, name);
+                let preds = graph.predecessors_slice(idx);
+                assert!(preds.iter().all(|&u| graph.has_edge(u as usize, idx)));
+                assert_eq!(preds.len() as i32, exp_in);
             }
         }
     }